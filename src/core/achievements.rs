@@ -0,0 +1,140 @@
+//! Achievements.
+//!
+//! Mini-games report what happens in a run as `StatEvent`s: a counter to add
+//! to ("emeralds collected") or a best to beat ("emeralds in a row"). The
+//! counters live in `PlayerStats`, and every achievement in
+//! `assets/data/achievements.json` unlocks once its stat reaches its goal.
+//! Each unlock is sent as an `AchievementUnlocked` message and saved right
+//! away. Replays and demos don't count.
+
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use super::progression::{PlayerProgress, SaveProgress};
+use super::replay::ReplayPlayback;
+use super::stats::StatEvent;
+
+/// The achievement definitions, embedded at build time.
+const ACHIEVEMENTS_JSON: &str = include_str!("../../assets/data/achievements.json");
+
+/// Plugin for achievement tracking.
+pub struct AchievementsPlugin;
+
+impl Plugin for AchievementsPlugin {
+    fn build(&self, app: &mut App) {
+        let list = AchievementList::bundled().unwrap_or_else(|err| {
+            error!("Could not read the achievements: {}", err);
+            AchievementList::default()
+        });
+        app.insert_resource(list)
+            .add_message::<AchievementUnlocked>()
+            .add_systems(Update, track_achievements);
+    }
+}
+
+// ─── Data ──────────────────────────────────────────────────────────
+
+/// One achievement from the definitions file.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Achievement {
+    /// Stable identifier stored in saves.
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    /// The counter it watches, named `game.counter`.
+    pub stat: String,
+    /// Counter value that unlocks it.
+    pub goal: u64,
+}
+
+/// Every achievement, in gallery order.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct AchievementList(pub Vec<Achievement>);
+
+impl AchievementList {
+    /// The achievements shipped with the game.
+    pub fn bundled() -> Result<AchievementList, serde_json::Error> {
+        serde_json::from_str(ACHIEVEMENTS_JSON).map(AchievementList)
+    }
+
+    /// Apply `event` to `progress`, returning the achievements it unlocked.
+    pub fn record(&self, progress: &mut PlayerProgress, event: &StatEvent) -> Vec<Achievement> {
+        let value = progress.record_stat(event);
+        self.0
+            .iter()
+            .filter(|achievement| achievement.stat == event.stat && value >= achievement.goal)
+            .filter(|achievement| progress.unlock_achievement(&achievement.id))
+            .cloned()
+            .collect()
+    }
+}
+
+// ─── Messages ──────────────────────────────────────────────────────
+
+/// Sent when an achievement unlocks.
+#[derive(Message, Debug, Clone)]
+pub struct AchievementUnlocked(pub Achievement);
+
+// ─── Systems ───────────────────────────────────────────────────────
+
+fn track_achievements(
+    mut events: MessageReader<StatEvent>,
+    list: Res<AchievementList>,
+    mut progress: ResMut<PlayerProgress>,
+    playback: Option<Res<ReplayPlayback>>,
+    mut unlocked: MessageWriter<AchievementUnlocked>,
+    mut save: MessageWriter<SaveProgress>,
+) {
+    if playback.is_some() {
+        events.clear();
+        return;
+    }
+    for event in events.read() {
+        for achievement in list.record(&mut progress, event) {
+            info!("Achievement unlocked: {}", achievement.name);
+            unlocked.write(AchievementUnlocked(achievement));
+            save.write(SaveProgress);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_bundled_achievements_parse() {
+        let list = AchievementList::bundled().unwrap();
+        assert!(list.0.iter().any(|a| a.id == "worm_wars_flawless"));
+        let ids: HashSet<_> = list.0.iter().map(|a| &a.id).collect();
+        assert_eq!(ids.len(), list.0.len());
+    }
+
+    #[test]
+    fn test_stat_reaching_goal_unlocks_once() {
+        let list = AchievementList::bundled().unwrap();
+        let mut progress = PlayerProgress::default();
+
+        let streak = |n| StatEvent::best("tunnel_miner.emerald_streak", n);
+        assert!(list.record(&mut progress, &streak(7)).is_empty());
+        assert!(list.record(&mut progress, &streak(3)).is_empty());
+        let unlocked = list.record(&mut progress, &streak(8));
+        assert_eq!(unlocked.len(), 1);
+        assert_eq!(unlocked[0].id, "tunnel_miner_streak");
+        assert!(list.record(&mut progress, &streak(9)).is_empty());
+        assert_eq!(progress.stat("tunnel_miner.emerald_streak"), 9);
+    }
+
+    #[test]
+    fn test_totals_add_up() {
+        let list = AchievementList::bundled().unwrap();
+        let mut progress = PlayerProgress::default();
+        for _ in 0..24 {
+            list.record(&mut progress, &StatEvent::add("depths_of_doom.kills", 1));
+        }
+        assert!(!progress.has_achievement("depths_of_doom_kills"));
+        list.record(&mut progress, &StatEvent::add("depths_of_doom.kills", 1));
+        assert!(progress.has_achievement("depths_of_doom_kills"));
+    }
+}
@@ -0,0 +1,290 @@
+//! Cosmetics sold for tokens in the timeline hub's shop.
+//!
+//! The catalog only names, prices and slots each item. What an item looks
+//! or sounds like lives with the code that applies it: `CrtPreset::cosmetic`,
+//! `EraPalette::cosmetic`, the mini-games' player skins, the hub music and
+//! the cabinet frame. `PlayerProgress` records what the player owns and has
+//! equipped, at most one item per slot.
+
+use std::fmt;
+
+use super::states::{Era, MiniGameId};
+
+/// Where a cosmetic goes. Equipping an item replaces whatever else was
+/// equipped in its slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CosmeticSlot {
+    /// Replaces the CRT preset on every screen.
+    CrtPreset,
+    /// Replaces the era palette while a mini-game plays.
+    Palette,
+    /// Recolors the player in one mini-game.
+    Skin(MiniGameId),
+    /// Replaces the track playing in the menus and the hub.
+    HubMusic,
+    /// Draws a cabinet around the mini-game screen.
+    CabinetFrame,
+}
+
+impl CosmeticSlot {
+    /// Heading shown above the slot's items in the shop.
+    pub fn label(self) -> &'static str {
+        match self {
+            CosmeticSlot::CrtPreset => "CRT PRESETS",
+            CosmeticSlot::Palette => "PALETTES",
+            CosmeticSlot::Skin(_) => "SKINS",
+            CosmeticSlot::HubMusic => "HUB MUSIC",
+            CosmeticSlot::CabinetFrame => "CABINET FRAMES",
+        }
+    }
+}
+
+/// An item in the shop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cosmetic {
+    /// Stable identifier stored in saves.
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Price in tokens.
+    pub price: u64,
+    pub slot: CosmeticSlot,
+}
+
+const fn game(era: Era, index: u8) -> MiniGameId {
+    MiniGameId { era, index }
+}
+
+/// Everything the shop sells, in shop order.
+pub const CATALOG: &[Cosmetic] = &[
+    // CRT presets
+    Cosmetic {
+        id: "crt_arcade",
+        name: "Arcade Glass",
+        description: "Deep scanlines and a bulging tube, wherever you are",
+        price: 150,
+        slot: CosmeticSlot::CrtPreset,
+    },
+    Cosmetic {
+        id: "crt_trinitron",
+        name: "Flat Trinitron",
+        description: "Flat glass with a fine aperture grille",
+        price: 100,
+        slot: CosmeticSlot::CrtPreset,
+    },
+    // Palettes
+    Cosmetic {
+        id: "palette_pocket",
+        name: "Pocket Green",
+        description: "Four shades of handheld green",
+        price: 200,
+        slot: CosmeticSlot::Palette,
+    },
+    Cosmetic {
+        id: "palette_cga",
+        name: "CGA Cyan & Magenta",
+        description: "The loudest four colors of 1981",
+        price: 150,
+        slot: CosmeticSlot::Palette,
+    },
+    Cosmetic {
+        id: "palette_amber",
+        name: "Amber Monitor",
+        description: "Monochrome amber phosphor",
+        price: 120,
+        slot: CosmeticSlot::Palette,
+    },
+    // Skins
+    Cosmetic {
+        id: "skin_tunnel_miner_gold",
+        name: "Gold Miner",
+        description: "Tunnel Miner: a digger worth its weight",
+        price: 80,
+        slot: CosmeticSlot::Skin(game(Era::The80s, 0)),
+    },
+    Cosmetic {
+        id: "skin_cosmic_captain_crimson",
+        name: "Crimson Captain",
+        description: "Cosmic Captain: a red spacesuit",
+        price: 80,
+        slot: CosmeticSlot::Skin(game(Era::The80s, 1)),
+    },
+    Cosmetic {
+        id: "skin_star_goose_phoenix",
+        name: "Phoenix Ship",
+        description: "Star Goose: a ship painted in flames",
+        price: 80,
+        slot: CosmeticSlot::Skin(game(Era::The80s, 2)),
+    },
+    Cosmetic {
+        id: "skin_worm_wars_toxic",
+        name: "Toxic Worms",
+        description: "Worm Wars: your team glows green",
+        price: 80,
+        slot: CosmeticSlot::Skin(game(Era::The90s, 0)),
+    },
+    Cosmetic {
+        id: "skin_ice_blitz_gold",
+        name: "Golden Skates",
+        description: "Ice Blitz: a championship jersey",
+        price: 80,
+        slot: CosmeticSlot::Skin(game(Era::The90s, 1)),
+    },
+    Cosmetic {
+        id: "skin_depths_of_doom_shadow",
+        name: "Shadow Marine",
+        description: "Depths of Doom: armor for the dark",
+        price: 80,
+        slot: CosmeticSlot::Skin(game(Era::The90s, 2)),
+    },
+    Cosmetic {
+        id: "skin_nebula_bouncer_solar",
+        name: "Solar Hull",
+        description: "Nebula Bouncer: a ship that burns bright",
+        price: 80,
+        slot: CosmeticSlot::Skin(game(Era::Future, 0)),
+    },
+    // Hub music
+    Cosmetic {
+        id: "music_popcorn_rush",
+        name: "Pixel Popcorn Rush",
+        description: "A faster tune for the menus and the hub",
+        price: 100,
+        slot: CosmeticSlot::HubMusic,
+    },
+    // Cabinet frames
+    Cosmetic {
+        id: "frame_woodgrain",
+        name: "Woodgrain Cabinet",
+        description: "Walnut veneer, like the den in 1983",
+        price: 120,
+        slot: CosmeticSlot::CabinetFrame,
+    },
+    Cosmetic {
+        id: "frame_chrome",
+        name: "Chrome Bezel",
+        description: "Polished metal around the glass",
+        price: 120,
+        slot: CosmeticSlot::CabinetFrame,
+    },
+    Cosmetic {
+        id: "frame_neon",
+        name: "Neon Trim",
+        description: "A glowing magenta outline",
+        price: 150,
+        slot: CosmeticSlot::CabinetFrame,
+    },
+];
+
+/// Look up a catalog item by id.
+pub fn cosmetic(id: &str) -> Option<&'static Cosmetic> {
+    CATALOG.iter().find(|cosmetic| cosmetic.id == id)
+}
+
+/// The catalog items that go in `slot`.
+pub fn in_slot(slot: CosmeticSlot) -> impl Iterator<Item = &'static Cosmetic> {
+    CATALOG.iter().filter(move |cosmetic| cosmetic.slot == slot)
+}
+
+// ─── Errors ────────────────────────────────────────────────────────
+
+/// Why a cosmetic could not be bought.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PurchaseError {
+    /// The player already owns it.
+    AlreadyOwned,
+    /// The player has fewer tokens than it costs.
+    NotEnoughTokens { price: u64, tokens: u64 },
+}
+
+impl fmt::Display for PurchaseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PurchaseError::AlreadyOwned => write!(f, "already owned"),
+            PurchaseError::NotEnoughTokens { price, tokens } => {
+                write!(f, "costs {price} tokens, you have {tokens}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PurchaseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::progression::PlayerProgress;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_catalog_ids_are_unique() {
+        let ids: HashSet<_> = CATALOG.iter().map(|cosmetic| cosmetic.id).collect();
+        assert_eq!(ids.len(), CATALOG.len());
+    }
+
+    #[test]
+    fn test_every_cosmetic_has_a_look() {
+        use crate::effects::crt::CrtPreset;
+        use crate::effects::presentation::EraPalette;
+        use crate::eras::shared::skin_tint;
+        use crate::ui::cabinet::CabinetFrame;
+        use crate::ui::music::hub_track;
+
+        for cosmetic in CATALOG {
+            let applied = match cosmetic.slot {
+                CosmeticSlot::CrtPreset => CrtPreset::cosmetic(cosmetic.id).is_some(),
+                CosmeticSlot::Palette => EraPalette::cosmetic(cosmetic.id).is_some(),
+                CosmeticSlot::Skin(_) => skin_tint(cosmetic.id).is_some(),
+                CosmeticSlot::HubMusic => hub_track(cosmetic.id).is_some(),
+                CosmeticSlot::CabinetFrame => CabinetFrame::cosmetic(cosmetic.id).is_some(),
+            };
+            assert!(applied, "{} is never applied", cosmetic.id);
+        }
+    }
+
+    #[test]
+    fn test_buying_spends_tokens_once() {
+        let arcade = cosmetic("crt_arcade").unwrap();
+        let mut progress = PlayerProgress {
+            tokens: 200,
+            ..Default::default()
+        };
+
+        assert_eq!(progress.buy(arcade), Ok(()));
+        assert_eq!(progress.tokens, 50);
+        assert!(progress.owns(arcade));
+        assert_eq!(progress.equipped(CosmeticSlot::CrtPreset), Some(arcade));
+        assert_eq!(progress.buy(arcade), Err(PurchaseError::AlreadyOwned));
+        assert_eq!(
+            progress.buy(cosmetic("crt_trinitron").unwrap()),
+            Err(PurchaseError::NotEnoughTokens {
+                price: 100,
+                tokens: 50
+            })
+        );
+        assert_eq!(progress.tokens, 50);
+    }
+
+    #[test]
+    fn test_equipping_replaces_the_slot() {
+        let woodgrain = cosmetic("frame_woodgrain").unwrap();
+        let neon = cosmetic("frame_neon").unwrap();
+        let mut progress = PlayerProgress {
+            tokens: 1000,
+            ..Default::default()
+        };
+        progress.buy(woodgrain).unwrap();
+        progress.buy(neon).unwrap();
+        assert_eq!(progress.equipped(CosmeticSlot::CabinetFrame), Some(neon));
+
+        assert!(progress.equip(woodgrain));
+        assert_eq!(
+            progress.equipped(CosmeticSlot::CabinetFrame),
+            Some(woodgrain)
+        );
+        assert!(!progress.equip(cosmetic("palette_amber").unwrap()));
+
+        progress.unequip(woodgrain);
+        assert_eq!(progress.equipped(CosmeticSlot::CabinetFrame), None);
+    }
+}
@@ -1,4 +1,16 @@
+use bevy::app::{RunFixedMainLoop, RunFixedMainLoopSystems};
+use bevy::input::InputSystems;
 use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use super::progression::DataDir;
+use super::save::{SaveError, write_atomic};
 
 /// Plugin for unified input handling across keyboard, gamepad, etc.
 pub struct InputPlugin;
@@ -6,12 +18,277 @@ pub struct InputPlugin;
 impl Plugin for InputPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<GameInput>()
-            .add_systems(PreUpdate, update_game_input);
+            .init_resource::<InputBuffer>()
+            .init_resource::<InputBindings>()
+            .init_resource::<FixedTickInput>()
+            .add_systems(Startup, load_bindings)
+            .add_systems(
+                PreUpdate,
+                (
+                    update_game_input.in_set(GameInputSystems::Read),
+                    record_input_buffer.in_set(GameInputSystems::Derive),
+                ),
+            )
+            .configure_sets(
+                PreUpdate,
+                (GameInputSystems::Read, GameInputSystems::Derive)
+                    .chain()
+                    .after(InputSystems),
+            )
+            .add_systems(
+                RunFixedMainLoop,
+                (
+                    begin_fixed_ticks.in_set(RunFixedMainLoopSystems::BeforeFixedMainLoop),
+                    end_fixed_ticks.in_set(RunFixedMainLoopSystems::AfterFixedMainLoop),
+                ),
+            )
+            .add_systems(FixedFirst, prepare_tick_input)
+            .add_systems(
+                Last,
+                save_bindings.run_if(
+                    resource_changed::<InputBindings>.and(not(resource_added::<InputBindings>)),
+                ),
+            );
     }
 }
 
+/// File name of the bindings file inside the data directory.
+pub const BINDINGS_FILE: &str = "bindings.json";
+
+/// Default radial deadzone applied to the left stick.
+pub const DEFAULT_STICK_DEADZONE: f32 = 0.2;
+
+/// Stick deflection past which a direction also counts as a pressed action.
+const STICK_DIGITAL_THRESHOLD: f32 = 0.5;
+
+// ─── Actions ───────────────────────────────────────────────────────
+
+/// A bindable game action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum GameAction {
+    Up,
+    Down,
+    Left,
+    Right,
+    Confirm,
+    Cancel,
+    ActionA,
+    ActionB,
+    Pause,
+    Jump,
+    Fire,
+    WeaponCycle,
+    Skill,
+}
+
+impl GameAction {
+    /// Every action, in the order shown on the bindings screen.
+    pub const ALL: [GameAction; 13] = [
+        GameAction::Up,
+        GameAction::Down,
+        GameAction::Left,
+        GameAction::Right,
+        GameAction::Confirm,
+        GameAction::Cancel,
+        GameAction::ActionA,
+        GameAction::ActionB,
+        GameAction::Pause,
+        GameAction::Jump,
+        GameAction::Fire,
+        GameAction::WeaponCycle,
+        GameAction::Skill,
+    ];
+}
+
+/// One physical input an action can be bound to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InputBinding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    Pad(GamepadButton),
+}
+
+// ─── Bindings ──────────────────────────────────────────────────────
+
+/// Player-editable mapping from actions to inputs, saved to `bindings.json`.
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InputBindings {
+    pub actions: BTreeMap<GameAction, Vec<InputBinding>>,
+    /// Radial deadzone for the left stick, from 0 to 1.
+    pub stick_deadzone: f32,
+}
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        use GameAction::*;
+        use InputBinding::{Key, Mouse, Pad};
+
+        let actions = [
+            (
+                Up,
+                vec![
+                    Key(KeyCode::KeyW),
+                    Key(KeyCode::ArrowUp),
+                    Pad(GamepadButton::DPadUp),
+                ],
+            ),
+            (
+                Down,
+                vec![
+                    Key(KeyCode::KeyS),
+                    Key(KeyCode::ArrowDown),
+                    Pad(GamepadButton::DPadDown),
+                ],
+            ),
+            (
+                Left,
+                vec![
+                    Key(KeyCode::KeyA),
+                    Key(KeyCode::ArrowLeft),
+                    Pad(GamepadButton::DPadLeft),
+                ],
+            ),
+            (
+                Right,
+                vec![
+                    Key(KeyCode::KeyD),
+                    Key(KeyCode::ArrowRight),
+                    Pad(GamepadButton::DPadRight),
+                ],
+            ),
+            (
+                Confirm,
+                vec![
+                    Key(KeyCode::Enter),
+                    Key(KeyCode::Space),
+                    Pad(GamepadButton::South),
+                ],
+            ),
+            (Cancel, vec![Key(KeyCode::Escape), Pad(GamepadButton::East)]),
+            (ActionA, vec![Key(KeyCode::Space), Pad(GamepadButton::West)]),
+            (
+                ActionB,
+                vec![
+                    Key(KeyCode::ShiftLeft),
+                    Key(KeyCode::ShiftRight),
+                    Pad(GamepadButton::North),
+                ],
+            ),
+            (
+                Pause,
+                vec![
+                    Key(KeyCode::Escape),
+                    Key(KeyCode::KeyP),
+                    Pad(GamepadButton::Start),
+                ],
+            ),
+            (
+                Jump,
+                vec![
+                    Key(KeyCode::KeyW),
+                    Key(KeyCode::ArrowUp),
+                    Pad(GamepadButton::South),
+                ],
+            ),
+            (
+                Fire,
+                vec![
+                    Key(KeyCode::Space),
+                    Key(KeyCode::F1),
+                    Mouse(MouseButton::Left),
+                    Pad(GamepadButton::West),
+                    Pad(GamepadButton::RightTrigger2),
+                ],
+            ),
+            (
+                WeaponCycle,
+                vec![Key(KeyCode::KeyQ), Pad(GamepadButton::RightTrigger)],
+            ),
+            (
+                Skill,
+                vec![Key(KeyCode::KeyE), Pad(GamepadButton::LeftTrigger)],
+            ),
+        ];
+
+        Self {
+            actions: actions.into_iter().collect(),
+            stick_deadzone: DEFAULT_STICK_DEADZONE,
+        }
+    }
+}
+
+impl InputBindings {
+    /// Inputs bound to `action`.
+    pub fn bindings(&self, action: GameAction) -> &[InputBinding] {
+        self.actions.get(&action).map_or(&[], Vec::as_slice)
+    }
+
+    /// Add `binding` to `action` if it isn't bound there already.
+    pub fn bind(&mut self, action: GameAction, binding: InputBinding) {
+        let bound = self.actions.entry(action).or_default();
+        if !bound.contains(&binding) {
+            bound.push(binding);
+        }
+    }
+
+    /// Remove `binding` from `action`.
+    pub fn unbind(&mut self, action: GameAction, binding: InputBinding) {
+        if let Some(bound) = self.actions.get_mut(&action) {
+            bound.retain(|b| *b != binding);
+        }
+    }
+
+    /// Make `binding` the only input of its kind (key, mouse button or pad
+    /// button) for `action`, keeping bindings on the other devices.
+    pub fn rebind(&mut self, action: GameAction, binding: InputBinding) {
+        let bound = self.actions.entry(action).or_default();
+        bound.retain(|b| std::mem::discriminant(b) != std::mem::discriminant(&binding));
+        bound.push(binding);
+    }
+
+    /// Restore the default inputs for `action`.
+    pub fn reset_action(&mut self, action: GameAction) {
+        let defaults = InputBindings::default().bindings(action).to_vec();
+        self.actions.insert(action, defaults);
+    }
+
+    /// Read bindings from `path`. Returns `Ok(None)` if it doesn't exist.
+    pub fn read(path: &Path) -> Result<Option<InputBindings>, SaveError> {
+        match fs::read_to_string(path) {
+            Ok(json) => Ok(Some(serde_json::from_str(&json)?)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Write bindings to `path` atomically.
+    pub fn write(&self, path: &Path) -> Result<(), SaveError> {
+        let json = serde_json::to_string_pretty(self)?;
+        write_atomic(path, &json)?;
+        Ok(())
+    }
+}
+
+/// Where the bindings file lives.
+pub fn bindings_path(dir: &Path) -> PathBuf {
+    dir.join(BINDINGS_FILE)
+}
+
+// ─── Game input ────────────────────────────────────────────────────
+
+/// Stages of building `GameInput` in `PreUpdate`. Systems that replace the
+/// device input (replay playback) run between `Read` and `Derive`.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum GameInputSystems {
+    /// Devices are read into `GameInput`.
+    Read,
+    /// State derived from the final `GameInput`, such as `InputBuffer`.
+    Derive,
+}
+
 /// Unified input state — abstracts keyboard and gamepad into game actions.
-#[derive(Resource, Debug, Default)]
+#[derive(Resource, Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 pub struct GameInput {
     // Directional
     pub move_direction: Vec2,
@@ -27,46 +304,444 @@ pub struct GameInput {
     pub action_b: bool, // Shift / Y button
     pub pause: bool,    // Escape / Start
 
-    // Mouse (for menus and some games)
+    // Aiming: right stick after the deadzone, zero when centred
+    pub aim_stick: Vec2,
+
+    // Mouse (for menus and some games), in primary window coordinates
     pub mouse_position: Vec2,
     pub mouse_click: bool,
+
+    pressed: HashSet<GameAction>,
+    just_pressed: HashSet<GameAction>,
 }
 
-fn update_game_input(keyboard: Res<ButtonInput<KeyCode>>, mut input: ResMut<GameInput>) {
-    // Directional movement (WASD + Arrow keys)
-    let mut dir = Vec2::ZERO;
-    if keyboard.pressed(KeyCode::KeyW) || keyboard.pressed(KeyCode::ArrowUp) {
-        dir.y += 1.0;
+impl GameInput {
+    /// Whether `action` is held this frame.
+    pub fn pressed(&self, action: GameAction) -> bool {
+        self.pressed.contains(&action)
+    }
+
+    /// Whether `action` started being held this frame.
+    pub fn just_pressed(&self, action: GameAction) -> bool {
+        self.just_pressed.contains(&action)
     }
-    if keyboard.pressed(KeyCode::KeyS) || keyboard.pressed(KeyCode::ArrowDown) {
-        dir.y -= 1.0;
+
+    /// Digital left/right axis: -1, 0 or 1. Unlike `move_direction.x` it is
+    /// not scaled down when a vertical direction is held too.
+    pub fn horizontal(&self) -> f32 {
+        match (
+            self.pressed(GameAction::Left),
+            self.pressed(GameAction::Right),
+        ) {
+            (true, false) => -1.0,
+            (false, true) => 1.0,
+            _ => 0.0,
+        }
     }
-    if keyboard.pressed(KeyCode::KeyA) || keyboard.pressed(KeyCode::ArrowLeft) {
-        dir.x -= 1.0;
+
+    /// Hold exactly `held` this frame, as a script or bot would instead of
+    /// reading devices. Actions not held last frame count as just pressed.
+    pub fn hold(&mut self, held: &[GameAction]) {
+        let previous = std::mem::take(&mut self.pressed);
+        self.pressed.extend(held.iter().copied());
+        self.just_pressed = self.pressed.difference(&previous).copied().collect();
+        self.aim_stick = Vec2::ZERO;
+        self.mouse_click = false;
+        self.derive_fields(Vec2::ZERO);
     }
-    if keyboard.pressed(KeyCode::KeyD) || keyboard.pressed(KeyCode::ArrowRight) {
-        dir.x += 1.0;
+
+    /// Fill the per-field view (`move_direction`, `just_up`, `confirm`, ...)
+    /// from the action sets. `stick` overrides the digital direction.
+    fn derive_fields(&mut self, stick: Vec2) {
+        let digital: Vec2 = [
+            (GameAction::Up, Vec2::Y),
+            (GameAction::Down, Vec2::NEG_Y),
+            (GameAction::Left, Vec2::NEG_X),
+            (GameAction::Right, Vec2::X),
+        ]
+        .into_iter()
+        .filter(|(action, _)| self.pressed(*action))
+        .map(|(_, direction)| direction)
+        .sum();
+
+        // Directional movement: analog stick wins over digital inputs.
+        self.move_direction = if stick != Vec2::ZERO {
+            stick
+        } else {
+            digital.normalize_or_zero()
+        };
+
+        self.derive_edges();
     }
-    input.move_direction = if dir.length_squared() > 0.0 {
-        dir.normalize()
-    } else {
-        Vec2::ZERO
+
+    /// Recompute the `just_*` and one-shot action fields from `just_pressed`.
+    fn derive_edges(&mut self) {
+        // Just pressed directionals
+        self.just_up = self.just_pressed(GameAction::Up);
+        self.just_down = self.just_pressed(GameAction::Down);
+        self.just_left = self.just_pressed(GameAction::Left);
+        self.just_right = self.just_pressed(GameAction::Right);
+
+        // Actions
+        self.confirm = self.just_pressed(GameAction::Confirm);
+        self.cancel = self.just_pressed(GameAction::Cancel);
+        self.action_a = self.just_pressed(GameAction::ActionA);
+        self.action_b = self.just_pressed(GameAction::ActionB);
+        self.pause = self.just_pressed(GameAction::Pause);
+    }
+}
+
+/// Rescale `stick` so the deadzone maps to zero and full deflection to one.
+pub fn apply_deadzone(stick: Vec2, deadzone: f32) -> Vec2 {
+    let length = stick.length();
+    if length <= deadzone {
+        return Vec2::ZERO;
+    }
+    let scaled = ((length - deadzone) / (1.0 - deadzone).max(f32::EPSILON)).min(1.0);
+    stick / length * scaled
+}
+
+fn stick_pushes(action: GameAction, stick: Vec2) -> bool {
+    match action {
+        GameAction::Up => stick.y > STICK_DIGITAL_THRESHOLD,
+        GameAction::Down => stick.y < -STICK_DIGITAL_THRESHOLD,
+        GameAction::Left => stick.x < -STICK_DIGITAL_THRESHOLD,
+        GameAction::Right => stick.x > STICK_DIGITAL_THRESHOLD,
+        _ => false,
+    }
+}
+
+fn update_game_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    gamepads: Query<&Gamepad>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    bindings: Res<InputBindings>,
+    mut input: ResMut<GameInput>,
+) {
+    let held = |binding: &InputBinding| match *binding {
+        InputBinding::Key(key) => keyboard.pressed(key),
+        InputBinding::Mouse(button) => mouse.pressed(button),
+        InputBinding::Pad(button) => gamepads.iter().any(|pad| pad.pressed(button)),
+    };
+    let tapped = |binding: &InputBinding| match *binding {
+        InputBinding::Key(key) => keyboard.just_pressed(key),
+        InputBinding::Mouse(button) => mouse.just_pressed(button),
+        InputBinding::Pad(button) => gamepads.iter().any(|pad| pad.just_pressed(button)),
     };
 
-    // Just pressed directionals
-    input.just_up = keyboard.just_pressed(KeyCode::KeyW) || keyboard.just_pressed(KeyCode::ArrowUp);
-    input.just_down =
-        keyboard.just_pressed(KeyCode::KeyS) || keyboard.just_pressed(KeyCode::ArrowDown);
-    input.just_left =
-        keyboard.just_pressed(KeyCode::KeyA) || keyboard.just_pressed(KeyCode::ArrowLeft);
-    input.just_right =
-        keyboard.just_pressed(KeyCode::KeyD) || keyboard.just_pressed(KeyCode::ArrowRight);
+    // First pad whose stick is outside the deadzone drives analog movement.
+    let stick = gamepads
+        .iter()
+        .map(|pad| apply_deadzone(pad.left_stick(), bindings.stick_deadzone))
+        .find(|stick| *stick != Vec2::ZERO)
+        .unwrap_or(Vec2::ZERO);
 
-    // Actions
-    input.confirm = keyboard.just_pressed(KeyCode::Enter) || keyboard.just_pressed(KeyCode::Space);
-    input.cancel = keyboard.just_pressed(KeyCode::Escape);
-    input.action_a = keyboard.just_pressed(KeyCode::Space);
-    input.action_b =
-        keyboard.just_pressed(KeyCode::ShiftLeft) || keyboard.just_pressed(KeyCode::ShiftRight);
-    input.pause = keyboard.just_pressed(KeyCode::Escape);
+    input.aim_stick = gamepads
+        .iter()
+        .map(|pad| apply_deadzone(pad.right_stick(), bindings.stick_deadzone))
+        .find(|stick| *stick != Vec2::ZERO)
+        .unwrap_or(Vec2::ZERO);
+
+    let previous = std::mem::take(&mut input.pressed);
+    input.just_pressed.clear();
+    for action in GameAction::ALL {
+        let bound = bindings.bindings(action);
+        if bound.iter().any(held) || stick_pushes(action, stick) {
+            input.pressed.insert(action);
+            if !previous.contains(&action) || bound.iter().any(tapped) {
+                input.just_pressed.insert(action);
+            }
+        }
+    }
+
+    input.derive_fields(stick);
+
+    // Mouse
+    if let Ok(window) = windows.single()
+        && let Some(position) = window.cursor_position()
+    {
+        input.mouse_position = position;
+    }
+    input.mouse_click = mouse.just_pressed(MouseButton::Left);
+}
+
+// ─── Buffering ─────────────────────────────────────────────────────
+
+/// Remembers when each action was last pressed, so a press made slightly
+/// too early (before landing, before the next grid step) still counts.
+#[derive(Resource, Debug, Default)]
+pub struct InputBuffer {
+    now: Duration,
+    presses: HashMap<GameAction, Duration>,
+}
+
+impl InputBuffer {
+    /// Time since `action` was last pressed, if it is still buffered.
+    pub fn since_pressed(&self, action: GameAction) -> Option<Duration> {
+        self.presses
+            .get(&action)
+            .map(|pressed_at| self.now.saturating_sub(*pressed_at))
+    }
+
+    /// Whether `action` was pressed within the last `window`.
+    pub fn pressed_within(&self, action: GameAction, window: Duration) -> bool {
+        self.since_pressed(action)
+            .is_some_and(|elapsed| elapsed <= window)
+    }
+
+    /// Forget the buffered press of `action` once it has been acted on.
+    pub fn consume(&mut self, action: GameAction) {
+        self.presses.remove(&action);
+    }
+
+    /// Record a press of `action` at `now`. Normally done from `GameInput`.
+    pub fn record(&mut self, action: GameAction, now: Duration) {
+        self.now = self.now.max(now);
+        self.presses.insert(action, now);
+    }
+
+    /// Advance the buffer's clock.
+    pub fn tick(&mut self, now: Duration) {
+        self.now = now;
+    }
+}
+
+/// A condition that stays true for a short while after it stops holding,
+/// e.g. "grounded" for coyote time.
+#[derive(Debug, Clone, Copy)]
+pub struct GraceWindow {
+    window: Duration,
+    remaining: Duration,
+}
+
+impl GraceWindow {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            remaining: Duration::ZERO,
+        }
+    }
+
+    /// Refill the window while `active`, otherwise let it run down.
+    pub fn update(&mut self, active: bool, delta: Duration) {
+        self.remaining = if active {
+            self.window
+        } else {
+            self.remaining.saturating_sub(delta)
+        };
+    }
+
+    /// Whether the condition held recently enough.
+    pub fn is_open(&self) -> bool {
+        !self.remaining.is_zero()
+    }
+
+    /// Close the window early, e.g. once the jump it allowed has happened.
+    pub fn close(&mut self) {
+        self.remaining = Duration::ZERO;
+    }
+}
+
+fn record_input_buffer(time: Res<Time>, input: Res<GameInput>, mut buffer: ResMut<InputBuffer>) {
+    let now = time.elapsed();
+    buffer.tick(now);
+    for action in GameAction::ALL {
+        if input.just_pressed(action) {
+            buffer.record(action, now);
+        }
+    }
+}
+
+// ─── Fixed ticks ───────────────────────────────────────────────────
+
+/// `GameInput` as gameplay in `FixedUpdate` sees it. A frame may run
+/// several ticks or none, so each press is handed to the first tick after
+/// it rather than to every tick of its frame, and is never dropped.
+#[derive(Resource, Debug, Default)]
+struct FixedTickInput {
+    /// Input read this frame, restored for `Update` once the ticks are done.
+    frame: GameInput,
+    /// Presses no tick has seen yet.
+    pending: HashSet<GameAction>,
+    pending_click: bool,
+}
+
+fn begin_fixed_ticks(
+    time: Res<Time<Virtual>>,
+    input: Res<GameInput>,
+    mut fixed: ResMut<FixedTickInput>,
+) {
+    fixed.frame = input.clone();
+    // Presses made while the game is paused belong to the pause menu.
+    if time.is_paused() {
+        fixed.pending.clear();
+        fixed.pending_click = false;
+        return;
+    }
+    fixed.pending.extend(input.just_pressed.iter().copied());
+    fixed.pending_click |= input.mouse_click;
+}
+
+fn prepare_tick_input(mut fixed: ResMut<FixedTickInput>, mut input: ResMut<GameInput>) {
+    let mut tick = fixed.frame.clone();
+    tick.just_pressed = std::mem::take(&mut fixed.pending);
+    tick.mouse_click = std::mem::take(&mut fixed.pending_click);
+    tick.derive_edges();
+    *input = tick;
+}
+
+fn end_fixed_ticks(fixed: Res<FixedTickInput>, mut input: ResMut<GameInput>) {
+    *input = fixed.frame.clone();
+}
+
+// ─── Persistence ───────────────────────────────────────────────────
+
+fn load_bindings(dir: Res<DataDir>, mut bindings: ResMut<InputBindings>) {
+    match InputBindings::read(&bindings_path(&dir.0)) {
+        Ok(Some(loaded)) => *bindings = loaded,
+        Ok(None) => {}
+        Err(err) => error!("Could not load input bindings, using defaults: {}", err),
+    }
+}
+
+fn save_bindings(dir: Res<DataDir>, bindings: Res<InputBindings>) {
+    if let Err(err) = bindings.write(&bindings_path(&dir.0)) {
+        error!("Failed to save input bindings: {}", err);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deadzone_zeroes_small_deflection() {
+        assert_eq!(apply_deadzone(Vec2::new(0.1, 0.1), 0.2), Vec2::ZERO);
+        let full = apply_deadzone(Vec2::new(1.0, 0.0), 0.2);
+        assert!((full.x - 1.0).abs() < 1e-6);
+        let half = apply_deadzone(Vec2::new(0.0, -0.6), 0.2);
+        assert!((half.y + 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_buffer_remembers_recent_presses() {
+        let mut buffer = InputBuffer::default();
+        buffer.record(GameAction::Jump, Duration::from_millis(1000));
+        buffer.tick(Duration::from_millis(1080));
+
+        assert!(buffer.pressed_within(GameAction::Jump, Duration::from_millis(100)));
+        assert!(!buffer.pressed_within(GameAction::Jump, Duration::from_millis(50)));
+        assert!(!buffer.pressed_within(GameAction::Fire, Duration::from_millis(100)));
+
+        buffer.consume(GameAction::Jump);
+        assert!(!buffer.pressed_within(GameAction::Jump, Duration::from_millis(100)));
+    }
+
+    #[test]
+    fn test_grace_window_runs_down() {
+        let mut grounded = GraceWindow::new(Duration::from_millis(100));
+        assert!(!grounded.is_open());
+
+        grounded.update(true, Duration::from_millis(16));
+        grounded.update(false, Duration::from_millis(60));
+        assert!(grounded.is_open());
+        grounded.update(false, Duration::from_millis(60));
+        assert!(!grounded.is_open());
+
+        grounded.update(true, Duration::from_millis(16));
+        grounded.close();
+        assert!(!grounded.is_open());
+    }
+
+    #[test]
+    fn test_press_reaches_exactly_one_fixed_tick() {
+        #[derive(Resource, Default)]
+        struct Shots(u32);
+
+        // Four frames per tick, so the press lands on a frame without one.
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<GameInput>()
+            .init_resource::<FixedTickInput>()
+            .init_resource::<Shots>()
+            .insert_resource(Time::<Fixed>::from_hz(60.0))
+            .insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(
+                Duration::from_secs_f64(1.0 / 240.0),
+            ))
+            .add_systems(
+                RunFixedMainLoop,
+                (
+                    begin_fixed_ticks.in_set(RunFixedMainLoopSystems::BeforeFixedMainLoop),
+                    end_fixed_ticks.in_set(RunFixedMainLoopSystems::AfterFixedMainLoop),
+                ),
+            )
+            .add_systems(FixedFirst, prepare_tick_input)
+            .add_systems(
+                FixedUpdate,
+                |input: Res<GameInput>, mut shots: ResMut<Shots>| {
+                    if input.just_pressed(GameAction::Fire) {
+                        shots.0 += 1;
+                    }
+                },
+            );
+
+        app.update();
+        for _ in 0..12 {
+            app.world_mut()
+                .resource_mut::<GameInput>()
+                .hold(&[GameAction::Fire]);
+            app.update();
+        }
+
+        assert_eq!(app.world().resource::<Shots>().0, 1);
+        assert!(
+            app.world()
+                .resource::<GameInput>()
+                .pressed(GameAction::Fire)
+        );
+    }
+
+    #[test]
+    fn test_bindings_round_trip() {
+        let mut bindings = InputBindings::default();
+        bindings.bind(GameAction::ActionA, InputBinding::Mouse(MouseButton::Left));
+        bindings.unbind(GameAction::Pause, InputBinding::Key(KeyCode::Escape));
+
+        let json = serde_json::to_string(&bindings).unwrap();
+        let loaded: InputBindings = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(loaded, bindings);
+        assert!(
+            loaded
+                .bindings(GameAction::ActionA)
+                .contains(&InputBinding::Mouse(MouseButton::Left))
+        );
+    }
+
+    #[test]
+    fn test_rebind_replaces_only_the_same_device() {
+        let mut bindings = InputBindings::default();
+        bindings.rebind(GameAction::Fire, InputBinding::Key(KeyCode::KeyJ));
+
+        assert_eq!(
+            bindings.bindings(GameAction::Fire),
+            [
+                InputBinding::Mouse(MouseButton::Left),
+                InputBinding::Pad(GamepadButton::West),
+                InputBinding::Pad(GamepadButton::RightTrigger2),
+                InputBinding::Key(KeyCode::KeyJ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_missing_fields_use_defaults() {
+        let loaded: InputBindings = serde_json::from_str(r#"{"stick_deadzone":0.3}"#).unwrap();
+        assert_eq!(loaded.stick_deadzone, 0.3);
+        assert_eq!(
+            loaded.bindings(GameAction::Confirm),
+            InputBindings::default().bindings(GameAction::Confirm)
+        );
+    }
 }
@@ -0,0 +1,179 @@
+//! Arcade-style top-10 tables, one per mini-game.
+//!
+//! Tables are kept in `PlayerProgress`, so they are saved with the rest of
+//! the profile. Each entry records the player's three initials, the score,
+//! when it was set, the run's seed and, once the run's recording has been
+//! kept, the name of its replay file.
+
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Entries kept per table.
+pub const TABLE_SIZE: usize = 10;
+
+/// Letters in a set of initials.
+pub const INITIALS_LEN: usize = 3;
+
+/// Characters the initials entry cycles through.
+pub const INITIALS_ALPHABET: &[char] = &[
+    'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R', 'S',
+    'T', 'U', 'V', 'W', 'X', 'Y', 'Z', '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', '.', ' ',
+];
+
+/// Initials used until the player has entered their own.
+pub const DEFAULT_INITIALS: &str = "AAA";
+
+/// One line of a table.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LeaderboardEntry {
+    pub initials: String,
+    pub score: u64,
+    /// When the score was set, in seconds since the Unix epoch.
+    pub date: u64,
+    pub seed: u64,
+    /// File name of the run's replay, under the replays directory.
+    #[serde(default)]
+    pub replay: Option<String>,
+}
+
+/// A mini-game's table, best score first.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Leaderboard(Vec<LeaderboardEntry>);
+
+impl Leaderboard {
+    /// The entries, best first.
+    pub fn entries(&self) -> &[LeaderboardEntry] {
+        &self.0
+    }
+
+    /// The entry at `rank` (0 is the top).
+    pub fn get_mut(&mut self, rank: usize) -> Option<&mut LeaderboardEntry> {
+        self.0.get_mut(rank)
+    }
+
+    /// Check if `score` would make it onto the table.
+    pub fn qualifies(&self, score: u64) -> bool {
+        score > 0
+            && (self.0.len() < TABLE_SIZE || self.0.last().is_some_and(|last| score > last.score))
+    }
+
+    /// Add an entry below any earlier entries with the same score, and drop
+    /// whatever falls off the bottom. Returns `None` if it didn't qualify.
+    pub fn insert(&mut self, entry: LeaderboardEntry) -> Option<Placement> {
+        if !self.qualifies(entry.score) {
+            return None;
+        }
+        let rank = self.0.partition_point(|other| other.score >= entry.score);
+        self.0.insert(rank, entry);
+        let bumped = if self.0.len() > TABLE_SIZE {
+            self.0.pop()
+        } else {
+            None
+        };
+        Some(Placement { rank, bumped })
+    }
+}
+
+/// Where a new entry landed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Placement {
+    /// 0 is the top of the table.
+    pub rank: usize,
+    /// The entry pushed off the bottom to make room.
+    pub bumped: Option<LeaderboardEntry>,
+}
+
+/// Seconds since the Unix epoch, for `LeaderboardEntry::date`.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+/// `date` as `YYYY-MM-DD`, in UTC.
+pub fn format_date(date: u64) -> String {
+    // Civil-from-days, from Howard Hinnant's date algorithms.
+    let days = (date / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// `letter` moved `steps` places through `INITIALS_ALPHABET`, wrapping
+/// around. Letters outside the alphabet start from 'A'.
+pub fn cycle_letter(letter: char, steps: i32) -> char {
+    let len = INITIALS_ALPHABET.len() as i64;
+    let index = INITIALS_ALPHABET
+        .iter()
+        .position(|c| *c == letter)
+        .unwrap_or(0) as i64;
+    INITIALS_ALPHABET[(index + i64::from(steps)).rem_euclid(len) as usize]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(initials: &str, score: u64) -> LeaderboardEntry {
+        LeaderboardEntry {
+            initials: initials.to_string(),
+            score,
+            date: 0,
+            seed: 0,
+            replay: None,
+        }
+    }
+
+    #[test]
+    fn test_table_keeps_the_best_ten() {
+        let mut table = Leaderboard::default();
+        for score in 1..=12 {
+            table.insert(entry("AAA", score * 100));
+        }
+        let scores: Vec<_> = table.entries().iter().map(|e| e.score).collect();
+        assert_eq!(scores, (3..=12).rev().map(|s| s * 100).collect::<Vec<_>>());
+
+        assert!(!table.qualifies(300));
+        assert_eq!(table.insert(entry("LOW", 300)), None);
+        let placement = table.insert(entry("MID", 650)).unwrap();
+        assert_eq!(placement.rank, 6);
+        assert_eq!(placement.bumped.map(|e| e.score), Some(300));
+        assert_eq!(table.entries().len(), TABLE_SIZE);
+    }
+
+    #[test]
+    fn test_ties_rank_below_earlier_entries() {
+        let mut table = Leaderboard::default();
+        table.insert(entry("OLD", 500));
+        assert_eq!(table.insert(entry("NEW", 500)).map(|p| p.rank), Some(1));
+        assert_eq!(table.entries()[0].initials, "OLD");
+        assert!(!table.qualifies(0));
+    }
+
+    #[test]
+    fn test_dates_format_in_utc() {
+        assert_eq!(format_date(0), "1970-01-01");
+        assert_eq!(format_date(951_782_400), "2000-02-29");
+        assert_eq!(format_date(1_767_225_599), "2025-12-31");
+    }
+
+    #[test]
+    fn test_letters_wrap() {
+        assert_eq!(cycle_letter('A', 1), 'B');
+        assert_eq!(cycle_letter('A', -1), ' ');
+        assert_eq!(cycle_letter(' ', 1), 'A');
+        assert_eq!(cycle_letter('?', 0), 'A');
+    }
+}
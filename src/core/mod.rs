@@ -1,10 +1,23 @@
+pub mod achievements;
+pub mod cosmetics;
 pub mod input;
+pub mod leaderboard;
+pub mod profiles;
 pub mod progression;
+pub mod registry;
+pub mod replay;
+pub mod rng;
+pub mod save;
+pub mod settings;
 pub mod states;
+pub mod stats;
+pub mod tick;
 
 use bevy::prelude::*;
 
-/// Core plugin: game states, progression/save system, input abstraction.
+/// Core plugin: game states, progression/save system, game registry, input abstraction,
+/// seeded RNG, replays, fixed-timestep clock, user settings, play stats,
+/// achievements.
 pub struct CorePlugin;
 
 impl Plugin for CorePlugin {
@@ -12,7 +25,14 @@ impl Plugin for CorePlugin {
         app.add_plugins((
             states::StatesPlugin,
             progression::ProgressionPlugin,
+            registry::RegistryPlugin,
             input::InputPlugin,
+            rng::RngPlugin,
+            replay::ReplayPlugin,
+            tick::TickPlugin,
+            settings::SettingsPlugin,
+            stats::StatsPlugin,
+            achievements::AchievementsPlugin,
         ));
     }
 }
@@ -0,0 +1,437 @@
+//! Named player profiles, each with its own progress file.
+//!
+//! The store keeps a small index (`profiles.json`) listing profiles and the
+//! active one; each profile's progress lives in `profiles/<id>.json` and is
+//! written through the versioned save format in `save`. If the data
+//! directory can't be used, an in-memory store keeps progress for the
+//! session instead.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::progression::PlayerProgress;
+use super::save::{self, SaveError};
+
+/// Name given to the profile created on first launch.
+pub const DEFAULT_PROFILE_NAME: &str = "Player 1";
+
+/// Longest profile name the store accepts.
+pub const MAX_PROFILE_NAME_LEN: usize = 16;
+
+/// Stable identifier for a profile. Unlike the name, it never changes, so
+/// renaming a profile doesn't move its save file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ProfileId(pub u32);
+
+/// A named profile.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Profile {
+    pub id: ProfileId,
+    pub name: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ProfileIndex {
+    active: Option<ProfileId>,
+    next_id: u32,
+    profiles: Vec<Profile>,
+}
+
+/// Why a profile operation failed.
+#[derive(Debug)]
+pub enum ProfileError {
+    /// No profile with this id exists.
+    NotFound(ProfileId),
+    /// The name is empty or longer than `MAX_PROFILE_NAME_LEN`.
+    InvalidName(String),
+    /// Another profile already uses this name.
+    DuplicateName(String),
+    /// Reading or writing a file failed.
+    Save(SaveError),
+}
+
+impl fmt::Display for ProfileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProfileError::NotFound(id) => write!(f, "profile {} does not exist", id.0),
+            ProfileError::InvalidName(name) => write!(f, "invalid profile name {name:?}"),
+            ProfileError::DuplicateName(name) => write!(f, "a profile named {name:?} exists"),
+            ProfileError::Save(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for ProfileError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ProfileError::Save(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<SaveError> for ProfileError {
+    fn from(err: SaveError) -> Self {
+        ProfileError::Save(err)
+    }
+}
+
+impl From<std::io::Error> for ProfileError {
+    fn from(err: std::io::Error) -> Self {
+        ProfileError::Save(SaveError::Io(err))
+    }
+}
+
+// ─── Store ─────────────────────────────────────────────────────────
+
+/// All profiles on this machine, rooted at a data directory.
+#[derive(Resource, Debug)]
+pub struct ProfileStore {
+    /// The data directory, or `None` for an in-memory store.
+    root: Option<PathBuf>,
+    index: ProfileIndex,
+    /// Progress saved by an in-memory store.
+    memory: HashMap<ProfileId, PlayerProgress>,
+}
+
+impl ProfileStore {
+    /// Open the store under `root`, creating the default profile if none
+    /// exist yet. A pre-profile `save.json` in `root` is imported into it.
+    pub fn open(root: impl Into<PathBuf>) -> Result<Self, ProfileError> {
+        let root = root.into();
+        let index = read_index(&root.join("profiles.json"))?;
+        let legacy_path = root.join("save.json");
+        let mut store = Self {
+            root: Some(root),
+            index,
+            memory: HashMap::new(),
+        };
+
+        if store.index.profiles.is_empty() {
+            let legacy = save::read_save_or_backup(&legacy_path).ok().flatten();
+            let id = store.insert(DEFAULT_PROFILE_NAME)?;
+            store.save_progress(id, &legacy.unwrap_or_default())?;
+            store.index.active = Some(id);
+            store.write_index()?;
+        }
+        if store.active().is_none() {
+            store.index.active = store.index.profiles.first().map(|p| p.id);
+        }
+        Ok(store)
+    }
+
+    /// A store that only lives until the game exits, starting with the
+    /// default profile. Used when the data directory can't be opened.
+    pub fn in_memory() -> Self {
+        let mut store = Self {
+            root: None,
+            index: ProfileIndex::default(),
+            memory: HashMap::new(),
+        };
+        let id = store
+            .insert(DEFAULT_PROFILE_NAME)
+            .expect("the default profile name is valid");
+        store.index.active = Some(id);
+        store
+    }
+
+    /// Whether saves reach the disk.
+    pub fn is_persistent(&self) -> bool {
+        self.root.is_some()
+    }
+
+    /// All profiles, in creation order.
+    pub fn profiles(&self) -> &[Profile] {
+        &self.index.profiles
+    }
+
+    /// Look up a profile by id.
+    pub fn get(&self, id: ProfileId) -> Option<&Profile> {
+        self.index.profiles.iter().find(|p| p.id == id)
+    }
+
+    /// Find a profile by name (case-insensitive).
+    pub fn find_by_name(&self, name: &str) -> Option<&Profile> {
+        let name = name.trim();
+        self.index
+            .profiles
+            .iter()
+            .find(|p| p.name.eq_ignore_ascii_case(name))
+    }
+
+    /// The profile whose progress is currently loaded.
+    pub fn active(&self) -> Option<&Profile> {
+        self.index.active.and_then(|id| self.get(id))
+    }
+
+    /// Make `id` the active profile and remember it for the next launch.
+    pub fn set_active(&mut self, id: ProfileId) -> Result<(), ProfileError> {
+        self.require(id)?;
+        self.index.active = Some(id);
+        self.write_index()
+    }
+
+    /// Create a profile with fresh progress.
+    pub fn create(&mut self, name: &str) -> Result<ProfileId, ProfileError> {
+        let id = self.insert(name)?;
+        self.save_progress(id, &PlayerProgress::default())?;
+        self.write_index()?;
+        Ok(id)
+    }
+
+    /// Rename a profile. Its save file is left where it is.
+    pub fn rename(&mut self, id: ProfileId, name: &str) -> Result<(), ProfileError> {
+        self.require(id)?;
+        let name = self.validate_name(name, Some(id))?;
+        if let Some(profile) = self.index.profiles.iter_mut().find(|p| p.id == id) {
+            profile.name = name;
+        }
+        self.write_index()
+    }
+
+    /// Delete a profile and its save files. If it was active, the first
+    /// remaining profile becomes active.
+    pub fn delete(&mut self, id: ProfileId) -> Result<(), ProfileError> {
+        self.require(id)?;
+        self.index.profiles.retain(|p| p.id != id);
+        if self.index.active == Some(id) {
+            self.index.active = self.index.profiles.first().map(|p| p.id);
+        }
+        self.write_index()?;
+
+        self.memory.remove(&id);
+        if let Some(path) = self.save_path(id) {
+            for file in [save::backup_path(&path), path] {
+                if file.exists() {
+                    fs::remove_file(file)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Duplicate a profile's progress under a new name.
+    pub fn copy(&mut self, id: ProfileId, name: &str) -> Result<ProfileId, ProfileError> {
+        let progress = self.load_progress(id)?;
+        let new_id = self.insert(name)?;
+        self.save_progress(new_id, &progress)?;
+        self.write_index()?;
+        Ok(new_id)
+    }
+
+    /// Load a profile's progress, or fresh progress if it has never saved.
+    pub fn load_progress(&self, id: ProfileId) -> Result<PlayerProgress, ProfileError> {
+        self.require(id)?;
+        let loaded = match self.save_path(id) {
+            Some(path) => save::read_save_or_backup(&path)?,
+            None => self.memory.get(&id).cloned(),
+        };
+        Ok(loaded.unwrap_or_default())
+    }
+
+    /// Write a profile's progress to disk.
+    pub fn save_progress(
+        &mut self,
+        id: ProfileId,
+        progress: &PlayerProgress,
+    ) -> Result<(), ProfileError> {
+        self.require(id)?;
+        match self.save_path(id) {
+            Some(path) => save::write_save(&path, progress)?,
+            None => {
+                self.memory.insert(id, progress.clone());
+            }
+        }
+        Ok(())
+    }
+
+    /// Write progress for the active profile.
+    pub fn save_active(&mut self, progress: &PlayerProgress) -> Result<(), ProfileError> {
+        match self.index.active {
+            Some(id) => self.save_progress(id, progress),
+            None => Ok(()),
+        }
+    }
+
+    /// Path of a profile's progress file, unless the store is in memory.
+    pub fn save_path(&self, id: ProfileId) -> Option<PathBuf> {
+        let root = self.root.as_ref()?;
+        Some(root.join("profiles").join(format!("{}.json", id.0)))
+    }
+
+    /// `base` if no profile uses it yet, otherwise the first free `"base N"`.
+    pub fn unused_name(&self, base: &str) -> String {
+        if self.find_by_name(base).is_none() {
+            return base.to_string();
+        }
+        (2..)
+            .map(|n| format!("{base} {n}"))
+            .find(|name| self.find_by_name(name).is_none())
+            .unwrap_or_default()
+    }
+
+    fn require(&self, id: ProfileId) -> Result<(), ProfileError> {
+        self.get(id).map(|_| ()).ok_or(ProfileError::NotFound(id))
+    }
+
+    fn validate_name(&self, name: &str, except: Option<ProfileId>) -> Result<String, ProfileError> {
+        let name = name.trim();
+        if name.is_empty() || name.chars().count() > MAX_PROFILE_NAME_LEN {
+            return Err(ProfileError::InvalidName(name.to_string()));
+        }
+        match self.find_by_name(name) {
+            Some(existing) if Some(existing.id) != except => {
+                Err(ProfileError::DuplicateName(name.to_string()))
+            }
+            _ => Ok(name.to_string()),
+        }
+    }
+
+    fn insert(&mut self, name: &str) -> Result<ProfileId, ProfileError> {
+        let name = self.validate_name(name, None)?;
+        let id = ProfileId(self.index.next_id);
+        self.index.next_id += 1;
+        self.index.profiles.push(Profile { id, name });
+        Ok(id)
+    }
+
+    fn write_index(&self) -> Result<(), ProfileError> {
+        let Some(root) = &self.root else {
+            return Ok(());
+        };
+        let json = serde_json::to_string_pretty(&self.index).map_err(SaveError::Parse)?;
+        save::write_atomic(&root.join("profiles.json"), &json)?;
+        Ok(())
+    }
+}
+
+fn read_index(path: &Path) -> Result<ProfileIndex, ProfileError> {
+    let parse = |path: &Path| -> Result<Option<ProfileIndex>, SaveError> {
+        match fs::read_to_string(path) {
+            Ok(json) => Ok(Some(serde_json::from_str(&json)?)),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    };
+    match parse(path) {
+        Ok(index) => Ok(index.unwrap_or_default()),
+        Err(err) => match parse(&save::backup_path(path)) {
+            Ok(Some(index)) => {
+                warn!("{}; restored profile list from backup", err);
+                Ok(index)
+            }
+            _ => Err(err.into()),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn first_high_score(progress: &PlayerProgress) -> u64 {
+        *progress.high_scores.get(&(0, 0)).unwrap_or(&0)
+    }
+
+    fn temp_root(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "retrogamegame-profile-test-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_open_creates_default_profile() {
+        let store = ProfileStore::open(temp_root("default")).unwrap();
+        assert_eq!(store.profiles().len(), 1);
+        assert_eq!(store.active().unwrap().name, DEFAULT_PROFILE_NAME);
+    }
+
+    #[test]
+    fn test_profiles_keep_separate_progress() {
+        let root = temp_root("separate");
+        let mut store = ProfileStore::open(&root).unwrap();
+        let first = store.active().unwrap().id;
+        let second = store.create("Alice").unwrap();
+
+        let mut progress = PlayerProgress::default();
+        progress.high_scores.insert((0, 0), 900);
+        store.save_progress(second, &progress).unwrap();
+
+        let reopened = ProfileStore::open(&root).unwrap();
+        assert_eq!(first_high_score(&reopened.load_progress(first).unwrap()), 0);
+        assert_eq!(
+            first_high_score(&reopened.load_progress(second).unwrap()),
+            900
+        );
+    }
+
+    #[test]
+    fn test_rename_copy_delete() {
+        let mut store = ProfileStore::open(temp_root("ops")).unwrap();
+        let id = store.active().unwrap().id;
+
+        store.rename(id, "Bob").unwrap();
+        assert_eq!(store.get(id).unwrap().name, "Bob");
+
+        let copy = store.copy(id, "Bob (copy)").unwrap();
+        assert!(store.save_path(copy).unwrap().exists());
+
+        store.delete(id).unwrap();
+        assert!(store.get(id).is_none());
+        assert!(!store.save_path(id).unwrap().exists());
+        assert_eq!(store.active().unwrap().id, copy);
+    }
+
+    #[test]
+    fn test_names_must_be_unique_and_non_empty() {
+        let mut store = ProfileStore::open(temp_root("names")).unwrap();
+        assert!(matches!(
+            store.create(&DEFAULT_PROFILE_NAME.to_lowercase()),
+            Err(ProfileError::DuplicateName(_))
+        ));
+        assert!(matches!(
+            store.create("   "),
+            Err(ProfileError::InvalidName(_))
+        ));
+        store.create("Alice").unwrap();
+        assert_eq!(store.unused_name("Alice"), "Alice 2");
+        assert_eq!(store.unused_name("Bob"), "Bob");
+    }
+
+    #[test]
+    fn test_legacy_save_is_imported() {
+        let root = temp_root("legacy");
+        let mut progress = PlayerProgress::default();
+        progress.high_scores.insert((0, 0), 1234);
+        save::write_save(&root.join("save.json"), &progress).unwrap();
+
+        let store = ProfileStore::open(&root).unwrap();
+        let id = store.active().unwrap().id;
+        assert_eq!(first_high_score(&store.load_progress(id).unwrap()), 1234);
+    }
+
+    #[test]
+    fn test_in_memory_store_keeps_progress() {
+        let mut store = ProfileStore::in_memory();
+        let first = store.active().unwrap().id;
+        let second = store.create("Alice").unwrap();
+
+        let mut progress = PlayerProgress::default();
+        progress.high_scores.insert((0, 0), 500);
+        store.save_active(&progress).unwrap();
+
+        assert!(!store.is_persistent());
+        assert!(store.save_path(first).is_none());
+        assert_eq!(first_high_score(&store.load_progress(first).unwrap()), 500);
+        assert_eq!(first_high_score(&store.load_progress(second).unwrap()), 0);
+    }
+}
@@ -1,8 +1,14 @@
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 
+use super::cosmetics::{self, Cosmetic, CosmeticSlot, PurchaseError};
+use super::leaderboard::{DEFAULT_INITIALS, Leaderboard};
+use super::profiles::ProfileStore;
+use super::registry::GameRegistry;
+use super::save::game_key_map;
 use super::states::{Era, MiniGameId};
+use super::stats::{PlayerStats, StatEvent};
 
 /// Plugin for save/load and progression tracking.
 pub struct ProgressionPlugin;
@@ -10,10 +16,42 @@ pub struct ProgressionPlugin;
 impl Plugin for ProgressionPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<PlayerProgress>()
-            .add_systems(Startup, load_progress);
+            .init_resource::<DataDir>()
+            .add_message::<MiniGameFinished>()
+            .add_message::<SaveProgress>()
+            .add_systems(Startup, load_progress)
+            .add_systems(Last, save_progress);
     }
 }
 
+// ─── Messages ──────────────────────────────────────────────────────
+
+/// Sent by a mini-game when a run ends. The results screen records it
+/// into `PlayerProgress` before switching to `GameState::Results`, or
+/// straight back to `GameState::EraSelect` for a quit run.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct MiniGameFinished {
+    pub game: MiniGameId,
+    pub score: u64,
+    pub outcome: MiniGameOutcome,
+}
+
+/// Ask for `PlayerProgress` to be written to the active profile. However
+/// many are sent in a frame, it is saved once, at the end of the frame.
+#[derive(Message, Debug, Clone, Copy, Default)]
+pub struct SaveProgress;
+
+/// How a mini-game run ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MiniGameOutcome {
+    /// The player cleared the level or won the match.
+    Victory,
+    /// The player ran out of lives, health or fuel.
+    Defeat,
+    /// The player left the game early.
+    Quit,
+}
+
 // ─── Resources ─────────────────────────────────────────────────────
 
 /// Tracks the player's overall progress across all eras and mini-games.
@@ -22,13 +60,37 @@ pub struct PlayerProgress {
     /// Total tokens earned across all games
     pub tokens: u64,
     /// Per-game high scores, keyed by (era_index, game_index)
+    #[serde(with = "game_key_map")]
     pub high_scores: HashMap<(u8, u8), u64>,
     /// Which eras are unlocked (era_index → unlocked)
     pub eras_unlocked: HashMap<u8, bool>,
     /// Which mini-games are unlocked (era_index, game_index → unlocked)
+    #[serde(with = "game_key_map")]
     pub games_unlocked: HashMap<(u8, u8), bool>,
     /// Which mini-games have been completed (reached score threshold)
+    #[serde(with = "game_key_map")]
     pub games_completed: HashMap<(u8, u8), bool>,
+    /// Ids of the cosmetics bought in the shop
+    #[serde(default)]
+    pub owned_cosmetics: BTreeSet<String>,
+    /// Ids of the owned cosmetics in use, at most one per slot
+    #[serde(default)]
+    pub equipped_cosmetics: BTreeSet<String>,
+    /// Ids of the unlocked achievements
+    #[serde(default)]
+    pub achievements_unlocked: BTreeSet<String>,
+    /// Per-game top-10 tables, keyed by (era_index, game_index)
+    #[serde(default, with = "game_key_map")]
+    pub leaderboards: HashMap<(u8, u8), Leaderboard>,
+    /// Initials entered for the last leaderboard entry
+    #[serde(default)]
+    pub last_initials: Option<String>,
+    /// Lifetime play statistics
+    #[serde(default)]
+    pub stats: PlayerStats,
+    /// Treat every era and game as unlocked (`--unlock-all`). Never saved.
+    #[serde(skip)]
+    pub unlock_all: bool,
 }
 
 impl Default for PlayerProgress {
@@ -48,21 +110,37 @@ impl Default for PlayerProgress {
             eras_unlocked,
             games_unlocked,
             games_completed: HashMap::new(),
+            owned_cosmetics: BTreeSet::new(),
+            equipped_cosmetics: BTreeSet::new(),
+            achievements_unlocked: BTreeSet::new(),
+            leaderboards: HashMap::new(),
+            last_initials: None,
+            stats: PlayerStats::default(),
+            unlock_all: false,
         }
     }
 }
 
 impl PlayerProgress {
+    /// Take on another profile's `loaded` progress. Session-only flags such
+    /// as `unlock_all` are never saved, so they carry over.
+    pub fn replace_with(&mut self, loaded: PlayerProgress) {
+        *self = PlayerProgress {
+            unlock_all: self.unlock_all,
+            ..loaded
+        };
+    }
+
     /// Check if an era is unlocked.
     pub fn is_era_unlocked(&self, era: Era) -> bool {
         let idx = era_to_index(era);
-        *self.eras_unlocked.get(&idx).unwrap_or(&false)
+        self.unlock_all || *self.eras_unlocked.get(&idx).unwrap_or(&false)
     }
 
     /// Check if a specific mini-game is unlocked.
     pub fn is_game_unlocked(&self, game: MiniGameId) -> bool {
         let key = game_to_key(game);
-        *self.games_unlocked.get(&key).unwrap_or(&false)
+        self.unlock_all || *self.games_unlocked.get(&key).unwrap_or(&false)
     }
 
     /// Check if a specific mini-game has been completed.
@@ -77,9 +155,13 @@ impl PlayerProgress {
         *self.high_scores.get(&key).unwrap_or(&0)
     }
 
-    /// Record a game result. Returns tokens earned.
-    pub fn record_result(&mut self, game: MiniGameId, score: u64, threshold: u64) -> u64 {
+    /// Record a game result against the game's registered completion
+    /// threshold. Returns tokens earned.
+    pub fn record_result(&mut self, registry: &GameRegistry, game: MiniGameId, score: u64) -> u64 {
         let key = game_to_key(game);
+        let threshold = registry
+            .get(game)
+            .map_or(u64::MAX, |info| info.completion_threshold);
 
         // Update high score
         let prev_high = *self.high_scores.get(&key).unwrap_or(&0);
@@ -101,19 +183,98 @@ impl PlayerProgress {
 
         // Unlock next game/era if newly completed
         if newly_completed {
-            self.unlock_next(game);
+            self.unlock_next(game, registry.games_in_era(game.era));
         }
 
         total_tokens
     }
 
+    /// Check if the player has bought a cosmetic.
+    pub fn owns(&self, cosmetic: &Cosmetic) -> bool {
+        self.owned_cosmetics.contains(cosmetic.id)
+    }
+
+    /// Spend tokens on a cosmetic and equip it.
+    pub fn buy(&mut self, cosmetic: &Cosmetic) -> Result<(), PurchaseError> {
+        if self.owns(cosmetic) {
+            return Err(PurchaseError::AlreadyOwned);
+        }
+        if self.tokens < cosmetic.price {
+            return Err(PurchaseError::NotEnoughTokens {
+                price: cosmetic.price,
+                tokens: self.tokens,
+            });
+        }
+        self.tokens -= cosmetic.price;
+        self.owned_cosmetics.insert(cosmetic.id.to_string());
+        self.equip(cosmetic);
+        Ok(())
+    }
+
+    /// Equip an owned cosmetic in place of whatever shares its slot.
+    /// Returns false if the player doesn't own it.
+    pub fn equip(&mut self, cosmetic: &Cosmetic) -> bool {
+        if !self.owns(cosmetic) {
+            return false;
+        }
+        for other in cosmetics::in_slot(cosmetic.slot) {
+            self.equipped_cosmetics.remove(other.id);
+        }
+        self.equipped_cosmetics.insert(cosmetic.id.to_string());
+        true
+    }
+
+    /// Go back to the default look for a cosmetic's slot.
+    pub fn unequip(&mut self, cosmetic: &Cosmetic) {
+        self.equipped_cosmetics.remove(cosmetic.id);
+    }
+
+    /// The cosmetic equipped in `slot`, if any.
+    pub fn equipped(&self, slot: CosmeticSlot) -> Option<&'static Cosmetic> {
+        cosmetics::in_slot(slot).find(|cosmetic| self.equipped_cosmetics.contains(cosmetic.id))
+    }
+
+    /// The top-10 table of a game, if anyone has made it on.
+    pub fn leaderboard(&self, game: MiniGameId) -> Option<&Leaderboard> {
+        self.leaderboards.get(&game_to_key(game))
+    }
+
+    /// The top-10 table of a game, created empty if needed.
+    pub fn leaderboard_mut(&mut self, game: MiniGameId) -> &mut Leaderboard {
+        self.leaderboards.entry(game_to_key(game)).or_default()
+    }
+
+    /// Initials to start the next leaderboard entry from.
+    pub fn initials(&self) -> &str {
+        self.last_initials.as_deref().unwrap_or(DEFAULT_INITIALS)
+    }
+
+    /// Current value of a stat counter.
+    pub fn stat(&self, stat: &str) -> u64 {
+        self.stats.counter(stat)
+    }
+
+    /// Apply a stat event to its counter and return the new value.
+    pub fn record_stat(&mut self, event: &StatEvent) -> u64 {
+        self.stats.record(event)
+    }
+
+    /// Check if an achievement is unlocked.
+    pub fn has_achievement(&self, id: &str) -> bool {
+        self.achievements_unlocked.contains(id)
+    }
+
+    /// Mark an achievement unlocked. Returns false if it already was.
+    pub fn unlock_achievement(&mut self, id: &str) -> bool {
+        self.achievements_unlocked.insert(id.to_string())
+    }
+
     /// Unlock the next game in sequence, or the next era.
-    fn unlock_next(&mut self, completed: MiniGameId) {
+    fn unlock_next(&mut self, completed: MiniGameId, games_in_era: u8) {
         let era_idx = era_to_index(completed.era);
         let game_idx = completed.index;
 
         // Try to unlock next game in same era
-        let games_in_era = games_per_era(completed.era);
         if game_idx + 1 < games_in_era {
             self.games_unlocked.insert((era_idx, game_idx + 1), true);
         }
@@ -128,37 +289,11 @@ impl PlayerProgress {
             self.games_unlocked.insert((next_era, 0), true);
         }
     }
-
-    /// Save progress to disk.
-    pub fn save(&self) {
-        if let Ok(json) = serde_json::to_string_pretty(self) {
-            let path = save_file_path();
-            if let Some(parent) = path.parent() {
-                let _ = std::fs::create_dir_all(parent);
-            }
-            let _ = std::fs::write(&path, json);
-            info!("Progress saved to {:?}", path);
-        }
-    }
-
-    /// Load progress from disk.
-    pub fn load() -> Option<Self> {
-        let path = save_file_path();
-        if path.exists() {
-            if let Ok(json) = std::fs::read_to_string(&path) {
-                if let Ok(progress) = serde_json::from_str(&json) {
-                    info!("Progress loaded from {:?}", path);
-                    return Some(progress);
-                }
-            }
-        }
-        None
-    }
 }
 
 // ─── Helper functions ──────────────────────────────────────────────
 
-fn era_to_index(era: Era) -> u8 {
+pub(crate) fn era_to_index(era: Era) -> u8 {
     match era {
         Era::The80s => 0,
         Era::The90s => 1,
@@ -168,26 +303,29 @@ fn era_to_index(era: Era) -> u8 {
     }
 }
 
-fn game_to_key(game: MiniGameId) -> (u8, u8) {
+pub(crate) fn game_to_key(game: MiniGameId) -> (u8, u8) {
     (era_to_index(game.era), game.index)
 }
 
-fn games_per_era(era: Era) -> u8 {
-    match era {
-        Era::The80s => 3,
-        Era::The90s => 3,
-        Era::The2000s => 0,
-        Era::The2010s => 0,
-        Era::Future => 1,
-    }
-}
-
-fn save_file_path() -> std::path::PathBuf {
+/// Directory holding profiles and their save files.
+pub fn data_dir() -> std::path::PathBuf {
     // Use the platform-appropriate data directory
     if let Some(proj_dirs) = dirs_next_or_fallback() {
-        proj_dirs.join("retrogamegame").join("save.json")
+        proj_dirs.join("retrogamegame")
     } else {
-        std::path::PathBuf::from("save.json")
+        std::path::PathBuf::from(".")
+    }
+}
+
+/// Directory the running game reads and writes its files in: profiles,
+/// settings, bindings and replays. Defaults to `data_dir()`; headless runs
+/// point it at a scratch directory so they never touch the player's files.
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub struct DataDir(pub std::path::PathBuf);
+
+impl Default for DataDir {
+    fn default() -> Self {
+        Self(data_dir())
     }
 }
 
@@ -198,8 +336,97 @@ fn dirs_next_or_fallback() -> Option<std::path::PathBuf> {
 
 // ─── Systems ───────────────────────────────────────────────────────
 
-fn load_progress(mut progress: ResMut<PlayerProgress>) {
-    if let Some(loaded) = PlayerProgress::load() {
-        *progress = loaded;
+/// Open the profile store and load the active profile. If the data
+/// directory can't be used, or the active profile's save can't be read,
+/// progress is kept in memory for the session so the unreadable file is
+/// never overwritten, and the hub warns that it won't be saved.
+fn load_progress(mut commands: Commands, dir: Res<DataDir>, mut progress: ResMut<PlayerProgress>) {
+    let mut store = match ProfileStore::open(dir.0.clone()) {
+        Ok(store) => store,
+        Err(err) => {
+            error!(
+                "Could not open profiles, progress will not be saved: {}",
+                err
+            );
+            ProfileStore::in_memory()
+        }
+    };
+
+    if let Some(active) = store.active() {
+        match store.load_progress(active.id) {
+            Ok(loaded) => {
+                info!("Loaded profile {:?}", active.name);
+                progress.replace_with(loaded);
+            }
+            Err(err) => {
+                error!(
+                    "Could not load profile {:?}, progress will not be saved: {}",
+                    active.name, err
+                );
+                store = ProfileStore::in_memory();
+            }
+        }
+    }
+    commands.insert_resource(store);
+}
+
+fn save_progress(
+    mut requests: MessageReader<SaveProgress>,
+    progress: Res<PlayerProgress>,
+    profiles: Option<ResMut<ProfileStore>>,
+) {
+    if requests.read().count() == 0 {
+        return;
+    }
+    if let Some(mut profiles) = profiles
+        && let Err(err) = profiles.save_active(&progress)
+    {
+        error!("Failed to save progress: {}", err);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+    use std::fs;
+
+    #[test]
+    fn test_replacing_progress_keeps_unlock_all() {
+        let mut progress = PlayerProgress {
+            unlock_all: true,
+            ..Default::default()
+        };
+        progress.replace_with(PlayerProgress {
+            tokens: 5,
+            ..Default::default()
+        });
+
+        assert_eq!(progress.tokens, 5);
+        assert!(progress.unlock_all);
+    }
+
+    #[test]
+    fn test_unreadable_save_is_never_overwritten() {
+        let root = std::env::temp_dir().join(format!(
+            "retrogamegame-progression-test-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&root);
+        let store = ProfileStore::open(root.clone()).unwrap();
+        let path = store.save_path(store.active().unwrap().id).unwrap();
+        let newer = r#"{"version":99,"progress":{}}"#;
+        fs::write(&path, newer).unwrap();
+
+        let mut world = World::new();
+        world.insert_resource(DataDir(root));
+        world.init_resource::<PlayerProgress>();
+        world.run_system_once(load_progress).unwrap();
+        world.insert_resource(Messages::<SaveProgress>::default());
+        world.write_message(SaveProgress);
+        world.run_system_once(save_progress).unwrap();
+
+        assert!(!world.resource::<ProfileStore>().is_persistent());
+        assert_eq!(fs::read_to_string(&path).unwrap(), newer);
     }
 }
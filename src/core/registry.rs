@@ -0,0 +1,126 @@
+use bevy::prelude::*;
+
+use super::states::{Era, MiniGameId, PlayingState};
+use crate::eras::shared::MiniGame;
+
+/// Plugin that owns the `GameRegistry`. Era plugins add to it with
+/// `app.register_mini_game::<T>()`.
+pub struct RegistryPlugin;
+
+impl Plugin for RegistryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameRegistry>();
+    }
+}
+
+// ─── Resources ─────────────────────────────────────────────────────
+
+/// Everything the menus and progression need to know about a mini-game.
+#[derive(Debug, Clone)]
+pub struct GameInfo {
+    pub id: MiniGameId,
+    pub playing_state: PlayingState,
+    pub name: &'static str,
+    pub description: &'static str,
+    pub thumbnail: &'static str,
+    pub completion_threshold: u64,
+    pub starting_lives: i32,
+    /// Size of the offscreen image the game is drawn into.
+    pub virtual_resolution: UVec2,
+    /// World area the game camera shows.
+    pub view: Vec2,
+    /// Reads the current run's score out of the world.
+    pub score: fn(&World) -> u64,
+}
+
+impl GameInfo {
+    /// Metadata for a `MiniGame` implementation.
+    pub fn of<T: MiniGame>() -> Self {
+        Self {
+            id: T::ID,
+            playing_state: T::PLAYING_STATE,
+            name: T::NAME,
+            description: T::DESCRIPTION,
+            thumbnail: T::THUMBNAIL,
+            completion_threshold: T::COMPLETION_THRESHOLD,
+            starting_lives: T::STARTING_LIVES,
+            virtual_resolution: T::VIRTUAL_RESOLUTION,
+            view: T::VIEW,
+            score: T::score,
+        }
+    }
+}
+
+/// All registered mini-games, kept sorted by era and index.
+#[derive(Resource, Debug, Default)]
+pub struct GameRegistry {
+    games: Vec<GameInfo>,
+}
+
+impl GameRegistry {
+    /// Add a game. Registering the same id twice replaces the old entry.
+    pub fn register(&mut self, info: GameInfo) {
+        if let Some(existing) = self.games.iter_mut().find(|g| g.id == info.id) {
+            warn!("Mini-game {:?} registered twice", info.id);
+            *existing = info;
+            return;
+        }
+        self.games.push(info);
+        self.games.sort_by_key(|g| (g.id.era as u8, g.id.index));
+    }
+
+    /// Look up a game by id.
+    pub fn get(&self, id: MiniGameId) -> Option<&GameInfo> {
+        self.games.iter().find(|g| g.id == id)
+    }
+
+    /// Look up the game that runs in `state`.
+    pub fn by_state(&self, state: PlayingState) -> Option<&GameInfo> {
+        self.games.iter().find(|g| g.playing_state == state)
+    }
+
+    /// All games, ordered by era then index.
+    pub fn iter(&self) -> impl Iterator<Item = &GameInfo> {
+        self.games.iter()
+    }
+
+    /// Games belonging to `era`, ordered by index.
+    pub fn era_games(&self, era: Era) -> Vec<&GameInfo> {
+        self.games.iter().filter(|g| g.id.era == era).collect()
+    }
+
+    /// Thumbnail of the first game in `era`, used for the era's card.
+    pub fn era_thumbnail(&self, era: Era) -> Option<&'static str> {
+        self.games
+            .iter()
+            .find(|g| g.id.era == era)
+            .map(|g| g.thumbnail)
+    }
+
+    /// Number of games in `era`.
+    pub fn games_in_era(&self, era: Era) -> u8 {
+        self.games.iter().filter(|g| g.id.era == era).count() as u8
+    }
+
+    /// Display name for `id`, or "Unknown" if it isn't registered.
+    pub fn name(&self, id: MiniGameId) -> &'static str {
+        self.get(id).map_or("Unknown", |g| g.name)
+    }
+}
+
+// ─── App extension ─────────────────────────────────────────────────
+
+/// Adds `register_mini_game` to `App`.
+pub trait RegisterMiniGameExt {
+    fn register_mini_game<T: MiniGame>(&mut self) -> &mut Self;
+}
+
+impl RegisterMiniGameExt for App {
+    fn register_mini_game<T: MiniGame>(&mut self) -> &mut Self {
+        self.init_resource::<GameRegistry>();
+        self.world_mut()
+            .resource_mut::<GameRegistry>()
+            .register(GameInfo::of::<T>());
+        self
+    }
+}
@@ -0,0 +1,526 @@
+//! Deterministic input recording and playback.
+//!
+//! Every mini-game run is recorded as its per-frame `GameInput` and frame
+//! duration, together with the run's RNG seed and `MiniGameId`. Playback
+//! feeds the recorded input back in and steps `Time` by the recorded
+//! durations instead of the wall clock, so the fixed-timestep schedule ticks
+//! on the same frames it did live. When the replayed run ends its score is
+//! compared with the recorded one to catch desyncs.
+
+use bevy::prelude::*;
+use bevy::state::state::{StateTransitionEvent, StateTransitionSystems};
+use bevy::time::{TimeSystems, TimeUpdateStrategy};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use super::input::{GameInput, GameInputSystems};
+use super::progression::{DataDir, MiniGameFinished};
+use super::registry::GameRegistry;
+use super::rng::{FixedSeed, GameRng};
+use super::save::{SaveError, write_atomic};
+use super::states::{MiniGameId, PauseState, PlayingState};
+use super::tick::{DEFAULT_TICK_RATE, TickRate};
+use crate::eras::shared::QuitMiniGame;
+
+/// Replay file format version written by `Replay::write`.
+pub const REPLAY_VERSION: u32 = 1;
+
+/// Directory under the data dir holding the latest replay of each game.
+const REPLAY_DIR: &str = "replays";
+
+/// Plugin that records every mini-game run and plays replays back.
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            StateTransition,
+            begin_run.in_set(StateTransitionSystems::TransitionSchedules),
+        )
+        .add_systems(
+            First,
+            step_playback_clock
+                .before(TimeSystems)
+                .run_if(resource_exists::<ReplayPlayback>),
+        )
+        .add_systems(
+            PreUpdate,
+            play_back_input
+                .after(GameInputSystems::Read)
+                .before(GameInputSystems::Derive)
+                .run_if(resource_exists::<ReplayPlayback>),
+        )
+        .add_systems(
+            Last,
+            (
+                record_frame
+                    .run_if(resource_exists::<Recording>.and(in_state(PauseState::Running))),
+                finish_run.run_if(on_message::<MiniGameFinished>),
+                advance_playback
+                    .run_if(resource_exists::<ReplayPlayback>.and(in_state(PauseState::Running))),
+            )
+                .chain(),
+        );
+    }
+}
+
+// ─── Data ──────────────────────────────────────────────────────────
+
+/// A recorded mini-game run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Replay {
+    pub version: u32,
+    pub game: MiniGameId,
+    pub seed: u64,
+    /// Score the recorded run ended with.
+    pub score: u64,
+    /// Gameplay ticks per second the run was recorded at.
+    #[serde(default = "default_tick_rate")]
+    pub tick_rate: f64,
+    /// Duration of each frame, in microseconds.
+    deltas: Vec<u32>,
+    /// Input of each frame, run-length encoded.
+    inputs: Vec<InputRun>,
+}
+
+/// `frames` consecutive frames with identical input.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct InputRun {
+    frames: u32,
+    input: GameInput,
+}
+
+impl Replay {
+    pub fn new(game: MiniGameId, seed: u64) -> Self {
+        Self {
+            version: REPLAY_VERSION,
+            game,
+            seed,
+            score: 0,
+            tick_rate: DEFAULT_TICK_RATE,
+            deltas: Vec::new(),
+            inputs: Vec::new(),
+        }
+    }
+
+    /// Append one frame.
+    pub fn push(&mut self, delta: Duration, input: &GameInput) {
+        self.deltas
+            .push(u32::try_from(delta.as_micros()).unwrap_or(u32::MAX));
+        match self.inputs.last_mut() {
+            Some(run) if run.input == *input => run.frames += 1,
+            _ => self.inputs.push(InputRun {
+                frames: 1,
+                input: input.clone(),
+            }),
+        }
+    }
+
+    /// Number of recorded frames.
+    pub fn len(&self) -> usize {
+        self.deltas.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.deltas.is_empty()
+    }
+
+    /// Every frame's duration and input, in order.
+    pub fn frames(&self) -> impl Iterator<Item = (Duration, &GameInput)> {
+        let inputs = self
+            .inputs
+            .iter()
+            .flat_map(|run| std::iter::repeat_n(&run.input, run.frames as usize));
+        self.deltas
+            .iter()
+            .map(|micros| Duration::from_micros(u64::from(*micros)))
+            .zip(inputs)
+    }
+
+    /// Read a replay file. Returns `Ok(None)` if it doesn't exist.
+    pub fn read(path: &Path) -> Result<Option<Replay>, SaveError> {
+        let json = match fs::read_to_string(path) {
+            Ok(json) => json,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        let replay: Replay = serde_json::from_str(&json)?;
+        if replay.version > REPLAY_VERSION {
+            return Err(SaveError::UnsupportedVersion(replay.version));
+        }
+        Ok(Some(replay))
+    }
+
+    /// Write the replay to `path` atomically.
+    pub fn write(&self, path: &Path) -> Result<(), SaveError> {
+        let json = serde_json::to_string(self)?;
+        write_atomic(path, &json)?;
+        Ok(())
+    }
+}
+
+fn default_tick_rate() -> f64 {
+    DEFAULT_TICK_RATE
+}
+
+/// Where the latest replay of `game` is kept.
+pub fn replay_path(dir: &Path, game: MiniGameId) -> PathBuf {
+    dir.join(REPLAY_DIR)
+        .join(format!("{}-{}.json", game.era as u8, game.index))
+}
+
+/// Where a replay kept for a leaderboard entry is stored.
+pub fn kept_replay_path(dir: &Path, name: &str) -> PathBuf {
+    dir.join(REPLAY_DIR).join(name)
+}
+
+/// The most recently finished recording, offered on the results screen.
+#[derive(Resource, Debug, Clone)]
+pub struct LastReplay(pub Replay);
+
+/// How `Time` was updated before playback took over the clock.
+#[derive(Resource)]
+struct LiveClock(TimeUpdateStrategy);
+
+/// The run currently being recorded.
+#[derive(Resource)]
+struct Recording(Replay);
+
+/// How a played-back run compared with its recording.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayVerdict {
+    /// The replay reproduced the recorded score.
+    Match,
+    /// The replay diverged from the recording.
+    Desync { expected: u64, actual: u64 },
+    /// The viewer stopped the replay before it ended.
+    Stopped,
+}
+
+/// A replay being played back. Insert it and enter the replay's
+/// `PlayingState` to start watching; it is removed when the run ends.
+#[derive(Resource, Debug)]
+pub struct ReplayPlayback {
+    replay: Replay,
+    frame: usize,
+    run: usize,
+    run_frame: u32,
+    stopped: bool,
+    demo: bool,
+}
+
+impl ReplayPlayback {
+    pub fn new(replay: Replay) -> Self {
+        Self {
+            replay,
+            frame: 0,
+            run: 0,
+            run_frame: 0,
+            stopped: false,
+            demo: false,
+        }
+    }
+
+    /// Play `replay` as an attract-mode demo. Demos may be scripted rather
+    /// than recorded, so their score isn't checked against the replay's.
+    pub fn demo(replay: Replay) -> Self {
+        Self {
+            demo: true,
+            ..Self::new(replay)
+        }
+    }
+
+    pub fn is_demo(&self) -> bool {
+        self.demo
+    }
+
+    pub fn replay(&self) -> &Replay {
+        &self.replay
+    }
+
+    /// Duration and input of the frame being played.
+    fn current(&self) -> Option<(Duration, &GameInput)> {
+        let micros = *self.replay.deltas.get(self.frame)?;
+        let run = self.replay.inputs.get(self.run)?;
+        Some((Duration::from_micros(u64::from(micros)), &run.input))
+    }
+
+    fn advance(&mut self) {
+        let Some(run) = self.replay.inputs.get(self.run) else {
+            return;
+        };
+        self.frame += 1;
+        self.run_frame += 1;
+        if self.run_frame >= run.frames {
+            self.run += 1;
+            self.run_frame = 0;
+        }
+    }
+
+    /// Skip to the end; the run is then quit like an exhausted replay.
+    pub fn stop(&mut self) {
+        self.stopped = true;
+        self.frame = self.replay.len();
+        self.run = self.replay.inputs.len();
+    }
+
+    /// Whether every recorded frame has been played.
+    pub fn is_finished(&self) -> bool {
+        self.frame >= self.replay.len()
+    }
+
+    /// Compare the replayed run's final score with the recording.
+    pub fn verdict(&self, score: u64) -> ReplayVerdict {
+        if self.stopped {
+            ReplayVerdict::Stopped
+        } else if score == self.replay.score {
+            ReplayVerdict::Match
+        } else {
+            ReplayVerdict::Desync {
+                expected: self.replay.score,
+                actual: score,
+            }
+        }
+    }
+}
+
+// ─── Systems ───────────────────────────────────────────────────────
+
+/// Entering a mini-game (including a restart) starts a new run: pick its
+/// seed, reseed `GameRng` and start recording, unless a replay is being
+/// played back.
+#[allow(clippy::too_many_arguments)]
+fn begin_run(
+    mut commands: Commands,
+    mut transitions: MessageReader<StateTransitionEvent<PlayingState>>,
+    registry: Res<GameRegistry>,
+    playback: Option<Res<ReplayPlayback>>,
+    fixed_seed: Option<Res<FixedSeed>>,
+    tick_rate: Res<TickRate>,
+    mut rng: ResMut<GameRng>,
+    mut fixed: ResMut<Time<Fixed>>,
+) {
+    let Some(transition) = transitions.read().last() else {
+        return;
+    };
+    if transition.exited == transition.entered && !transition.allow_same_state_transitions {
+        return;
+    }
+    let Some(info) = transition
+        .entered
+        .and_then(|state| registry.by_state(state))
+    else {
+        return;
+    };
+
+    // Leftover fixed-step time would shift which frames physics steps on.
+    let overstep = fixed.overstep();
+    fixed.discard_overstep(overstep);
+
+    let seed = match (&playback, fixed_seed) {
+        (Some(playback), _) => playback.replay.seed,
+        (None, Some(fixed)) => fixed.0,
+        (None, None) => rand::random(),
+    };
+    rng.reseed(seed);
+    match playback {
+        Some(playback) => fixed.set_timestep_hz(playback.replay.tick_rate),
+        None => {
+            let mut replay = Replay::new(info.id, seed);
+            replay.tick_rate = tick_rate.0;
+            commands.insert_resource(Recording(replay));
+        }
+    }
+}
+
+/// Step `Time` by the recorded frame durations, putting the clock that
+/// was in use aside until playback ends.
+fn step_playback_clock(
+    mut commands: Commands,
+    playback: Res<ReplayPlayback>,
+    live: Option<Res<LiveClock>>,
+    mut strategy: ResMut<TimeUpdateStrategy>,
+) {
+    if let Some((delta, _)) = playback.current() {
+        let previous = std::mem::replace(&mut *strategy, TimeUpdateStrategy::ManualDuration(delta));
+        if live.is_none() {
+            commands.insert_resource(LiveClock(previous));
+        }
+    }
+}
+
+/// Replace device input with the recorded frame. Pause or cancel on a real
+/// device stops the replay.
+fn play_back_input(mut input: ResMut<GameInput>, mut playback: ResMut<ReplayPlayback>) {
+    if input.pause || input.cancel {
+        playback.stop();
+    }
+    *input = playback
+        .current()
+        .map(|(_, recorded)| recorded.clone())
+        .unwrap_or_default();
+}
+
+fn record_frame(time: Res<Time>, input: Res<GameInput>, mut recording: ResMut<Recording>) {
+    recording.0.push(time.delta(), &input);
+}
+
+/// Save the finished recording, or end playback and report how it compared.
+fn finish_run(
+    mut commands: Commands,
+    mut finished: MessageReader<MiniGameFinished>,
+    recording: Option<Res<Recording>>,
+    playback: Option<Res<ReplayPlayback>>,
+    tick_rate: Res<TickRate>,
+    mut fixed: ResMut<Time<Fixed>>,
+) {
+    let Some(run) = finished.read().next().copied() else {
+        return;
+    };
+    finished.clear();
+
+    if recording.is_some() {
+        commands.queue(move |world: &mut World| {
+            let Some(Recording(mut replay)) = world.remove_resource::<Recording>() else {
+                return;
+            };
+            replay.score = run.score;
+            let path = replay_path(&world.resource::<DataDir>().0, replay.game);
+            if let Err(err) = replay.write(&path) {
+                error!("Failed to save replay: {}", err);
+            }
+            world.insert_resource(LastReplay(replay));
+        });
+    }
+
+    if let Some(playback) = playback {
+        match playback.verdict(run.score) {
+            _ if playback.is_demo() => {}
+            ReplayVerdict::Match => info!("Replay reproduced its score of {}", run.score),
+            ReplayVerdict::Desync { expected, actual } => {
+                warn!("Replay desynced: recorded score {expected}, replayed {actual}")
+            }
+            ReplayVerdict::Stopped => info!("Replay stopped by the viewer"),
+        }
+        commands.remove_resource::<ReplayPlayback>();
+        commands.queue(|world: &mut World| {
+            let live = world
+                .remove_resource::<LiveClock>()
+                .map_or(TimeUpdateStrategy::Automatic, |LiveClock(live)| live);
+            world.insert_resource(live);
+        });
+        fixed.set_timestep_hz(tick_rate.0);
+    }
+}
+
+/// Move to the next recorded frame. Once the frames run out the run is
+/// quit, as the recorded player did from the pause menu.
+fn advance_playback(
+    mut playback: ResMut<ReplayPlayback>,
+    mut quit: MessageWriter<QuitMiniGame>,
+    mut pause: ResMut<NextState<PauseState>>,
+) {
+    playback.advance();
+    if playback.is_finished() {
+        quit.write(QuitMiniGame);
+        pause.set(PauseState::Paused);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::states::Era;
+
+    const GAME: MiniGameId = MiniGameId {
+        era: Era::The80s,
+        index: 0,
+    };
+
+    fn frame(move_x: f32) -> GameInput {
+        let mut input = GameInput::default();
+        input.move_direction = Vec2::new(move_x, 0.0);
+        input
+    }
+
+    #[test]
+    fn test_identical_frames_are_run_length_encoded() {
+        let mut replay = Replay::new(GAME, 7);
+        let delta = Duration::from_micros(16_667);
+        for input in [frame(0.0), frame(0.0), frame(1.0), frame(1.0), frame(0.0)] {
+            replay.push(delta, &input);
+        }
+
+        assert_eq!(replay.len(), 5);
+        assert_eq!(replay.inputs.len(), 3);
+        let played: Vec<f32> = replay
+            .frames()
+            .map(|(d, input)| {
+                assert_eq!(d, delta);
+                input.move_direction.x
+            })
+            .collect();
+        assert_eq!(played, vec![0.0, 0.0, 1.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_playback_walks_every_frame() {
+        let mut replay = Replay::new(GAME, 7);
+        replay.push(Duration::from_millis(10), &frame(0.0));
+        replay.push(Duration::from_millis(20), &frame(0.0));
+        replay.push(Duration::from_millis(30), &frame(1.0));
+        let expected: Vec<_> = replay.frames().map(|(d, i)| (d, i.clone())).collect();
+
+        let mut playback = ReplayPlayback::new(replay);
+        let mut played = Vec::new();
+        while let Some((d, input)) = playback.current() {
+            played.push((d, input.clone()));
+            playback.advance();
+        }
+
+        assert_eq!(played, expected);
+        assert!(playback.is_finished());
+    }
+
+    #[test]
+    fn test_verdict_compares_scores() {
+        let mut replay = Replay::new(GAME, 7);
+        replay.score = 500;
+        let mut playback = ReplayPlayback::new(replay);
+
+        assert_eq!(playback.verdict(500), ReplayVerdict::Match);
+        assert_eq!(
+            playback.verdict(450),
+            ReplayVerdict::Desync {
+                expected: 500,
+                actual: 450
+            }
+        );
+        playback.stop();
+        assert_eq!(playback.verdict(500), ReplayVerdict::Stopped);
+    }
+
+    #[test]
+    fn test_replay_file_round_trip() {
+        let dir =
+            std::env::temp_dir().join(format!("retrogamegame-replay-test-{}", std::process::id()));
+        let path = dir.join("replay.json");
+        let mut replay = Replay::new(GAME, 42);
+        replay.score = 120;
+        replay.push(Duration::from_millis(16), &frame(-1.0));
+        replay.write(&path).unwrap();
+
+        let loaded = Replay::read(&path).unwrap().unwrap();
+        assert_eq!(loaded.game, GAME);
+        assert_eq!(loaded.seed, 42);
+        assert_eq!(loaded.score, 120);
+        assert_eq!(loaded.tick_rate, DEFAULT_TICK_RATE);
+        assert_eq!(loaded.frames().count(), 1);
+        assert!(Replay::read(&dir.join("missing.json")).unwrap().is_none());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,425 @@
+//! On-disk save format: a versioned envelope around `PlayerProgress`,
+//! written atomically with a rolling backup.
+//!
+//! Layout history:
+//! - v1: bare `PlayerProgress` JSON with no envelope.
+//! - v2: `{ "version": 2, "progress": { .. } }`, game keys written as `"era:index"`.
+//! - v3: `achievement_counters` moved into `stats.counters`.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use super::progression::PlayerProgress;
+
+/// Current save schema version written by `write_save`.
+pub const SAVE_VERSION: u32 = 3;
+
+/// Migrations indexed by the version they upgrade *from* (index 0 = v1 → v2).
+const MIGRATIONS: &[fn(Value) -> Value] = &[migrate_v1_to_v2, migrate_v2_to_v3];
+
+// ─── Errors ────────────────────────────────────────────────────────
+
+/// Why a save file could not be read or written.
+#[derive(Debug)]
+pub enum SaveError {
+    /// The file could not be read, written or renamed.
+    Io(io::Error),
+    /// The file is not valid JSON or does not match the schema.
+    Parse(serde_json::Error),
+    /// The file was written by a newer build than this one.
+    UnsupportedVersion(u32),
+    /// The file claims a version that never existed.
+    InvalidVersion(u32),
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveError::Io(err) => write!(f, "save file I/O failed: {err}"),
+            SaveError::Parse(err) => write!(f, "save file is corrupt: {err}"),
+            SaveError::UnsupportedVersion(v) => write!(
+                f,
+                "save file version {v} is newer than supported version {SAVE_VERSION}"
+            ),
+            SaveError::InvalidVersion(v) => write!(f, "save file version {v} is not valid"),
+        }
+    }
+}
+
+impl std::error::Error for SaveError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SaveError::Io(err) => Some(err),
+            SaveError::Parse(err) => Some(err),
+            SaveError::UnsupportedVersion(_) | SaveError::InvalidVersion(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for SaveError {
+    fn from(err: io::Error) -> Self {
+        SaveError::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for SaveError {
+    fn from(err: serde_json::Error) -> Self {
+        SaveError::Parse(err)
+    }
+}
+
+// ─── Envelope ──────────────────────────────────────────────────────
+
+#[derive(Serialize, Deserialize)]
+struct SaveFile {
+    version: u32,
+    progress: PlayerProgress,
+}
+
+/// Serialize `progress` into the current envelope and write it atomically.
+/// The previous save, if any, is kept as `<name>.bak`.
+pub fn write_save(path: &Path, progress: &PlayerProgress) -> Result<(), SaveError> {
+    let file = SaveFile {
+        version: SAVE_VERSION,
+        progress: progress.clone(),
+    };
+    let json = serde_json::to_string_pretty(&file)?;
+    write_atomic(path, &json)?;
+    Ok(())
+}
+
+/// Write `contents` to a temp file next to `path`, then rename it into
+/// place. The file being replaced is first copied to `<name>.bak`, so
+/// `path` itself is never missing.
+pub fn write_atomic(path: &Path, contents: &str) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let tmp = sibling(path, "tmp");
+    {
+        let mut out = fs::File::create(&tmp)?;
+        out.write_all(contents.as_bytes())?;
+        out.sync_all()?;
+    }
+
+    if path.exists() {
+        fs::copy(path, backup_path(path))?;
+    }
+    fs::rename(&tmp, path)
+}
+
+/// Read and migrate a single save file. Returns `Ok(None)` if it doesn't exist.
+pub fn read_save(path: &Path) -> Result<Option<PlayerProgress>, SaveError> {
+    let json = match fs::read_to_string(path) {
+        Ok(json) => json,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+    let value = migrate(serde_json::from_str(&json)?)?;
+    let file: SaveFile = serde_json::from_value(value)?;
+    Ok(Some(file.progress))
+}
+
+/// Read the save at `path`, falling back to its `.bak` if the primary file
+/// is missing or unreadable. A corrupt primary is moved aside to
+/// `<name>.corrupt` so the next write doesn't rotate it over the good backup.
+pub fn read_save_or_backup(path: &Path) -> Result<Option<PlayerProgress>, SaveError> {
+    match read_save(path) {
+        Ok(Some(progress)) => Ok(Some(progress)),
+        Ok(None) => {
+            let backup = backup_path(path);
+            let restored = read_save(&backup)?;
+            if restored.is_some() {
+                bevy::log::warn!("{:?} is missing; restored progress from backup", path);
+            }
+            Ok(restored)
+        }
+        Err(primary_err) => {
+            let backup = backup_path(path);
+            match read_save(&backup) {
+                Ok(Some(progress)) => {
+                    bevy::log::warn!(
+                        "{}; restored progress from backup {:?}",
+                        primary_err,
+                        backup
+                    );
+                    let _ = fs::rename(path, sibling(path, "corrupt"));
+                    Ok(Some(progress))
+                }
+                _ => Err(primary_err),
+            }
+        }
+    }
+}
+
+/// Path of the rolling backup kept next to `path`.
+pub fn backup_path(path: &Path) -> PathBuf {
+    sibling(path, "bak")
+}
+
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+// ─── Migrations ────────────────────────────────────────────────────
+
+fn version_of(value: &Value) -> u32 {
+    value
+        .get("version")
+        .and_then(Value::as_u64)
+        .map_or(1, |v| v as u32)
+}
+
+fn migrate(mut value: Value) -> Result<Value, SaveError> {
+    let mut version = version_of(&value);
+    if version == 0 {
+        return Err(SaveError::InvalidVersion(version));
+    }
+    if version > SAVE_VERSION {
+        return Err(SaveError::UnsupportedVersion(version));
+    }
+    while version < SAVE_VERSION {
+        value = MIGRATIONS[(version - 1) as usize](value);
+        version = version_of(&value);
+    }
+    Ok(value)
+}
+
+/// v1 stored `PlayerProgress` directly. Game-keyed maps could never be
+/// written in v1, so only the envelope needs adding.
+fn migrate_v1_to_v2(progress: Value) -> Value {
+    serde_json::json!({ "version": 2, "progress": progress })
+}
+
+/// v2 kept the counters achievements watch in `achievement_counters`; they
+/// now live with the rest of the stats. A counter already in
+/// `stats.counters` wins.
+fn migrate_v2_to_v3(mut file: Value) -> Value {
+    if let Some(progress) = file.get_mut("progress").and_then(Value::as_object_mut)
+        && let Some(Value::Object(old)) = progress.remove("achievement_counters")
+    {
+        let stats = progress
+            .entry("stats")
+            .or_insert_with(|| serde_json::json!({}));
+        if let Some(stats) = stats.as_object_mut() {
+            let counters = stats
+                .entry("counters")
+                .or_insert_with(|| serde_json::json!({}));
+            if let Some(counters) = counters.as_object_mut() {
+                for (stat, value) in old {
+                    counters.entry(stat).or_insert(value);
+                }
+            }
+        }
+    }
+    file["version"] = 3.into();
+    file
+}
+
+// ─── Key encoding ──────────────────────────────────────────────────
+
+/// Serde adapter for `HashMap<(u8, u8), V>`: JSON object keys must be
+/// strings, so `(era, index)` is written as `"era:index"`.
+pub(crate) mod game_key_map {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::collections::{BTreeMap, HashMap};
+
+    pub fn serialize<V, S>(map: &HashMap<(u8, u8), V>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        V: Serialize,
+        S: Serializer,
+    {
+        // Sorted so save files diff cleanly.
+        let encoded: BTreeMap<String, &V> = map
+            .iter()
+            .map(|((era, index), v)| (format!("{era}:{index}"), v))
+            .collect();
+        encoded.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, V, D>(deserializer: D) -> Result<HashMap<(u8, u8), V>, D::Error>
+    where
+        V: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        let encoded = HashMap::<String, V>::deserialize(deserializer)?;
+        encoded
+            .into_iter()
+            .map(|(key, v)| {
+                let parsed = key
+                    .split_once(':')
+                    .and_then(|(era, index)| Some((era.parse().ok()?, index.parse().ok()?)));
+                parsed
+                    .map(|k| (k, v))
+                    .ok_or_else(|| D::Error::custom(format!("invalid game key {key:?}")))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_save_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "retrogamegame-save-test-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        dir.join("save.json")
+    }
+
+    #[test]
+    fn test_round_trip_with_game_keys() {
+        let path = temp_save_path("round_trip");
+        let mut progress = PlayerProgress::default();
+        progress.high_scores.insert((1, 2), 4200);
+        progress.games_completed.insert((0, 0), true);
+
+        write_save(&path, &progress).unwrap();
+        let loaded = read_save(&path).unwrap().unwrap();
+
+        assert_eq!(loaded.high_scores.get(&(1, 2)), Some(&4200));
+        assert_eq!(loaded.games_completed.get(&(0, 0)), Some(&true));
+        assert_eq!(loaded.games_unlocked, progress.games_unlocked);
+    }
+
+    #[test]
+    fn test_leaderboards_round_trip() {
+        use crate::core::leaderboard::LeaderboardEntry;
+        use crate::core::states::{Era, MiniGameId};
+
+        let path = temp_save_path("leaderboards");
+        let game = MiniGameId {
+            era: Era::The90s,
+            index: 1,
+        };
+        let mut progress = PlayerProgress::default();
+        progress.leaderboard_mut(game).insert(LeaderboardEntry {
+            initials: "JLT".to_string(),
+            score: 900,
+            date: 1_700_000_000,
+            seed: 42,
+            replay: Some("1-1-1700000000.json".to_string()),
+        });
+
+        write_save(&path, &progress).unwrap();
+        let loaded = read_save(&path).unwrap().unwrap();
+
+        assert_eq!(loaded.leaderboard(game), progress.leaderboard(game));
+    }
+
+    #[test]
+    fn test_second_write_keeps_backup() {
+        let path = temp_save_path("backup");
+        let mut progress = PlayerProgress {
+            tokens: 10,
+            ..Default::default()
+        };
+        write_save(&path, &progress).unwrap();
+        progress.tokens = 20;
+        write_save(&path, &progress).unwrap();
+
+        let backup = read_save(&backup_path(&path)).unwrap().unwrap();
+        assert_eq!(backup.tokens, 10);
+        assert!(!sibling(&path, "tmp").exists());
+    }
+
+    #[test]
+    fn test_corrupt_primary_falls_back_to_backup() {
+        let path = temp_save_path("corrupt");
+        let progress = PlayerProgress {
+            tokens: 7,
+            ..Default::default()
+        };
+        write_save(&path, &progress).unwrap();
+        write_save(&path, &progress).unwrap();
+        fs::write(&path, "{ not json").unwrap();
+
+        assert!(matches!(read_save(&path), Err(SaveError::Parse(_))));
+        let restored = read_save_or_backup(&path).unwrap().unwrap();
+        assert_eq!(restored.tokens, 7);
+    }
+
+    #[test]
+    fn test_missing_primary_falls_back_to_backup() {
+        let path = temp_save_path("only_backup");
+        let progress = PlayerProgress {
+            tokens: 3,
+            ..Default::default()
+        };
+        write_save(&path, &progress).unwrap();
+        write_save(&path, &progress).unwrap();
+        // As if a crash hit between rotating the backup and renaming the
+        // new save into place.
+        fs::remove_file(&path).unwrap();
+
+        assert!(read_save(&path).unwrap().is_none());
+        let restored = read_save_or_backup(&path).unwrap().unwrap();
+        assert_eq!(restored.tokens, 3);
+    }
+
+    #[test]
+    fn test_v1_save_is_migrated() {
+        let path = temp_save_path("v1");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(
+            &path,
+            r#"{"tokens":5,"high_scores":{},"eras_unlocked":{"0":true},"games_unlocked":{},"games_completed":{}}"#,
+        )
+        .unwrap();
+
+        let loaded = read_save(&path).unwrap().unwrap();
+        assert_eq!(loaded.tokens, 5);
+        assert_eq!(loaded.eras_unlocked.get(&0), Some(&true));
+    }
+
+    #[test]
+    fn test_v2_achievement_counters_move_into_stats() {
+        let path = temp_save_path("v2");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(
+            &path,
+            r#"{"version":2,"progress":{"tokens":1,"high_scores":{},"eras_unlocked":{},"games_unlocked":{},"games_completed":{},"achievement_counters":{"tunnel_miner.emeralds":12}}}"#,
+        )
+        .unwrap();
+
+        let loaded = read_save(&path).unwrap().unwrap();
+        assert_eq!(loaded.stat("tunnel_miner.emeralds"), 12);
+    }
+
+    #[test]
+    fn test_newer_version_is_rejected() {
+        let path = temp_save_path("future");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, r#"{"version":99,"progress":{}}"#).unwrap();
+
+        assert!(matches!(
+            read_save(&path),
+            Err(SaveError::UnsupportedVersion(99))
+        ));
+    }
+
+    #[test]
+    fn test_version_zero_is_rejected() {
+        let path = temp_save_path("zero");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, r#"{"version":0,"progress":{}}"#).unwrap();
+
+        assert!(matches!(
+            read_save(&path),
+            Err(SaveError::InvalidVersion(0))
+        ));
+    }
+}
@@ -0,0 +1,281 @@
+//! Player preferences: audio levels, window, CRT look, era palettes and
+//! screen shake.
+//!
+//! `UserSettings` is saved to `settings.json` whenever it changes. Systems
+//! that own the affected output (audio sinks, the primary window, the CRT
+//! effect, camera shake) read it every time it changes, so edits on the
+//! settings screen apply immediately. Input bindings have their own file,
+//! see `InputBindings`.
+
+use bevy::audio::{AudioSink, AudioSinkPlayback, Volume};
+use bevy::prelude::*;
+use bevy::window::{MonitorSelection, PrimaryWindow, WindowMode};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use super::progression::DataDir;
+use super::save::{SaveError, write_atomic};
+
+/// Plugin that loads, saves and applies `UserSettings`.
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<UserSettings>()
+            .add_systems(Startup, load_settings)
+            .add_systems(
+                Update,
+                (
+                    apply_channel_volumes,
+                    apply_window_settings.run_if(resource_changed::<UserSettings>),
+                ),
+            )
+            .add_systems(
+                Last,
+                save_settings.run_if(
+                    resource_changed::<UserSettings>.and(not(resource_added::<UserSettings>)),
+                ),
+            );
+    }
+}
+
+/// File name of the settings file inside the data directory.
+pub const SETTINGS_FILE: &str = "settings.json";
+
+/// Window size used until the player picks another.
+pub const DEFAULT_RESOLUTION: UVec2 = UVec2::new(1024, 768);
+
+/// Window sizes offered on the settings screen.
+pub const RESOLUTIONS: [UVec2; 7] = [
+    UVec2::new(800, 600),
+    UVec2::new(1024, 768),
+    UVec2::new(1280, 720),
+    UVec2::new(1280, 1024),
+    UVec2::new(1600, 900),
+    UVec2::new(1920, 1080),
+    UVec2::new(2560, 1440),
+];
+
+// ─── Settings ──────────────────────────────────────────────────────
+
+/// Player preferences, saved to `settings.json`.
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UserSettings {
+    /// Scales every channel, from 0 to 1.
+    pub master_volume: f32,
+    pub music_volume: f32,
+    /// Borderless fullscreen on the current monitor instead of a window.
+    pub fullscreen: bool,
+    /// Window size when not fullscreen.
+    pub resolution: UVec2,
+    pub crt_enabled: bool,
+    pub crt_scanlines: f32,
+    pub crt_curvature: f32,
+    pub crt_vignette: f32,
+    pub crt_brightness: f32,
+    /// Snap the picture to the era's palette while one of its games plays.
+    pub palette_quantization: bool,
+    /// Multiplier on camera shake; 0 turns it off.
+    pub screen_shake: f32,
+}
+
+impl Default for UserSettings {
+    fn default() -> Self {
+        Self {
+            master_volume: 1.0,
+            music_volume: 1.0,
+            fullscreen: false,
+            resolution: DEFAULT_RESOLUTION,
+            crt_enabled: true,
+            crt_scanlines: 0.3,
+            crt_curvature: 0.02,
+            crt_vignette: 0.2,
+            crt_brightness: 1.1,
+            palette_quantization: true,
+            screen_shake: 1.0,
+        }
+    }
+}
+
+impl UserSettings {
+    /// Final linear gain for sounds on `channel`, master volume included.
+    pub fn volume(&self, channel: AudioChannel) -> Volume {
+        let level = match channel {
+            AudioChannel::Music => self.music_volume,
+        };
+        Volume::Linear(self.master_volume * level)
+    }
+
+    /// The window mode these settings ask for.
+    pub fn window_mode(&self) -> WindowMode {
+        if self.fullscreen {
+            WindowMode::BorderlessFullscreen(MonitorSelection::Current)
+        } else {
+            WindowMode::Windowed
+        }
+    }
+
+    /// Clamp every value into the range the settings screen offers, so a
+    /// hand-edited file can't mute the game at -3 or shake it at 50x.
+    pub fn clamped(mut self) -> Self {
+        for level in [
+            &mut self.master_volume,
+            &mut self.music_volume,
+            &mut self.crt_scanlines,
+            &mut self.crt_vignette,
+            &mut self.screen_shake,
+        ] {
+            *level = level.clamp(0.0, 1.0);
+        }
+        self.crt_curvature = self.crt_curvature.clamp(0.0, 0.1);
+        self.crt_brightness = self.crt_brightness.clamp(0.5, 1.5);
+        self.resolution = self.resolution.max(UVec2::new(640, 480));
+        self
+    }
+
+    /// Read settings from `path`. Returns `Ok(None)` if it doesn't exist.
+    pub fn read(path: &Path) -> Result<Option<UserSettings>, SaveError> {
+        match fs::read_to_string(path) {
+            Ok(json) => Ok(Some(serde_json::from_str::<UserSettings>(&json)?.clamped())),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Write settings to `path` atomically.
+    pub fn write(&self, path: &Path) -> Result<(), SaveError> {
+        let json = serde_json::to_string_pretty(self)?;
+        write_atomic(path, &json)?;
+        Ok(())
+    }
+}
+
+/// Where the settings file lives.
+pub fn settings_path(dir: &Path) -> PathBuf {
+    dir.join(SETTINGS_FILE)
+}
+
+// ─── Audio ─────────────────────────────────────────────────────────
+
+/// Which volume slider an audio entity follows. Its sink volume is kept at
+/// `UserSettings::volume` for the channel. Only music plays so far; sound
+/// effects get their own channel and slider along with the first one.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioChannel {
+    Music,
+}
+
+fn apply_channel_volumes(
+    settings: Res<UserSettings>,
+    mut sinks: Query<(&AudioChannel, Mut<AudioSink>)>,
+) {
+    for (channel, mut sink) in &mut sinks {
+        if settings.is_changed() || sink.is_added() {
+            sink.set_volume(settings.volume(*channel));
+        }
+    }
+}
+
+// ─── Systems ───────────────────────────────────────────────────────
+
+fn load_settings(dir: Res<DataDir>, mut settings: ResMut<UserSettings>) {
+    match UserSettings::read(&settings_path(&dir.0)) {
+        Ok(Some(loaded)) => *settings = loaded,
+        Ok(None) => {}
+        Err(err) => error!("Could not load settings, using defaults: {}", err),
+    }
+}
+
+fn save_settings(dir: Res<DataDir>, settings: Res<UserSettings>) {
+    if let Err(err) = settings.write(&settings_path(&dir.0)) {
+        error!("Failed to save settings: {}", err);
+    }
+}
+
+/// Resize or switch the window mode after the player changes them. The
+/// window starts out with the saved values (see `LaunchOptions::window`),
+/// and a window the player resized by hand keeps its size until the
+/// resolution setting itself changes.
+fn apply_window_settings(
+    settings: Res<UserSettings>,
+    mut applied: Local<Option<(bool, UVec2)>>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    let wanted = (settings.fullscreen, settings.resolution);
+    let Some(previous) = applied.replace(wanted) else {
+        return;
+    };
+    if previous == wanted {
+        return;
+    }
+    let Ok(mut window) = windows.single_mut() else {
+        return;
+    };
+    if previous.0 != wanted.0 {
+        window.mode = settings.window_mode();
+    }
+    if previous.1 != wanted.1 {
+        let size = settings.resolution;
+        window.resolution.set_physical_resolution(size.x, size.y);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settings_round_trip() {
+        let settings = UserSettings {
+            music_volume: 0.4,
+            fullscreen: true,
+            resolution: UVec2::new(1920, 1080),
+            crt_enabled: false,
+            screen_shake: 0.0,
+            ..default()
+        };
+
+        let json = serde_json::to_string(&settings).unwrap();
+        let loaded: UserSettings = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(loaded, settings);
+    }
+
+    #[test]
+    fn test_missing_fields_use_defaults() {
+        let loaded: UserSettings = serde_json::from_str(r#"{"music_volume":0.5}"#).unwrap();
+        assert_eq!(loaded.music_volume, 0.5);
+        assert_eq!(loaded.resolution, DEFAULT_RESOLUTION);
+        assert!(loaded.crt_enabled);
+    }
+
+    #[test]
+    fn test_out_of_range_values_are_clamped() {
+        let settings = UserSettings {
+            master_volume: -3.0,
+            screen_shake: 50.0,
+            crt_brightness: 9.0,
+            resolution: UVec2::new(10, 10),
+            ..default()
+        }
+        .clamped();
+
+        assert_eq!(settings.master_volume, 0.0);
+        assert_eq!(settings.screen_shake, 1.0);
+        assert_eq!(settings.crt_brightness, 1.5);
+        assert_eq!(settings.resolution, UVec2::new(640, 480));
+    }
+
+    #[test]
+    fn test_master_volume_scales_the_music() {
+        let settings = UserSettings {
+            master_volume: 0.5,
+            music_volume: 0.8,
+            ..default()
+        };
+        assert_eq!(settings.volume(AudioChannel::Music).to_linear(), 0.4);
+    }
+}
@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 /// Plugin that registers all game states.
 pub struct StatesPlugin;
@@ -7,7 +8,11 @@ impl Plugin for StatesPlugin {
     fn build(&self, app: &mut App) {
         app.init_state::<GameState>()
             .add_sub_state::<MenuState>()
-            .add_sub_state::<PlayingState>();
+            .add_sub_state::<TimelineState>()
+            .add_sub_state::<ResultsState>()
+            .add_sub_state::<PlayingState>()
+            .add_sub_state::<PauseState>()
+            .add_sub_state::<PauseMenuState>();
     }
 }
 
@@ -29,8 +34,6 @@ pub enum GameState {
     Playing,
     /// Results / score screen after a mini-game
     Results,
-    /// Settings screen
-    Settings,
     /// Credits screen
     Credits,
 }
@@ -43,10 +46,39 @@ pub enum GameState {
 pub enum MenuState {
     #[default]
     Main,
+    Profiles,
     Settings,
     Credits,
 }
 
+// ─── Timeline sub-state ────────────────────────────────────────────
+
+/// Sub-state active only when `GameState::Timeline` is set.
+#[derive(SubStates, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[source(GameState = GameState::Timeline)]
+pub enum TimelineState {
+    #[default]
+    Hub,
+    /// The token shop, over the hub.
+    Shop,
+    /// The achievement gallery, over the hub.
+    Achievements,
+    /// Lifetime play stats, over the hub.
+    Stats,
+}
+
+// ─── Results sub-state ─────────────────────────────────────────────
+
+/// Sub-state active only when `GameState::Results` is set.
+#[derive(SubStates, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[source(GameState = GameState::Results)]
+pub enum ResultsState {
+    #[default]
+    Summary,
+    /// Every game's top-10 table, over the results.
+    Leaderboards,
+}
+
 // ─── Playing sub-state ─────────────────────────────────────────────
 
 /// Sub-state active only when `GameState::Playing` is set.
@@ -67,10 +99,34 @@ pub enum PlayingState {
     NebulaBouncer,
 }
 
+// ─── Pause sub-state ───────────────────────────────────────────────
+
+/// Sub-state active only when `GameState::Playing` is set.
+/// Mini-game gameplay systems only run while `Running`.
+#[derive(SubStates, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[source(GameState = GameState::Playing)]
+pub enum PauseState {
+    #[default]
+    Running,
+    Paused,
+}
+
+// ─── Pause menu sub-state ──────────────────────────────────────────
+
+/// Sub-state active only when `PauseState::Paused` is set.
+#[derive(SubStates, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[source(PauseState = PauseState::Paused)]
+pub enum PauseMenuState {
+    #[default]
+    Main,
+    /// The settings screen, over the paused game.
+    Settings,
+}
+
 // ─── Era identification ────────────────────────────────────────────
 
 /// Identifies a gaming era (decade).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Era {
     The80s,
     The90s,
@@ -80,6 +136,15 @@ pub enum Era {
 }
 
 impl Era {
+    /// Every era, in timeline order.
+    pub const ALL: [Era; 5] = [
+        Era::The80s,
+        Era::The90s,
+        Era::The2000s,
+        Era::The2010s,
+        Era::Future,
+    ];
+
     /// Human-readable display name for the era.
     pub fn display_name(&self) -> &'static str {
         match self {
@@ -91,6 +156,27 @@ impl Era {
         }
     }
 
+    /// Card title on the timeline.
+    pub fn title(&self) -> &'static str {
+        match self {
+            Era::The80s => "The 1980s",
+            Era::The90s => "The 1990s",
+            Era::The2000s => "The 2000s",
+            Era::The2010s => "The 2010s",
+            Era::Future => "Future",
+        }
+    }
+
+    /// Card subtitle on the timeline.
+    pub fn tagline(&self) -> &'static str {
+        match self {
+            Era::The80s => "The DOS Age",
+            Era::The90s => "The Golden Age",
+            Era::The2000s | Era::The2010s => "Coming Soon",
+            Era::Future => "Experimental Frontier",
+        }
+    }
+
     /// Short label for UI.
     pub fn label(&self) -> &'static str {
         match self {
@@ -104,49 +190,8 @@ impl Era {
 }
 
 /// Identifies a specific mini-game within an era.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct MiniGameId {
     pub era: Era,
     pub index: u8, // 0-based index within the era
 }
-
-impl MiniGameId {
-    pub fn display_name(&self) -> &'static str {
-        match (self.era, self.index) {
-            (Era::The80s, 0) => "Tunnel Miner",
-            (Era::The80s, 1) => "Cosmic Captain",
-            (Era::The80s, 2) => "Star Goose",
-            (Era::The90s, 0) => "Worm Wars",
-            (Era::The90s, 1) => "Ice Blitz",
-            (Era::The90s, 2) => "Depths of Doom",
-            (Era::Future, 0) => "Nebula Bouncer",
-            _ => "Unknown",
-        }
-    }
-
-    pub fn description(&self) -> &'static str {
-        match (self.era, self.index) {
-            (Era::The80s, 0) => "Dig tunnels, collect emeralds, crush enemies!",
-            (Era::The80s, 1) => "Side-scrolling exploration across alien worlds",
-            (Era::The80s, 2) => "Vertical-scrolling shooter over alien terrain",
-            (Era::The90s, 0) => "Turn-based artillery with destructible terrain",
-            (Era::The90s, 1) => "Fast-paced top-down arcade ice hockey",
-            (Era::The90s, 2) => "Turn-based roguelike dungeon crawler",
-            (Era::Future, 0) => "Ricochet-driven sci-fi shooter with buildcrafting",
-            _ => "",
-        }
-    }
-
-    pub fn playing_state(&self) -> PlayingState {
-        match (self.era, self.index) {
-            (Era::The80s, 0) => PlayingState::TunnelMiner,
-            (Era::The80s, 1) => PlayingState::CosmicCaptain,
-            (Era::The80s, 2) => PlayingState::StarGoose,
-            (Era::The90s, 0) => PlayingState::WormWars,
-            (Era::The90s, 1) => PlayingState::IceBlitz,
-            (Era::The90s, 2) => PlayingState::DepthsOfDoom,
-            (Era::Future, 0) => PlayingState::NebulaBouncer,
-            _ => PlayingState::TunnelMiner,
-        }
-    }
-}
@@ -0,0 +1,306 @@
+//! Lifetime play statistics.
+//!
+//! `PlayerStats` lives in `PlayerProgress`, so it is saved with the rest of
+//! the profile. Every finished run adds to its game's play count, play time,
+//! deaths and win streak. Mini-games report everything else as `StatEvent`s
+//! into named counters (`game.counter`), which achievements watch too.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+
+use super::progression::{MiniGameOutcome, era_to_index, game_to_key};
+use super::save::game_key_map;
+use super::states::{Era, GameState, MiniGameId, PauseState};
+
+/// Plugin for stat events and the run clock.
+pub struct StatsPlugin;
+
+impl Plugin for StatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RunClock>()
+            .add_message::<StatEvent>()
+            .add_systems(OnEnter(GameState::Playing), reset_run_clock)
+            .add_systems(Update, tick_run_clock.run_if(in_state(PauseState::Running)));
+    }
+}
+
+// ─── Messages ──────────────────────────────────────────────────────
+
+/// How a `StatEvent` changes its counter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatKind {
+    /// Add the value to a running total.
+    Add,
+    /// Keep the highest value seen.
+    Best,
+}
+
+/// Sent by a mini-game when something worth counting happens.
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatEvent {
+    pub stat: &'static str,
+    pub value: u64,
+    pub kind: StatKind,
+}
+
+impl StatEvent {
+    /// Add `amount` to the `stat` total.
+    pub const fn add(stat: &'static str, amount: u64) -> Self {
+        Self {
+            stat,
+            value: amount,
+            kind: StatKind::Add,
+        }
+    }
+
+    /// Raise the `stat` best to `value` if it's higher.
+    pub const fn best(stat: &'static str, value: u64) -> Self {
+        Self {
+            stat,
+            value,
+            kind: StatKind::Best,
+        }
+    }
+}
+
+// ─── Data ──────────────────────────────────────────────────────────
+
+/// Everything recorded about how the player plays.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PlayerStats {
+    /// Per-game run totals, keyed by (era_index, game_index)
+    #[serde(default, with = "game_key_map")]
+    pub games: HashMap<(u8, u8), GameStats>,
+    /// Stat event counters, keyed by stat name
+    #[serde(default)]
+    pub counters: BTreeMap<String, u64>,
+}
+
+/// Run totals for one mini-game.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameStats {
+    pub plays: u64,
+    /// Unpaused time spent in runs, in seconds.
+    pub play_time_secs: f64,
+    /// Runs that ended in defeat.
+    pub deaths: u64,
+    pub victories: u64,
+    /// Victories in a row, up to the latest run.
+    pub win_streak: u64,
+    pub best_win_streak: u64,
+}
+
+impl GameStats {
+    /// Add `other`'s totals to these, keeping the better best streak.
+    fn merge(&mut self, other: &GameStats) {
+        self.plays += other.plays;
+        self.play_time_secs += other.play_time_secs;
+        self.deaths += other.deaths;
+        self.victories += other.victories;
+        self.win_streak = self.win_streak.max(other.win_streak);
+        self.best_win_streak = self.best_win_streak.max(other.best_win_streak);
+    }
+}
+
+impl PlayerStats {
+    /// Run totals for a game.
+    pub fn game(&self, game: MiniGameId) -> GameStats {
+        self.games
+            .get(&game_to_key(game))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Run totals summed over every game in `era`.
+    pub fn era(&self, era: Era) -> GameStats {
+        let index = era_to_index(era);
+        let mut total = GameStats::default();
+        for (_, stats) in self.games.iter().filter(|((e, _), _)| *e == index) {
+            total.merge(stats);
+        }
+        total
+    }
+
+    /// Run totals summed over every game.
+    pub fn total(&self) -> GameStats {
+        let mut total = GameStats::default();
+        for stats in self.games.values() {
+            total.merge(stats);
+        }
+        total
+    }
+
+    /// Count a finished run that took `play_time`.
+    pub fn record_run(&mut self, game: MiniGameId, outcome: MiniGameOutcome, play_time: Duration) {
+        let stats = self.games.entry(game_to_key(game)).or_default();
+        stats.plays += 1;
+        stats.play_time_secs += play_time.as_secs_f64();
+        match outcome {
+            MiniGameOutcome::Victory => {
+                stats.victories += 1;
+                stats.win_streak += 1;
+                stats.best_win_streak = stats.best_win_streak.max(stats.win_streak);
+            }
+            MiniGameOutcome::Defeat => {
+                stats.deaths += 1;
+                stats.win_streak = 0;
+            }
+            MiniGameOutcome::Quit => stats.win_streak = 0,
+        }
+    }
+
+    /// Current value of a counter.
+    pub fn counter(&self, stat: &str) -> u64 {
+        self.counters.get(stat).copied().unwrap_or(0)
+    }
+
+    /// Apply a stat event to its counter and return the new value.
+    pub fn record(&mut self, event: &StatEvent) -> u64 {
+        let counter = self.counters.entry(event.stat.to_string()).or_insert(0);
+        *counter = match event.kind {
+            StatKind::Add => counter.saturating_add(event.value),
+            StatKind::Best => (*counter).max(event.value),
+        };
+        *counter
+    }
+}
+
+// ─── Counter labels ────────────────────────────────────────────────
+
+/// A counter shown on the stats screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CounterInfo {
+    pub game: MiniGameId,
+    pub stat: &'static str,
+    pub label: &'static str,
+}
+
+const fn counter(era: Era, index: u8, stat: &'static str, label: &'static str) -> CounterInfo {
+    CounterInfo {
+        game: MiniGameId { era, index },
+        stat,
+        label,
+    }
+}
+
+/// Every counter the mini-games report, in stats screen order.
+pub const COUNTERS: &[CounterInfo] = &[
+    counter(
+        Era::The80s,
+        0,
+        "tunnel_miner.emeralds",
+        "Emeralds collected",
+    ),
+    counter(
+        Era::The80s,
+        0,
+        "tunnel_miner.emerald_streak",
+        "Best emerald streak",
+    ),
+    counter(
+        Era::The80s,
+        1,
+        "cosmic_captain.items_found",
+        "Most items in a run",
+    ),
+    counter(Era::The80s, 2, "star_goose.kills", "Enemies destroyed"),
+    counter(Era::The80s, 2, "star_goose.crystals", "Crystals picked up"),
+    counter(Era::The90s, 0, "worm_wars.flawless_wins", "Flawless wins"),
+    counter(Era::The90s, 1, "ice_blitz.goals", "Goals scored"),
+    counter(
+        Era::The90s,
+        1,
+        "ice_blitz.goals_in_game",
+        "Most goals in a game",
+    ),
+    counter(Era::The90s, 2, "depths_of_doom.depth", "Deepest floor"),
+    counter(Era::The90s, 2, "depths_of_doom.kills", "Monsters defeated"),
+    counter(Era::Future, 0, "nebula_bouncer.orbs_fired", "Orbs fired"),
+    counter(
+        Era::Future,
+        0,
+        "nebula_bouncer.bounce_kill",
+        "Most bounces on a kill",
+    ),
+];
+
+/// The counters `game` reports.
+pub fn counters_for(game: MiniGameId) -> impl Iterator<Item = &'static CounterInfo> {
+    COUNTERS.iter().filter(move |info| info.game == game)
+}
+
+// ─── Run clock ─────────────────────────────────────────────────────
+
+/// Unpaused real time spent in the current run.
+#[derive(Resource, Debug, Default)]
+pub struct RunClock(pub Duration);
+
+fn reset_run_clock(mut clock: ResMut<RunClock>) {
+    clock.0 = Duration::ZERO;
+}
+
+fn tick_run_clock(time: Res<Time<Real>>, mut clock: ResMut<RunClock>) {
+    clock.0 += time.delta();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TUNNEL_MINER: MiniGameId = MiniGameId {
+        era: Era::The80s,
+        index: 0,
+    };
+    const STAR_GOOSE: MiniGameId = MiniGameId {
+        era: Era::The80s,
+        index: 2,
+    };
+
+    #[test]
+    fn test_runs_add_up_per_game_and_era() {
+        let mut stats = PlayerStats::default();
+        let minute = Duration::from_secs(60);
+        stats.record_run(TUNNEL_MINER, MiniGameOutcome::Victory, minute);
+        stats.record_run(TUNNEL_MINER, MiniGameOutcome::Victory, minute);
+        stats.record_run(TUNNEL_MINER, MiniGameOutcome::Defeat, minute);
+        stats.record_run(TUNNEL_MINER, MiniGameOutcome::Victory, minute);
+        stats.record_run(STAR_GOOSE, MiniGameOutcome::Quit, minute);
+
+        let miner = stats.game(TUNNEL_MINER);
+        assert_eq!(miner.plays, 4);
+        assert_eq!(miner.deaths, 1);
+        assert_eq!(miner.win_streak, 1);
+        assert_eq!(miner.best_win_streak, 2);
+
+        let era = stats.era(Era::The80s);
+        assert_eq!(era.plays, 5);
+        assert_eq!(era.play_time_secs, 300.0);
+        assert_eq!(stats.era(Era::The90s), GameStats::default());
+    }
+
+    #[test]
+    fn test_counters_add_or_keep_best() {
+        let mut stats = PlayerStats::default();
+        stats.record(&StatEvent::add("ice_blitz.goals", 2));
+        stats.record(&StatEvent::add("ice_blitz.goals", 3));
+        stats.record(&StatEvent::best("ice_blitz.goals_in_game", 3));
+        stats.record(&StatEvent::best("ice_blitz.goals_in_game", 1));
+        assert_eq!(stats.counter("ice_blitz.goals"), 5);
+        assert_eq!(stats.counter("ice_blitz.goals_in_game"), 3);
+    }
+
+    #[test]
+    fn test_every_achievement_stat_is_shown() {
+        let list = crate::core::achievements::AchievementList::bundled().unwrap();
+        for achievement in &list.0 {
+            assert!(
+                COUNTERS.iter().any(|info| info.stat == achievement.stat),
+                "{} has no label",
+                achievement.stat
+            );
+        }
+    }
+}
@@ -0,0 +1,40 @@
+//! Fixed-timestep simulation clock.
+//!
+//! Mini-game gameplay runs in `FixedUpdate`, so it advances in identical
+//! steps however fast frames are drawn. `TickRate` sets how many of those
+//! steps happen per second; rendering smooths over the gaps with `Transform`
+//! interpolation.
+
+use bevy::prelude::*;
+use bevy::time::TimeSystems;
+
+/// Gameplay ticks per second unless configured otherwise.
+pub const DEFAULT_TICK_RATE: f64 = 60.0;
+
+/// Plugin that keeps `Time<Fixed>` in step with `TickRate`.
+pub struct TickPlugin;
+
+impl Plugin for TickPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TickRate>().add_systems(
+            First,
+            apply_tick_rate
+                .before(TimeSystems)
+                .run_if(resource_changed::<TickRate>),
+        );
+    }
+}
+
+/// Gameplay ticks per second.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct TickRate(pub f64);
+
+impl Default for TickRate {
+    fn default() -> Self {
+        Self(DEFAULT_TICK_RATE)
+    }
+}
+
+fn apply_tick_rate(rate: Res<TickRate>, mut fixed: ResMut<Time<Fixed>>) {
+    fixed.set_timestep_hz(rate.0);
+}
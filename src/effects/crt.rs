@@ -1,12 +1,74 @@
+//! CRT post-processing pass.
+//!
+//! Cameras carrying `CrtCamera` get a full-screen pass after the UI is drawn
+//! that adds scanlines, barrel curvature, a vignette, a phosphor mask and a
+//! brightness boost. The player's `CrtSettings` set the overall look and the
+//! current era's `CrtPreset` scales it, so the 80s are heavier on the glass
+//! than the 2010s. A CRT preset bought in the shop replaces the era's.
+//! Without a renderer (headless runs, tests) only the main-world bookkeeping
+//! runs.
+
+use bevy::asset::{embedded_asset, load_embedded_asset};
+use bevy::core_pipeline::FullscreenShader;
+use bevy::core_pipeline::core_2d::graph::{Core2d, Node2d};
+use bevy::ecs::query::QueryItem;
 use bevy::prelude::*;
+use bevy::render::extract_component::{
+    ComponentUniforms, DynamicUniformIndex, ExtractComponent, ExtractComponentPlugin,
+    UniformComponentPlugin,
+};
+use bevy::render::render_graph::{
+    InternedRenderLabel, NodeRunError, RenderGraph, RenderGraphContext, RenderGraphExt,
+    RenderLabel, ViewNode, ViewNodeRunner,
+};
+use bevy::render::render_resource::binding_types::{sampler, texture_2d, uniform_buffer};
+use bevy::render::render_resource::*;
+use bevy::render::renderer::{RenderContext, RenderDevice};
+use bevy::render::view::ViewTarget;
+use bevy::render::{RenderApp, RenderStartup};
+use bevy::ui_render::graph::NodeUi;
+
+use crate::core::cosmetics::CosmeticSlot;
+use crate::core::progression::PlayerProgress;
+use crate::core::settings::UserSettings;
+use crate::core::states::{Era, GameState};
+use crate::ui::era_select::CurrentEra;
 
-/// CRT scanline effect plugin.
-/// Currently a placeholder — will use a WGSL post-processing shader.
+/// CRT effect plugin: settings, per-era presets and the render node.
 pub struct CrtPlugin;
 
 impl Plugin for CrtPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<CrtSettings>();
+        app.init_resource::<CrtSettings>().add_systems(
+            Update,
+            (
+                sync_crt_settings.run_if(resource_changed::<UserSettings>),
+                attach_crt_to_cameras,
+                update_crt_uniforms,
+            )
+                .chain(),
+        );
+
+        // Extraction needs the render world's entity sync, so without a
+        // renderer the uniforms just stay in the main world.
+        if app.get_sub_app(RenderApp).is_none() {
+            return;
+        }
+        app.add_plugins((
+            ExtractComponentPlugin::<CrtUniform>::default(),
+            UniformComponentPlugin::<CrtUniform>::default(),
+        ));
+        embedded_asset!(app, "crt.wgsl");
+
+        let render_app = app.sub_app_mut(RenderApp);
+        let after = frame_drawn_label(render_app);
+        render_app
+            .add_systems(RenderStartup, init_crt_pipeline)
+            .add_render_graph_node::<ViewNodeRunner<CrtNode>>(Core2d, CrtLabel)
+            .add_render_graph_edges(
+                Core2d,
+                (after, CrtLabel.intern(), Node2d::Upscaling.intern()),
+            );
     }
 }
 
@@ -31,3 +93,395 @@ impl Default for CrtSettings {
         }
     }
 }
+
+impl From<&UserSettings> for CrtSettings {
+    fn from(settings: &UserSettings) -> Self {
+        Self {
+            enabled: settings.crt_enabled,
+            scanline_intensity: settings.crt_scanlines,
+            curvature: settings.crt_curvature,
+            vignette: settings.crt_vignette,
+            brightness: settings.crt_brightness,
+        }
+    }
+}
+
+// ─── Presets ───────────────────────────────────────────────────────
+
+/// How strongly a screen leans into the CRT look. The first four scale the
+/// player's `CrtSettings`; `phosphor_mask` is the mask strength itself.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CrtPreset {
+    pub scanlines: f32,
+    pub curvature: f32,
+    pub vignette: f32,
+    pub brightness: f32,
+    pub phosphor_mask: f32,
+}
+
+impl CrtPreset {
+    /// Menus and the timeline: the player's settings as they are.
+    pub const HUB: CrtPreset = CrtPreset {
+        scanlines: 1.0,
+        curvature: 1.0,
+        vignette: 1.0,
+        brightness: 1.0,
+        phosphor_mask: 0.2,
+    };
+
+    /// The preset for screens inside `era`.
+    pub fn for_era(era: Era) -> CrtPreset {
+        match era {
+            Era::The80s => CrtPreset {
+                scanlines: 1.5,
+                curvature: 1.5,
+                vignette: 1.3,
+                brightness: 1.0,
+                phosphor_mask: 0.35,
+            },
+            Era::The90s => CrtPreset {
+                phosphor_mask: 0.25,
+                ..CrtPreset::HUB
+            },
+            Era::The2000s => CrtPreset {
+                scanlines: 0.5,
+                curvature: 0.5,
+                vignette: 0.8,
+                brightness: 1.0,
+                phosphor_mask: 0.1,
+            },
+            Era::The2010s => CrtPreset {
+                scanlines: 0.2,
+                curvature: 0.0,
+                vignette: 0.5,
+                brightness: 1.0,
+                phosphor_mask: 0.0,
+            },
+            Era::Future => CrtPreset {
+                scanlines: 0.0,
+                curvature: 0.0,
+                vignette: 0.3,
+                brightness: 1.0,
+                phosphor_mask: 0.0,
+            },
+        }
+    }
+
+    /// The preset sold in the shop as cosmetic `id`.
+    pub fn cosmetic(id: &str) -> Option<CrtPreset> {
+        match id {
+            "crt_arcade" => Some(CrtPreset {
+                scanlines: 2.0,
+                curvature: 2.0,
+                vignette: 1.5,
+                brightness: 1.1,
+                phosphor_mask: 0.45,
+            }),
+            "crt_trinitron" => Some(CrtPreset {
+                scanlines: 1.2,
+                curvature: 0.0,
+                vignette: 0.6,
+                brightness: 1.0,
+                phosphor_mask: 0.3,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Put on a camera to run the CRT pass on it. New 2D cameras get one
+/// automatically; remove it to opt a camera out.
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct CrtCamera {
+    /// Use this preset instead of following the current era.
+    pub preset: Option<CrtPreset>,
+}
+
+/// The values the shader sees, kept on each `CrtCamera` while the effect
+/// is enabled.
+#[derive(Component, Debug, Clone, Copy, PartialEq, ExtractComponent, ShaderType)]
+pub struct CrtUniform {
+    pub scanlines: f32,
+    pub curvature: f32,
+    pub vignette: f32,
+    pub brightness: f32,
+    pub phosphor_mask: f32,
+}
+
+impl CrtUniform {
+    fn new(settings: &CrtSettings, preset: CrtPreset) -> Self {
+        Self {
+            scanlines: settings.scanline_intensity * preset.scanlines,
+            curvature: settings.curvature * preset.curvature,
+            vignette: settings.vignette * preset.vignette,
+            brightness: settings.brightness * preset.brightness,
+            phosphor_mask: preset.phosphor_mask,
+        }
+    }
+}
+
+// ─── Systems ───────────────────────────────────────────────────────
+
+/// The CRT look follows the player's settings.
+fn sync_crt_settings(settings: Res<UserSettings>, mut crt: ResMut<CrtSettings>) {
+    *crt = CrtSettings::from(&*settings);
+}
+
+fn attach_crt_to_cameras(
+    mut commands: Commands,
+    cameras: Query<Entity, (Added<Camera2d>, Without<CrtCamera>)>,
+) {
+    for camera in &cameras {
+        commands.entity(camera).insert(CrtCamera::default());
+    }
+}
+
+/// The preset for whatever screen is showing.
+fn current_preset(state: &GameState, era: &CurrentEra, progress: &PlayerProgress) -> CrtPreset {
+    if let Some(preset) = progress
+        .equipped(CosmeticSlot::CrtPreset)
+        .and_then(|cosmetic| CrtPreset::cosmetic(cosmetic.id))
+    {
+        return preset;
+    }
+    match state {
+        GameState::EraSelect | GameState::Playing | GameState::Results => {
+            CrtPreset::for_era(era.era)
+        }
+        _ => CrtPreset::HUB,
+    }
+}
+
+fn update_crt_uniforms(
+    mut commands: Commands,
+    settings: Res<CrtSettings>,
+    state: Res<State<GameState>>,
+    era: Res<CurrentEra>,
+    progress: Res<PlayerProgress>,
+    cameras: Query<(Entity, &CrtCamera, Option<&CrtUniform>)>,
+) {
+    let era_preset = current_preset(state.get(), &era, &progress);
+    for (entity, camera, current) in &cameras {
+        if !settings.enabled {
+            if current.is_some() {
+                commands.entity(entity).remove::<CrtUniform>();
+            }
+            continue;
+        }
+        let uniform = CrtUniform::new(&settings, camera.preset.unwrap_or(era_preset));
+        if current != Some(&uniform) {
+            commands.entity(entity).insert(uniform);
+        }
+    }
+}
+
+// ─── Render node ───────────────────────────────────────────────────
+
+#[derive(Debug, Hash, PartialEq, Eq, Clone, RenderLabel)]
+pub(crate) struct CrtLabel;
+
+/// The last node that draws into the 2D frame. Post-process passes go after
+/// it, over the UI too when it's there, so menus get the effects as well.
+pub(crate) fn frame_drawn_label(render_app: &SubApp) -> InternedRenderLabel {
+    match render_app
+        .world()
+        .resource::<RenderGraph>()
+        .get_sub_graph(Core2d)
+    {
+        Some(graph) if graph.get_node_state(NodeUi::UiPass).is_ok() => NodeUi::UiPass.intern(),
+        _ => Node2d::EndMainPassPostProcessing.intern(),
+    }
+}
+
+#[derive(Default)]
+struct CrtNode;
+
+impl ViewNode for CrtNode {
+    type ViewQuery = (
+        &'static ViewTarget,
+        &'static CrtUniform,
+        &'static DynamicUniformIndex<CrtUniform>,
+    );
+
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        (view_target, _crt, uniform_index): QueryItem<Self::ViewQuery>,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let Some(crt_pipeline) = world.get_resource::<CrtPipeline>() else {
+            return Ok(());
+        };
+        // The pipeline targets the default SDR format; HDR views go without.
+        if view_target.main_texture_format() != TextureFormat::bevy_default() {
+            return Ok(());
+        }
+        let pipeline_cache = world.resource::<PipelineCache>();
+        // Still compiling on the first frames.
+        let Some(pipeline) = pipeline_cache.get_render_pipeline(crt_pipeline.pipeline_id) else {
+            return Ok(());
+        };
+        let uniforms = world.resource::<ComponentUniforms<CrtUniform>>();
+        let Some(uniform_binding) = uniforms.uniforms().binding() else {
+            return Ok(());
+        };
+
+        // Source and destination swap on every post-process write, so the
+        // bind group has to be made here rather than in a prepare system.
+        let post_process = view_target.post_process_write();
+        let bind_group = render_context.render_device().create_bind_group(
+            "crt_bind_group",
+            &pipeline_cache.get_bind_group_layout(&crt_pipeline.layout),
+            &BindGroupEntries::sequential((
+                post_process.source,
+                &crt_pipeline.sampler,
+                uniform_binding.clone(),
+            )),
+        );
+
+        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some("crt_pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: post_process.destination,
+                depth_slice: None,
+                resolve_target: None,
+                ops: Operations::default(),
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        render_pass.set_render_pipeline(pipeline);
+        render_pass.set_bind_group(0, &bind_group, &[uniform_index.index()]);
+        render_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
+
+#[derive(Resource)]
+struct CrtPipeline {
+    layout: BindGroupLayoutDescriptor,
+    sampler: Sampler,
+    pipeline_id: CachedRenderPipelineId,
+}
+
+fn init_crt_pipeline(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    asset_server: Res<AssetServer>,
+    fullscreen_shader: Res<FullscreenShader>,
+    pipeline_cache: Res<PipelineCache>,
+) {
+    let layout = BindGroupLayoutDescriptor::new(
+        "crt_bind_group_layout",
+        &BindGroupLayoutEntries::sequential(
+            ShaderStages::FRAGMENT,
+            (
+                texture_2d(TextureSampleType::Float { filterable: true }),
+                sampler(SamplerBindingType::Filtering),
+                uniform_buffer::<CrtUniform>(true),
+            ),
+        ),
+    );
+    let sampler = render_device.create_sampler(&SamplerDescriptor {
+        mag_filter: FilterMode::Linear,
+        min_filter: FilterMode::Linear,
+        ..default()
+    });
+    let pipeline_id = pipeline_cache.queue_render_pipeline(RenderPipelineDescriptor {
+        label: Some("crt_pipeline".into()),
+        layout: vec![layout.clone()],
+        vertex: fullscreen_shader.to_vertex_state(),
+        fragment: Some(FragmentState {
+            shader: load_embedded_asset!(&*asset_server, "crt.wgsl"),
+            targets: vec![Some(ColorTargetState {
+                format: TextureFormat::bevy_default(),
+                blend: None,
+                write_mask: ColorWrites::ALL,
+            })],
+            ..default()
+        }),
+        ..default()
+    });
+
+    commands.insert_resource(CrtPipeline {
+        layout,
+        sampler,
+        pipeline_id,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::cosmetics::cosmetic;
+    use bevy::state::app::StatesPlugin;
+
+    /// No renderer: the plugin still builds and keeps camera uniforms current.
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin, CrtPlugin))
+            .init_state::<GameState>()
+            .init_resource::<UserSettings>()
+            .init_resource::<CurrentEra>()
+            .init_resource::<PlayerProgress>();
+        app
+    }
+
+    #[test]
+    fn test_cameras_follow_settings_without_a_renderer() {
+        let mut app = app();
+        let camera = app.world_mut().spawn(Camera2d).id();
+        app.update();
+
+        let uniform = *app.world().get::<CrtUniform>(camera).unwrap();
+        assert_eq!(
+            uniform,
+            CrtUniform::new(&CrtSettings::default(), CrtPreset::HUB)
+        );
+
+        app.world_mut().resource_mut::<UserSettings>().crt_enabled = false;
+        app.update();
+        assert!(app.world().get::<CrtUniform>(camera).is_none());
+    }
+
+    #[test]
+    fn test_era_preset_applies_inside_an_era() {
+        let mut app = app();
+        app.world_mut().resource_mut::<CurrentEra>().era = Era::The80s;
+        app.world_mut()
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::Playing);
+        let camera = app.world_mut().spawn(Camera2d).id();
+        app.update();
+
+        let uniform = app.world().get::<CrtUniform>(camera).unwrap();
+        let preset = CrtPreset::for_era(Era::The80s);
+        assert_eq!(uniform.scanlines, 0.3 * preset.scanlines);
+        assert_eq!(uniform.phosphor_mask, preset.phosphor_mask);
+    }
+
+    #[test]
+    fn test_equipped_preset_replaces_the_era() {
+        let mut app = app();
+        {
+            let mut progress = app.world_mut().resource_mut::<PlayerProgress>();
+            progress.tokens = 500;
+            progress.buy(cosmetic("crt_trinitron").unwrap()).unwrap();
+        }
+        app.world_mut()
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::Playing);
+        let camera = app.world_mut().spawn(Camera2d).id();
+        app.update();
+
+        let uniform = app.world().get::<CrtUniform>(camera).unwrap();
+        assert_eq!(uniform.curvature, 0.0);
+        assert_eq!(
+            uniform.phosphor_mask,
+            CrtPreset::cosmetic("crt_trinitron").unwrap().phosphor_mask
+        );
+    }
+}
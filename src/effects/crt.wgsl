@@ -0,0 +1,49 @@
+// CRT post-process: barrel curvature, scanlines, aperture-grille phosphor
+// mask, vignette and brightness, applied to the finished frame.
+
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+
+struct CrtUniform {
+    scanlines: f32,
+    curvature: f32,
+    vignette: f32,
+    brightness: f32,
+    phosphor_mask: f32,
+}
+
+@group(0) @binding(0) var screen_texture: texture_2d<f32>;
+@group(0) @binding(1) var screen_sampler: sampler;
+@group(0) @binding(2) var<uniform> crt: CrtUniform;
+
+const PI: f32 = 3.14159265;
+// Physical pixels per scanline, so lines stay visible on large windows.
+const SCANLINE_PERIOD: f32 = 3.0;
+
+// Bulge the image out from the centre like curved glass.
+fn barrel(uv: vec2<f32>, amount: f32) -> vec2<f32> {
+    let centered = uv * 2.0 - 1.0;
+    let bent = centered * (1.0 + centered.yx * centered.yx * amount);
+    return bent * 0.5 + 0.5;
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let uv = barrel(in.uv, crt.curvature * 2.0);
+    if any(uv < vec2(0.0)) || any(uv > vec2(1.0)) {
+        return vec4(0.0, 0.0, 0.0, 1.0);
+    }
+    var color = textureSample(screen_texture, screen_sampler, uv).rgb;
+
+    let size = vec2<f32>(textureDimensions(screen_texture));
+    let line = 0.5 - 0.5 * cos(uv.y * size.y / SCANLINE_PERIOD * 2.0 * PI);
+    color *= 1.0 - crt.scanlines * line;
+
+    var mask = vec3(1.0 - crt.phosphor_mask);
+    mask[u32(in.position.x) % 3u] = 1.0;
+    color *= mask;
+
+    let edge = smoothstep(0.35, 0.75, distance(uv, vec2(0.5)));
+    color *= 1.0 - crt.vignette * edge;
+
+    return vec4(color * crt.brightness, 1.0);
+}
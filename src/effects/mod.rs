@@ -1,13 +1,22 @@
 pub mod crt;
+pub mod palette;
+pub mod presentation;
 pub mod transitions;
+pub mod virtual_screen;
 
 use bevy::prelude::*;
 
-/// Visual effects plugin: CRT shader, screen transitions, era-specific effects.
+/// Visual effects plugin: CRT shader, era palettes, screen transitions and
+/// the virtual screen mini-games render into.
 pub struct EffectsPlugin;
 
 impl Plugin for EffectsPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((crt::CrtPlugin, transitions::TransitionsPlugin));
+        app.add_plugins((
+            crt::CrtPlugin,
+            palette::PalettePlugin,
+            transitions::TransitionsPlugin,
+            virtual_screen::VirtualScreenPlugin,
+        ));
     }
 }
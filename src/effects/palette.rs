@@ -0,0 +1,300 @@
+//! Palette quantization pass.
+//!
+//! While a mini-game plays, 2D cameras get a full-screen pass that snaps the
+//! frame to its era's `EraPalette`, or the palette equipped from the shop,
+//! before the CRT pass adds the glass. Eras with true color and players who
+//! turned `palette_quantization` off skip it. Without a renderer it runs
+//! only the bookkeeping, as the CRT pass does.
+
+use bevy::asset::{embedded_asset, load_embedded_asset};
+use bevy::core_pipeline::FullscreenShader;
+use bevy::core_pipeline::core_2d::graph::Core2d;
+use bevy::ecs::query::QueryItem;
+use bevy::prelude::*;
+use bevy::render::extract_component::{
+    ComponentUniforms, DynamicUniformIndex, ExtractComponent, ExtractComponentPlugin,
+    UniformComponentPlugin,
+};
+use bevy::render::render_graph::{
+    NodeRunError, RenderGraphContext, RenderGraphExt, RenderLabel, ViewNode, ViewNodeRunner,
+};
+use bevy::render::render_resource::binding_types::{texture_2d, uniform_buffer};
+use bevy::render::render_resource::*;
+use bevy::render::renderer::RenderContext;
+use bevy::render::view::ViewTarget;
+use bevy::render::{RenderApp, RenderStartup};
+
+use super::crt::{CrtLabel, frame_drawn_label};
+use super::presentation::{EraPalette, EraPresentation, MAX_PALETTE_COLORS};
+use crate::core::cosmetics::CosmeticSlot;
+use crate::core::progression::PlayerProgress;
+use crate::core::settings::UserSettings;
+use crate::core::states::GameState;
+use crate::ui::era_select::CurrentEra;
+
+/// Palette quantization plugin: per-camera uniforms and the render node.
+pub struct PalettePlugin;
+
+impl Plugin for PalettePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, update_palette_uniforms);
+
+        if app.get_sub_app(RenderApp).is_none() {
+            return;
+        }
+        app.add_plugins((
+            ExtractComponentPlugin::<PaletteUniform>::default(),
+            UniformComponentPlugin::<PaletteUniform>::default(),
+        ));
+        embedded_asset!(app, "palette.wgsl");
+
+        let render_app = app.sub_app_mut(RenderApp);
+        let after = frame_drawn_label(render_app);
+        render_app
+            .add_systems(RenderStartup, init_palette_pipeline)
+            .add_render_graph_node::<ViewNodeRunner<PaletteNode>>(Core2d, PaletteLabel)
+            .add_render_graph_edges(Core2d, (after, PaletteLabel.intern(), CrtLabel.intern()));
+    }
+}
+
+/// The palette the shader snaps to, kept on each 2D camera while it applies.
+/// `count` entries of `colors` are used when non-zero, otherwise `levels`.
+#[derive(Component, Debug, Clone, Copy, PartialEq, ExtractComponent, ShaderType)]
+pub struct PaletteUniform {
+    pub colors: [Vec4; MAX_PALETTE_COLORS],
+    pub levels: Vec3,
+    pub count: u32,
+}
+
+impl PaletteUniform {
+    /// The uniform for `palette`, or `None` when there is nothing to snap.
+    pub fn new(palette: EraPalette) -> Option<Self> {
+        let mut uniform = PaletteUniform {
+            colors: [Vec4::ZERO; MAX_PALETTE_COLORS],
+            levels: Vec3::ZERO,
+            count: 0,
+        };
+        match palette {
+            EraPalette::Indexed(entries) => {
+                for (slot, entry) in uniform.colors.iter_mut().zip(entries) {
+                    *slot = entry.to_srgba().to_vec4();
+                }
+                uniform.count = entries.len().min(MAX_PALETTE_COLORS) as u32;
+            }
+            EraPalette::Levels(levels) => uniform.levels = levels.as_vec3(),
+            EraPalette::TrueColor => return None,
+        }
+        Some(uniform)
+    }
+}
+
+// ─── Systems ───────────────────────────────────────────────────────
+
+fn update_palette_uniforms(
+    mut commands: Commands,
+    settings: Res<UserSettings>,
+    state: Res<State<GameState>>,
+    era: Res<CurrentEra>,
+    progress: Res<PlayerProgress>,
+    cameras: Query<(Entity, Option<&PaletteUniform>), With<Camera2d>>,
+) {
+    let wanted = if settings.palette_quantization && *state.get() == GameState::Playing {
+        let palette = progress
+            .equipped(CosmeticSlot::Palette)
+            .and_then(|cosmetic| EraPalette::cosmetic(cosmetic.id))
+            .unwrap_or(EraPresentation::for_era(era.era).palette);
+        PaletteUniform::new(palette)
+    } else {
+        None
+    };
+    for (entity, current) in &cameras {
+        match wanted {
+            Some(uniform) if current != Some(&uniform) => {
+                commands.entity(entity).insert(uniform);
+            }
+            None if current.is_some() => {
+                commands.entity(entity).remove::<PaletteUniform>();
+            }
+            _ => {}
+        }
+    }
+}
+
+// ─── Render node ───────────────────────────────────────────────────
+
+#[derive(Debug, Hash, PartialEq, Eq, Clone, RenderLabel)]
+struct PaletteLabel;
+
+#[derive(Default)]
+struct PaletteNode;
+
+impl ViewNode for PaletteNode {
+    type ViewQuery = (
+        &'static ViewTarget,
+        &'static PaletteUniform,
+        &'static DynamicUniformIndex<PaletteUniform>,
+    );
+
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        (view_target, _palette, uniform_index): QueryItem<Self::ViewQuery>,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let Some(palette_pipeline) = world.get_resource::<PalettePipeline>() else {
+            return Ok(());
+        };
+        if view_target.main_texture_format() != TextureFormat::bevy_default() {
+            return Ok(());
+        }
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let Some(pipeline) = pipeline_cache.get_render_pipeline(palette_pipeline.pipeline_id)
+        else {
+            return Ok(());
+        };
+        let uniforms = world.resource::<ComponentUniforms<PaletteUniform>>();
+        let Some(uniform_binding) = uniforms.uniforms().binding() else {
+            return Ok(());
+        };
+
+        let post_process = view_target.post_process_write();
+        let bind_group = render_context.render_device().create_bind_group(
+            "palette_bind_group",
+            &pipeline_cache.get_bind_group_layout(&palette_pipeline.layout),
+            &BindGroupEntries::sequential((post_process.source, uniform_binding.clone())),
+        );
+
+        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some("palette_pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: post_process.destination,
+                depth_slice: None,
+                resolve_target: None,
+                ops: Operations::default(),
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        render_pass.set_render_pipeline(pipeline);
+        render_pass.set_bind_group(0, &bind_group, &[uniform_index.index()]);
+        render_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
+
+#[derive(Resource)]
+struct PalettePipeline {
+    layout: BindGroupLayoutDescriptor,
+    pipeline_id: CachedRenderPipelineId,
+}
+
+fn init_palette_pipeline(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    fullscreen_shader: Res<FullscreenShader>,
+    pipeline_cache: Res<PipelineCache>,
+) {
+    // Pixels are read with `textureLoad`, so no sampler.
+    let layout = BindGroupLayoutDescriptor::new(
+        "palette_bind_group_layout",
+        &BindGroupLayoutEntries::sequential(
+            ShaderStages::FRAGMENT,
+            (
+                texture_2d(TextureSampleType::Float { filterable: true }),
+                uniform_buffer::<PaletteUniform>(true),
+            ),
+        ),
+    );
+    let pipeline_id = pipeline_cache.queue_render_pipeline(RenderPipelineDescriptor {
+        label: Some("palette_pipeline".into()),
+        layout: vec![layout.clone()],
+        vertex: fullscreen_shader.to_vertex_state(),
+        fragment: Some(FragmentState {
+            shader: load_embedded_asset!(&*asset_server, "palette.wgsl"),
+            targets: vec![Some(ColorTargetState {
+                format: TextureFormat::bevy_default(),
+                blend: None,
+                write_mask: ColorWrites::ALL,
+            })],
+            ..default()
+        }),
+        ..default()
+    });
+
+    commands.insert_resource(PalettePipeline {
+        layout,
+        pipeline_id,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::cosmetics::cosmetic;
+    use crate::core::states::Era;
+    use bevy::state::app::StatesPlugin;
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin, PalettePlugin))
+            .init_state::<GameState>()
+            .init_resource::<UserSettings>()
+            .init_resource::<CurrentEra>()
+            .init_resource::<PlayerProgress>();
+        app
+    }
+
+    #[test]
+    fn test_palette_applies_only_while_playing() {
+        let mut app = app();
+        let camera = app.world_mut().spawn(Camera2d).id();
+        app.update();
+        assert!(app.world().get::<PaletteUniform>(camera).is_none());
+
+        app.world_mut()
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::Playing);
+        app.update();
+        let uniform = app.world().get::<PaletteUniform>(camera).unwrap();
+        assert_eq!(uniform.count, 16);
+
+        app.world_mut()
+            .resource_mut::<UserSettings>()
+            .palette_quantization = false;
+        app.update();
+        assert!(app.world().get::<PaletteUniform>(camera).is_none());
+    }
+
+    #[test]
+    fn test_true_color_eras_skip_the_pass() {
+        let mut app = app();
+        app.world_mut().resource_mut::<CurrentEra>().era = Era::The2010s;
+        app.world_mut()
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::Playing);
+        let camera = app.world_mut().spawn(Camera2d).id();
+        app.update();
+        assert!(app.world().get::<PaletteUniform>(camera).is_none());
+    }
+
+    #[test]
+    fn test_equipped_palette_applies_in_true_color_eras() {
+        let mut app = app();
+        {
+            let mut progress = app.world_mut().resource_mut::<PlayerProgress>();
+            progress.tokens = 500;
+            progress.buy(cosmetic("palette_cga").unwrap()).unwrap();
+        }
+        app.world_mut().resource_mut::<CurrentEra>().era = Era::The2010s;
+        app.world_mut()
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::Playing);
+        let camera = app.world_mut().spawn(Camera2d).id();
+        app.update();
+        let uniform = app.world().get::<PaletteUniform>(camera).unwrap();
+        assert_eq!(uniform.count, 4);
+    }
+}
@@ -0,0 +1,52 @@
+// Palette quantization: snap every pixel of the finished frame to the
+// nearest color the era could show. Mirrors `EraPalette::quantize`.
+
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+
+struct PaletteUniform {
+    colors: array<vec4<f32>, 16>,
+    levels: vec3<f32>,
+    count: u32,
+}
+
+@group(0) @binding(0) var screen_texture: texture_2d<f32>;
+@group(0) @binding(1) var<uniform> palette: PaletteUniform;
+
+// Rec. 601 luma weights, so green mismatches count the most.
+const LUMA: vec3<f32> = vec3(0.299, 0.587, 0.114);
+
+fn to_srgb(linear: vec3<f32>) -> vec3<f32> {
+    let low = linear * 12.92;
+    let high = 1.055 * pow(linear, vec3(1.0 / 2.4)) - 0.055;
+    return select(high, low, linear <= vec3(0.0031308));
+}
+
+fn to_linear(srgb: vec3<f32>) -> vec3<f32> {
+    let low = srgb / 12.92;
+    let high = pow((srgb + 0.055) / 1.055, vec3(2.4));
+    return select(high, low, srgb <= vec3(0.04045));
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let texel = textureLoad(screen_texture, vec2<i32>(in.position.xy), 0);
+    let color = to_srgb(clamp(texel.rgb, vec3(0.0), vec3(1.0)));
+
+    var snapped = color;
+    if palette.count > 0u {
+        var best = 1e9;
+        for (var i = 0u; i < min(palette.count, 16u); i++) {
+            let entry = palette.colors[i].rgb;
+            let d = color - entry;
+            let dist = dot(d * d, LUMA);
+            if dist < best {
+                best = dist;
+                snapped = entry;
+            }
+        }
+    } else {
+        let steps = max(palette.levels, vec3(2.0)) - 1.0;
+        snapped = floor(color * steps + 0.5) / steps;
+    }
+    return vec4(to_linear(snapped), texel.a);
+}
@@ -0,0 +1,199 @@
+//! How each era looks on screen.
+//!
+//! An `EraPresentation` collects what DESIGN.md promises per era: the
+//! palette it draws with, the resolution its games are designed at, how
+//! that resolution is scaled to the window and the font it uses. The
+//! palette pass (see `palette`) snaps the picture to the palette while one
+//! of the era's games is playing, unless the player equipped one of the
+//! shop's palettes instead.
+
+use bevy::prelude::*;
+
+use crate::core::states::Era;
+use crate::ui::colors;
+
+/// The colors an era can show.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EraPalette {
+    /// A fixed set of colors, like the 16 of EGA. At most
+    /// `MAX_PALETTE_COLORS` entries.
+    Indexed(&'static [Color]),
+    /// Evenly spaced levels per red, green and blue channel, like 256-color
+    /// SVGA or 16-bit high color.
+    Levels(UVec3),
+    /// Anything goes.
+    TrueColor,
+}
+
+/// The most colors an `EraPalette::Indexed` palette may hold.
+pub const MAX_PALETTE_COLORS: usize = 16;
+
+/// Four greens of a pocket handheld, darkest first.
+const POCKET_PALETTE: [Color; 4] = [
+    Color::srgb(0.06, 0.22, 0.06),
+    Color::srgb(0.19, 0.38, 0.19),
+    Color::srgb(0.55, 0.67, 0.06),
+    Color::srgb(0.61, 0.74, 0.06),
+];
+
+/// CGA mode 4, palette 1 at high intensity.
+const CGA_PALETTE: [Color; 4] = [
+    colors::EGA_BLACK,
+    colors::EGA_BRIGHT_CYAN,
+    colors::EGA_BRIGHT_MAGENTA,
+    colors::EGA_BRIGHT_WHITE,
+];
+
+/// An amber monochrome monitor at six brightnesses.
+const AMBER_PALETTE: [Color; 6] = [
+    Color::srgb(0.0, 0.0, 0.0),
+    Color::srgb(0.2, 0.1, 0.0),
+    Color::srgb(0.4, 0.2, 0.0),
+    Color::srgb(0.6, 0.34, 0.0),
+    Color::srgb(0.8, 0.5, 0.0),
+    Color::srgb(1.0, 0.69, 0.0),
+];
+
+/// How a game's virtual resolution is fitted to the window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PixelScaling {
+    /// Whole multiples only, nearest-neighbour, letterboxed. Keeps chunky
+    /// pixels square.
+    Integer,
+    /// As large as fits while keeping the aspect ratio, filtered.
+    Fit,
+}
+
+/// The look of one era.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EraPresentation {
+    pub palette: EraPalette,
+    /// The resolution the era's games are designed at.
+    pub virtual_resolution: UVec2,
+    pub scaling: PixelScaling,
+    /// Asset path of the era's font. `None` uses Bevy's built-in font,
+    /// which is all there is until font files ship in `assets/fonts`.
+    pub font: Option<&'static str>,
+}
+
+impl EraPresentation {
+    /// The presentation for `era`.
+    pub const fn for_era(era: Era) -> EraPresentation {
+        match era {
+            // EGA on a 320×200 DOS screen.
+            Era::The80s => EraPresentation {
+                palette: EraPalette::Indexed(&colors::EGA_PALETTE),
+                virtual_resolution: UVec2::new(320, 200),
+                scaling: PixelScaling::Integer,
+                font: None,
+            },
+            // 256-color SVGA, approximated by the 6×6×6 color cube.
+            Era::The90s => EraPresentation {
+                palette: EraPalette::Levels(UVec3::splat(6)),
+                virtual_resolution: UVec2::new(640, 480),
+                scaling: PixelScaling::Integer,
+                font: None,
+            },
+            // 16-bit high color, 5-6-5 bits per channel.
+            Era::The2000s => EraPresentation {
+                palette: EraPalette::Levels(UVec3::new(32, 64, 32)),
+                virtual_resolution: UVec2::new(1024, 768),
+                scaling: PixelScaling::Fit,
+                font: None,
+            },
+            Era::The2010s | Era::Future => EraPresentation {
+                palette: EraPalette::TrueColor,
+                virtual_resolution: UVec2::new(1920, 1080),
+                scaling: PixelScaling::Fit,
+                font: None,
+            },
+        }
+    }
+
+    /// The era's font, or the default font handle when it has none.
+    pub fn font(&self, asset_server: &AssetServer) -> Handle<Font> {
+        self.font
+            .map(|path| asset_server.load(path))
+            .unwrap_or_default()
+    }
+}
+
+impl EraPalette {
+    /// The palette sold in the shop as cosmetic `id`.
+    pub fn cosmetic(id: &str) -> Option<EraPalette> {
+        match id {
+            "palette_pocket" => Some(EraPalette::Indexed(&POCKET_PALETTE)),
+            "palette_cga" => Some(EraPalette::Indexed(&CGA_PALETTE)),
+            "palette_amber" => Some(EraPalette::Indexed(&AMBER_PALETTE)),
+            _ => None,
+        }
+    }
+
+    /// The palette color closest to `color`, compared in sRGB with each
+    /// channel weighted by how bright it looks. Matches `palette.wgsl`.
+    pub fn quantize(&self, color: Color) -> Color {
+        let srgba = color.to_srgba();
+        let rgb = Vec3::new(srgba.red, srgba.green, srgba.blue);
+        let snapped = match *self {
+            EraPalette::Indexed(entries) => entries
+                .iter()
+                .map(|entry| {
+                    let entry = entry.to_srgba();
+                    Vec3::new(entry.red, entry.green, entry.blue)
+                })
+                .min_by(|a, b| distance(*a, rgb).total_cmp(&distance(*b, rgb)))
+                .unwrap_or(rgb),
+            EraPalette::Levels(levels) => {
+                let steps = (levels.max(UVec3::splat(2)) - UVec3::ONE).as_vec3();
+                (rgb * steps + 0.5).floor() / steps
+            }
+            EraPalette::TrueColor => rgb,
+        };
+        Color::srgba(snapped.x, snapped.y, snapped.z, srgba.alpha)
+    }
+}
+
+/// Squared distance with the Rec. 601 luma weights.
+fn distance(a: Vec3, b: Vec3) -> f32 {
+    let d = a - b;
+    (d * d).dot(Vec3::new(0.299, 0.587, 0.114))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::cosmetics::{CosmeticSlot, in_slot};
+
+    #[test]
+    fn test_ega_snaps_to_nearest_entry() {
+        let palette = EraPresentation::for_era(Era::The80s).palette;
+        assert_eq!(
+            palette.quantize(Color::srgb(0.95, 0.4, 0.35)),
+            colors::EGA_BRIGHT_RED
+        );
+        assert_eq!(
+            palette.quantize(Color::srgb(0.02, 0.03, 0.01)),
+            colors::EGA_BLACK
+        );
+    }
+
+    #[test]
+    fn test_levels_round_each_channel() {
+        let palette = EraPalette::Levels(UVec3::splat(6));
+        let snapped = palette.quantize(Color::srgb(0.33, 0.5, 0.95)).to_srgba();
+        assert_eq!((snapped.red, snapped.green, snapped.blue), (0.4, 0.6, 1.0));
+    }
+
+    #[test]
+    fn test_indexed_palettes_fit_the_shader() {
+        let shop = in_slot(CosmeticSlot::Palette).map(|cosmetic| EraPalette::cosmetic(cosmetic.id));
+        let eras = Era::ALL.map(|era| Some(EraPresentation::for_era(era).palette));
+        for palette in shop.chain(eras) {
+            match palette {
+                Some(EraPalette::Indexed(entries)) => assert!(entries.len() <= MAX_PALETTE_COLORS),
+                Some(_) => {}
+                None => panic!("a shop palette has no colors"),
+            }
+        }
+    }
+}
@@ -1,42 +1,606 @@
+//! Screen transitions between game states.
+//!
+//! Every `GameState` change request is held back while an outgoing effect
+//! covers the screen. At full cover the switch goes through and an incoming
+//! effect uncovers the new screen. Sub-state requests made alongside, such
+//! as which `PlayingState` to launch, are held with it and applied once the
+//! new screen exists. Which effect plays follows the screen
+//! being left or entered: hub screens power the CRT off and on, the 80s
+//! wipe like a DOS `cls`, the 90s burst into TV static and later eras fade.
+
+use bevy::asset::RenderAssetUsages;
+use bevy::image::ImageSampler;
 use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy::state::state::{FreelyMutableState, StateTransitionSystems};
+use bevy::ui::FocusPolicy;
+use rand::RngExt;
+
+use crate::core::rng::GameRng;
+use crate::core::states::{Era, GameState, MenuState, PlayingState, ResultsState, TimelineState};
+use crate::ui::era_select::CurrentEra;
+
+/// Seconds each half of a transition takes unless overridden.
+pub const DEFAULT_TRANSITION_SECS: f32 = 0.35;
+
+/// Rows the DOS wipe clears, like lines of an 80×25 text screen.
+const DOS_ROWS: f32 = 25.0;
+
+/// Size of the static noise texture; it is stretched over the screen.
+const NOISE_SIZE: UVec2 = UVec2::new(160, 120);
 
 /// Screen transition effects (fade, static, etc).
 pub struct TransitionsPlugin;
 
 impl Plugin for TransitionsPlugin {
     fn build(&self, app: &mut App) {
-        // app.add_event::<ScreenTransitionEvent>()
-        //     .add_systems(Update, (start_transition, update_transition));
+        app.init_resource::<ScreenTransition>()
+            .add_message::<ScreenTransitionEvent>()
+            .add_systems(
+                StateTransition,
+                start_transition.before(StateTransitionSystems::DependentTransitions),
+            )
+            .add_systems(PostUpdate, update_transition);
+        hold_sub_state::<MenuState>(app);
+        hold_sub_state::<TimelineState>(app);
+        hold_sub_state::<ResultsState>(app);
+        hold_sub_state::<PlayingState>(app);
     }
 }
 
-/// Event to trigger a screen transition.
-#[derive(Event)]
+/// Hold requests for `S`, a sub-state of `GameState`, while a `GameState`
+/// change is held back. Otherwise they'd be dropped, since `S` doesn't
+/// exist until the new screen does.
+fn hold_sub_state<S: FreelyMutableState>(app: &mut App) {
+    app.init_resource::<HeldSubState<S>>()
+        .add_systems(
+            StateTransition,
+            stash_sub_state::<S>
+                .after(start_transition)
+                .before(StateTransitionSystems::DependentTransitions),
+        )
+        .add_systems(PostUpdate, release_sub_state::<S>.after(update_transition));
+}
+
+/// Play `kind` over `duration` seconds per half for the next state change,
+/// instead of the effect chosen for the screens involved.
+#[derive(Message, Debug, Clone, Copy)]
 pub struct ScreenTransitionEvent {
     pub kind: TransitionKind,
     pub duration: f32,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum TransitionKind {
-    FadeToBlack,
-    FadeFromBlack,
+    /// Fade through black.
+    #[default]
+    Fade,
+    /// A burst of TV static.
     Static,
+    /// Black rows wipe down the screen, a text line at a time.
+    DosWipe,
+    /// The picture collapses to a line, then a dot, like a CRT switching
+    /// off; it powers back on in reverse.
+    CrtPowerOff,
+}
+
+impl TransitionKind {
+    /// The effect used when leaving or entering `state`.
+    pub fn for_screen(state: GameState, era: Era) -> TransitionKind {
+        match state {
+            GameState::EraSelect | GameState::Playing | GameState::Results => match era {
+                Era::The80s => TransitionKind::DosWipe,
+                Era::The90s => TransitionKind::Static,
+                Era::The2000s | Era::The2010s | Era::Future => TransitionKind::Fade,
+            },
+            _ => TransitionKind::CrtPowerOff,
+        }
+    }
+}
+
+// ─── Resources ─────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Phase {
+    /// Covering the old screen.
+    Out,
+    /// Uncovering the new one.
+    In,
+}
+
+/// The transition in progress, if any.
+#[derive(Resource, Debug, Default)]
+pub struct ScreenTransition {
+    phase: Option<Phase>,
+    kind: TransitionKind,
+    target: Option<GameState>,
+    elapsed: f32,
+    duration: f32,
+    /// The held-back request was re-issued and should go through.
+    released: bool,
+    /// Virtual time was paused by us for the outgoing half.
+    froze_time: bool,
+    next: Option<ScreenTransitionEvent>,
+}
+
+impl ScreenTransition {
+    /// Whether a transition is on screen.
+    pub fn is_running(&self) -> bool {
+        self.phase.is_some()
+    }
+
+    /// How much of the screen is covered, from 0 to 1.
+    fn coverage(&self) -> f32 {
+        let t = (self.elapsed / self.duration.max(f32::EPSILON)).clamp(0.0, 1.0);
+        match self.phase {
+            Some(Phase::Out) => t,
+            Some(Phase::In) => 1.0 - t,
+            None => 0.0,
+        }
+    }
+
+    /// Settle on the effect for the half starting now.
+    fn choose(&mut self, screen: GameState, era: Era) {
+        (self.kind, self.duration) = match self.next.take() {
+            Some(event) => (event.kind, event.duration),
+            None => (
+                TransitionKind::for_screen(screen, era),
+                DEFAULT_TRANSITION_SECS,
+            ),
+        };
+    }
+}
+
+/// A sub-state request waiting for the held `GameState` change.
+#[derive(Resource)]
+struct HeldSubState<S: FreelyMutableState>(Option<S>);
+
+impl<S: FreelyMutableState> Default for HeldSubState<S> {
+    fn default() -> Self {
+        Self(None)
+    }
 }
 
-/// Component on the transition overlay entity.
+// ─── Components ────────────────────────────────────────────────────
+
+/// Root of the overlay drawing the current effect.
 #[derive(Component)]
 struct TransitionOverlay {
     kind: TransitionKind,
-    timer: Timer,
 }
 
-fn start_transition(/* mut events: EventReader<ScreenTransitionEvent> */) {
-    // Placeholder
+#[derive(Component, Clone, Copy)]
+enum OverlayPart {
+    Fill,
+    Noise,
+    Top,
+    Bottom,
+    Left,
+    Right,
+    Beam,
+}
+
+// ─── Systems ───────────────────────────────────────────────────────
+
+/// Hold back a `GameState` change and start covering the screen instead.
+/// A request during the incoming half turns it around from where it is.
+fn start_transition(
+    state: Res<State<GameState>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut transition: ResMut<ScreenTransition>,
+    mut overrides: MessageReader<ScreenTransitionEvent>,
+    era: Res<CurrentEra>,
+    mut time: ResMut<Time<Virtual>>,
+) {
+    if let Some(event) = overrides.read().last() {
+        transition.next = Some(*event);
+    }
+    let target = match *next_state {
+        NextState::Pending(target) => target,
+        NextState::PendingIfNeq(target) if target != *state.get() => target,
+        _ => return,
+    };
+    if std::mem::take(&mut transition.released) {
+        return;
+    }
+    next_state.reset();
+
+    transition.target = Some(target);
+    match transition.phase {
+        Some(Phase::Out) => {}
+        Some(Phase::In) => {
+            transition.phase = Some(Phase::Out);
+            transition.elapsed = transition.duration - transition.elapsed;
+        }
+        None => {
+            transition.phase = Some(Phase::Out);
+            transition.elapsed = 0.0;
+            transition.choose(*state.get(), era.era);
+        }
+    }
+    // Freeze the old screen so a finished game can't report twice.
+    if !time.is_paused() {
+        time.pause();
+        transition.froze_time = true;
+    }
+}
+
+/// Take a sub-state request made while a `GameState` change is held.
+fn stash_sub_state<S: FreelyMutableState>(
+    transition: Res<ScreenTransition>,
+    next_state: Option<ResMut<NextState<S>>>,
+    mut held: ResMut<HeldSubState<S>>,
+) {
+    let Some(mut next_state) = next_state else {
+        return;
+    };
+    if transition.target.is_none() {
+        return;
+    }
+    if let NextState::Pending(target) | NextState::PendingIfNeq(target) = &*next_state {
+        held.0 = Some(target.clone());
+        next_state.reset();
+    }
+}
+
+/// Re-issue a held sub-state request along with the `GameState` change.
+fn release_sub_state<S: FreelyMutableState>(
+    transition: Res<ScreenTransition>,
+    next_state: Option<ResMut<NextState<S>>>,
+    mut held: ResMut<HeldSubState<S>>,
+) {
+    if transition.target.is_some() {
+        return;
+    }
+    if let (Some(target), Some(mut next_state)) = (held.0.take(), next_state) {
+        next_state.set(target);
+    }
+}
+
+/// Advance the transition, switch state at full cover and draw the overlay.
+#[allow(clippy::too_many_arguments)]
+fn update_transition(
+    mut commands: Commands,
+    real_time: Res<Time<Real>>,
+    mut time: ResMut<Time<Virtual>>,
+    mut transition: ResMut<ScreenTransition>,
+    mut next_state: ResMut<NextState<GameState>>,
+    era: Res<CurrentEra>,
+    mut rng: ResMut<GameRng>,
+    mut images: ResMut<Assets<Image>>,
+    overlays: Query<(Entity, &TransitionOverlay)>,
+    mut parts: Query<(
+        &OverlayPart,
+        &mut Node,
+        Option<&mut BackgroundColor>,
+        Option<&mut ImageNode>,
+    )>,
+) {
+    let Some(phase) = transition.phase else {
+        for (entity, _) in &overlays {
+            commands.entity(entity).despawn();
+        }
+        return;
+    };
+
+    transition.elapsed += real_time.delta_secs();
+    if transition.elapsed >= transition.duration {
+        match phase {
+            Phase::Out => {
+                if let Some(target) = transition.target.take() {
+                    next_state.set(target);
+                    transition.released = true;
+                    transition.phase = Some(Phase::In);
+                    transition.elapsed = 0.0;
+                    transition.choose(target, era.era);
+                }
+                if std::mem::take(&mut transition.froze_time) {
+                    time.unpause();
+                }
+            }
+            Phase::In => {
+                transition.phase = None;
+                for (entity, _) in &overlays {
+                    commands.entity(entity).despawn();
+                }
+                return;
+            }
+        }
+    }
+
+    let kind = transition.kind;
+    let mut current = false;
+    for (entity, overlay) in &overlays {
+        if overlay.kind == kind {
+            current = true;
+        } else {
+            commands.entity(entity).despawn();
+        }
+    }
+    if !current {
+        spawn_overlay(&mut commands, kind, &mut images);
+        return;
+    }
+
+    let cover = transition.coverage();
+    let incoming = transition.phase == Some(Phase::In);
+    for (part, mut node, background, image) in &mut parts {
+        match (kind, part) {
+            (TransitionKind::Fade, _) => {
+                if let Some(mut background) = background {
+                    background.0 = Color::BLACK.with_alpha(cover);
+                }
+            }
+            (TransitionKind::DosWipe, _) => {
+                // Covers from the top going out and uncovers from the top
+                // coming in, so the new screen appears in reading order.
+                node.height = Val::Percent((cover * DOS_ROWS).ceil() / DOS_ROWS * 100.0);
+                (node.top, node.bottom) = if incoming {
+                    (Val::Auto, Val::Px(0.0))
+                } else {
+                    (Val::Px(0.0), Val::Auto)
+                };
+            }
+            (TransitionKind::Static, OverlayPart::Noise) => {
+                if let Some(mut image) = image {
+                    image.color = Color::WHITE.with_alpha(cover);
+                    if let Some(data) = images
+                        .get_mut(&image.image)
+                        .and_then(|noise| noise.data.as_mut())
+                    {
+                        for pixel in data.chunks_exact_mut(4) {
+                            let level = rng.cosmetic().random::<u8>();
+                            pixel[..3].fill(level);
+                        }
+                    }
+                }
+            }
+            (TransitionKind::CrtPowerOff, part) => {
+                // First the picture squashes to a line, then the line
+                // shrinks to a dot that fades out.
+                let squash = (cover / 0.7).min(1.0);
+                let shrink = ((cover - 0.7) / 0.3).clamp(0.0, 1.0);
+                match part {
+                    OverlayPart::Top | OverlayPart::Bottom => {
+                        node.height = Val::Percent(squash * 50.0);
+                    }
+                    OverlayPart::Left | OverlayPart::Right => {
+                        node.width = Val::Percent(shrink * 50.0);
+                    }
+                    OverlayPart::Beam => {
+                        node.left = Val::Percent(shrink * 50.0);
+                        node.width = Val::Percent((1.0 - shrink) * 100.0);
+                        let glow = ((squash - 0.8) / 0.2).clamp(0.0, 1.0) * (1.0 - shrink);
+                        if let Some(mut background) = background {
+                            background.0 = Color::WHITE.with_alpha(glow);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn spawn_overlay(commands: &mut Commands, kind: TransitionKind, images: &mut Assets<Image>) {
+    let full = Node {
+        position_type: PositionType::Absolute,
+        width: Val::Percent(100.0),
+        height: Val::Percent(100.0),
+        ..default()
+    };
+    let bar = |left, top, right, bottom| Node {
+        position_type: PositionType::Absolute,
+        left,
+        top,
+        right,
+        bottom,
+        width: Val::Percent(100.0),
+        height: Val::Percent(100.0),
+        ..default()
+    };
+
+    commands
+        .spawn((
+            TransitionOverlay { kind },
+            // Above every menu and overlay, and swallowing clicks.
+            GlobalZIndex(100),
+            FocusPolicy::Block,
+            full.clone(),
+        ))
+        .with_children(|root| match kind {
+            TransitionKind::Fade => {
+                root.spawn((OverlayPart::Fill, full, BackgroundColor(Color::NONE)));
+            }
+            TransitionKind::DosWipe => {
+                root.spawn((
+                    OverlayPart::Fill,
+                    Node {
+                        height: Val::Percent(0.0),
+                        ..full
+                    },
+                    BackgroundColor(Color::BLACK),
+                ));
+            }
+            TransitionKind::Static => {
+                let mut noise = Image::new_fill(
+                    Extent3d {
+                        width: NOISE_SIZE.x,
+                        height: NOISE_SIZE.y,
+                        depth_or_array_layers: 1,
+                    },
+                    TextureDimension::D2,
+                    &[0, 0, 0, 255],
+                    TextureFormat::Rgba8UnormSrgb,
+                    RenderAssetUsages::default(),
+                );
+                noise.sampler = ImageSampler::nearest();
+                root.spawn((
+                    OverlayPart::Noise,
+                    full,
+                    ImageNode::new(images.add(noise)).with_color(Color::NONE),
+                ));
+            }
+            TransitionKind::CrtPowerOff => {
+                let zero = Val::Px(0.0);
+                let auto = Val::Auto;
+                let black = BackgroundColor(Color::BLACK);
+                root.spawn((
+                    OverlayPart::Top,
+                    Node {
+                        height: Val::Percent(0.0),
+                        ..bar(zero, zero, auto, auto)
+                    },
+                    black,
+                ));
+                root.spawn((
+                    OverlayPart::Bottom,
+                    Node {
+                        height: Val::Percent(0.0),
+                        ..bar(zero, auto, auto, zero)
+                    },
+                    black,
+                ));
+                root.spawn((
+                    OverlayPart::Left,
+                    Node {
+                        width: Val::Percent(0.0),
+                        ..bar(zero, zero, auto, auto)
+                    },
+                    black,
+                ));
+                root.spawn((
+                    OverlayPart::Right,
+                    Node {
+                        width: Val::Percent(0.0),
+                        ..bar(auto, zero, zero, auto)
+                    },
+                    black,
+                ));
+                root.spawn((
+                    OverlayPart::Beam,
+                    Node {
+                        position_type: PositionType::Absolute,
+                        top: Val::Percent(50.0),
+                        height: Val::Px(3.0),
+                        margin: UiRect::top(Val::Px(-1.5)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::NONE),
+                ));
+            }
+        });
 }
 
-fn update_transition(/* time: Res<Time>,
-    mut query: Query<(&mut TransitionOverlay, &mut BackgroundColor, Entity)>,
-    mut commands: Commands, */) {
-    // Placeholder
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::state::app::StatesPlugin;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            AssetPlugin::default(),
+            TransitionsPlugin,
+        ))
+        .init_asset::<Image>()
+        .init_state::<GameState>()
+        .add_sub_state::<PlayingState>()
+        .init_resource::<CurrentEra>()
+        .insert_resource(GameRng::new(1))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            50,
+        )));
+        app.update();
+        app
+    }
+
+    fn state(app: &App) -> GameState {
+        *app.world().resource::<State<GameState>>().get()
+    }
+
+    #[test]
+    fn test_state_switches_at_full_cover() {
+        let mut app = app();
+        app.world_mut()
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::Menu);
+        app.update();
+
+        // Held back while the timeline powers off.
+        assert_eq!(state(&app), GameState::Timeline);
+        assert!(app.world().resource::<ScreenTransition>().is_running());
+        assert!(app.world().resource::<Time<Virtual>>().is_paused());
+
+        for _ in 0..10 {
+            app.update();
+        }
+        assert_eq!(state(&app), GameState::Menu);
+        assert!(!app.world().resource::<Time<Virtual>>().is_paused());
+
+        for _ in 0..10 {
+            app.update();
+        }
+        assert!(!app.world().resource::<ScreenTransition>().is_running());
+        let overlays = app
+            .world_mut()
+            .query::<&TransitionOverlay>()
+            .iter(app.world())
+            .count();
+        assert_eq!(overlays, 0);
+    }
+
+    #[test]
+    fn test_latest_request_wins() {
+        let mut app = app();
+        for target in [GameState::Menu, GameState::EraSelect] {
+            app.world_mut()
+                .resource_mut::<NextState<GameState>>()
+                .set(target);
+            app.update();
+        }
+        for _ in 0..10 {
+            app.update();
+        }
+        assert_eq!(state(&app), GameState::EraSelect);
+    }
+
+    #[test]
+    fn test_sub_state_request_survives_the_transition() {
+        let mut app = app();
+        app.world_mut()
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::Playing);
+        app.world_mut()
+            .resource_mut::<NextState<PlayingState>>()
+            .set(PlayingState::StarGoose);
+        for _ in 0..10 {
+            app.update();
+        }
+
+        assert_eq!(state(&app), GameState::Playing);
+        let playing = app.world().resource::<State<PlayingState>>();
+        assert_eq!(*playing.get(), PlayingState::StarGoose);
+    }
+
+    #[test]
+    fn test_effect_follows_era() {
+        assert_eq!(
+            TransitionKind::for_screen(GameState::Playing, Era::The80s),
+            TransitionKind::DosWipe
+        );
+        assert_eq!(
+            TransitionKind::for_screen(GameState::Results, Era::The90s),
+            TransitionKind::Static
+        );
+        assert_eq!(
+            TransitionKind::for_screen(GameState::Timeline, Era::The90s),
+            TransitionKind::CrtPowerOff
+        );
+    }
 }
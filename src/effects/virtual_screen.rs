@@ -0,0 +1,370 @@
+//! Pixel-perfect virtual resolution for mini-games.
+//!
+//! While a mini-game plays, the main camera draws the game's `view` of the
+//! world into an offscreen image of the game's `virtual_resolution`. A
+//! second camera shows that image in the window, scaled by a whole number
+//! for eras with `PixelScaling::Integer` (as far as fits otherwise) and
+//! letterboxed in black. UI draws on that window camera at full
+//! resolution, so HUDs and overlays stay sharp.
+//!
+//! Window cursor positions mean nothing to the game camera until they go
+//! through `VirtualScreen::to_target`; `cursor_to_world` does both steps.
+
+use bevy::camera::visibility::RenderLayers;
+use bevy::camera::{RenderTarget, ScalingMode};
+use bevy::image::ImageSampler;
+use bevy::prelude::*;
+use bevy::render::render_resource::TextureFormat;
+use bevy::window::{PrimaryWindow, WindowRef};
+
+use super::crt::{CrtCamera, CrtUniform};
+use super::presentation::{EraPresentation, PixelScaling};
+use crate::core::registry::GameRegistry;
+use crate::core::states::{GameState, PlayingState};
+use crate::ui::StateCleanup;
+
+/// World area every mini-game is laid out for: the original 1024×768
+/// window. Unless a game picks its own view, its camera shows at least this
+/// much of it.
+pub const GAME_VIEW: Vec2 = Vec2::new(1024.0, 768.0);
+
+/// The world area a game drawn at `resolution` shows by default:
+/// `GAME_VIEW`, widened or heightened to the resolution's aspect ratio.
+pub const fn default_view(resolution: UVec2) -> Vec2 {
+    let width = (resolution.x as f32).max(1.0);
+    let height = (resolution.y as f32).max(1.0);
+    if width * GAME_VIEW.y >= height * GAME_VIEW.x {
+        Vec2::new(GAME_VIEW.y * width / height, GAME_VIEW.y)
+    } else {
+        Vec2::new(GAME_VIEW.x, GAME_VIEW.x * height / width)
+    }
+}
+
+/// Render layer of the scaled image, so only the window camera sees it.
+const SCREEN_LAYER: usize = 1;
+
+/// Renders mini-games at their virtual resolution.
+pub struct VirtualScreenPlugin;
+
+impl Plugin for VirtualScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Playing), enter_virtual_screen)
+            .add_systems(OnExit(GameState::Playing), exit_virtual_screen)
+            .add_systems(
+                Update,
+                layout_virtual_screen.run_if(resource_exists::<VirtualScreen>),
+            );
+    }
+}
+
+// ─── Resources ─────────────────────────────────────────────────────
+
+/// The offscreen image the running mini-game is drawn into.
+#[derive(Resource, Debug, Clone)]
+pub struct VirtualScreen {
+    pub resolution: UVec2,
+    pub scaling: PixelScaling,
+    pub image: Handle<Image>,
+    /// Where the image is shown, in logical window pixels from the top left
+    /// like `Window::cursor_position`. Empty until there is a window.
+    pub rect: Rect,
+}
+
+impl VirtualScreen {
+    /// Convert a window position to the matching pixel of the image. Points
+    /// in the letterbox land outside `0..resolution`.
+    pub fn to_target(&self, window_position: Vec2) -> Vec2 {
+        if self.rect.is_empty() {
+            return window_position;
+        }
+        (window_position - self.rect.min) / self.rect.size() * self.resolution.as_vec2()
+    }
+}
+
+/// Size in physical pixels to show a `resolution` image at in a window of
+/// `window` physical pixels. Integer scaling falls back to shrinking only
+/// when the window is smaller than the image.
+pub fn scaled_size(resolution: UVec2, window: UVec2, scaling: PixelScaling) -> Vec2 {
+    let ratio = window.as_vec2() / resolution.max(UVec2::ONE).as_vec2();
+    let fit = ratio.x.min(ratio.y);
+    let scale = match scaling {
+        PixelScaling::Integer if fit >= 1.0 => fit.floor(),
+        _ => fit,
+    };
+    resolution.as_vec2() * scale
+}
+
+/// World position under `cursor`, given in logical window pixels, as seen by
+/// a mini-game camera. Goes through the virtual screen when one is up.
+pub fn cursor_to_world(
+    camera: &Camera,
+    transform: &GlobalTransform,
+    screen: Option<&VirtualScreen>,
+    cursor: Vec2,
+) -> Option<Vec2> {
+    let viewport = screen.map_or(cursor, |screen| screen.to_target(cursor));
+    camera.viewport_to_world_2d(transform, viewport).ok()
+}
+
+// ─── Components ────────────────────────────────────────────────────
+
+/// The camera that shows the virtual screen in the window. Game systems
+/// that move or query "the" camera should skip it.
+#[derive(Component)]
+pub struct ScreenCamera;
+
+/// The sprite showing the virtual screen.
+#[derive(Component)]
+struct ScreenQuad;
+
+/// A camera redirected into the virtual screen, with the projection to
+/// restore afterwards.
+#[derive(Component)]
+struct OffscreenCamera {
+    projection: Projection,
+}
+
+// ─── Systems ───────────────────────────────────────────────────────
+
+#[allow(clippy::type_complexity)]
+fn enter_virtual_screen(
+    mut commands: Commands,
+    playing: Option<Res<State<PlayingState>>>,
+    registry: Res<GameRegistry>,
+    mut images: ResMut<Assets<Image>>,
+    cameras: Query<(Entity, &Projection), (With<Camera2d>, Without<ScreenCamera>)>,
+) {
+    let Some(info) = playing.and_then(|playing| registry.by_state(*playing.get())) else {
+        return;
+    };
+    let resolution = info.virtual_resolution.max(UVec2::ONE);
+    let scaling = EraPresentation::for_era(info.id.era).scaling;
+
+    let mut image = Image::new_target_texture(
+        resolution.x,
+        resolution.y,
+        TextureFormat::bevy_default(),
+        None,
+    );
+    image.sampler = match scaling {
+        PixelScaling::Integer => ImageSampler::nearest(),
+        PixelScaling::Fit => ImageSampler::linear(),
+    };
+    let image = images.add(image);
+
+    for (camera, projection) in &cameras {
+        // The glass goes on the window camera, at full resolution.
+        commands
+            .entity(camera)
+            .remove::<(CrtCamera, CrtUniform)>()
+            .insert((
+                OffscreenCamera {
+                    projection: projection.clone(),
+                },
+                RenderTarget::Image(image.clone().into()),
+                Projection::Orthographic(OrthographicProjection {
+                    scaling_mode: ScalingMode::Fixed {
+                        width: info.view.x,
+                        height: info.view.y,
+                    },
+                    ..OrthographicProjection::default_2d()
+                }),
+            ));
+    }
+
+    commands.spawn((
+        ScreenCamera,
+        StateCleanup(GameState::Playing),
+        Camera2d,
+        Camera {
+            order: 1,
+            clear_color: ClearColorConfig::Custom(Color::BLACK),
+            ..default()
+        },
+        RenderLayers::layer(SCREEN_LAYER),
+    ));
+    commands.spawn((
+        ScreenQuad,
+        StateCleanup(GameState::Playing),
+        Sprite::from_image(image.clone()),
+        RenderLayers::layer(SCREEN_LAYER),
+    ));
+    commands.insert_resource(VirtualScreen {
+        resolution,
+        scaling,
+        image,
+        rect: Rect::default(),
+    });
+}
+
+fn exit_virtual_screen(mut commands: Commands, cameras: Query<(Entity, &OffscreenCamera)>) {
+    for (camera, offscreen) in &cameras {
+        commands.entity(camera).remove::<OffscreenCamera>().insert((
+            RenderTarget::Window(WindowRef::Primary),
+            offscreen.projection.clone(),
+            CrtCamera::default(),
+        ));
+    }
+    commands.remove_resource::<VirtualScreen>();
+}
+
+/// Size and place the image for the current window. The top-left corner
+/// lands on a whole physical pixel so scaled pixels stay crisp.
+fn layout_virtual_screen(
+    mut screen: ResMut<VirtualScreen>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut quads: Query<(&mut Sprite, &mut Transform), With<ScreenQuad>>,
+) {
+    let Ok(window) = windows.single() else {
+        return;
+    };
+    let window_px = window.physical_size().as_vec2();
+    let scale_factor = window.scale_factor();
+    let shown = scaled_size(screen.resolution, window.physical_size(), screen.scaling).floor();
+    let margin = ((window_px - shown) / 2.0).floor();
+
+    let rect = Rect::from_corners(margin / scale_factor, (margin + shown) / scale_factor);
+    if screen.rect != rect {
+        screen.rect = rect;
+    }
+
+    // Sprites are placed from the window centre with y up.
+    let offset = (margin + shown / 2.0 - window_px / 2.0) / scale_factor;
+    let size = shown / scale_factor;
+    for (mut sprite, mut transform) in &mut quads {
+        if sprite.custom_size != Some(size) {
+            sprite.custom_size = Some(size);
+        }
+        let translation = Vec3::new(offset.x, -offset.y, 0.0);
+        if transform.translation != translation {
+            transform.translation = translation;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::registry::GameInfo;
+    use crate::core::states::{Era, MiniGameId};
+    use crate::ui::StateCleanupPlugin;
+    use bevy::state::app::StatesPlugin;
+
+    #[test]
+    fn test_integer_scaling_uses_whole_multiples() {
+        let size = scaled_size(
+            UVec2::new(320, 200),
+            UVec2::new(1024, 768),
+            PixelScaling::Integer,
+        );
+        assert_eq!(size, Vec2::new(960.0, 600.0));
+
+        let fit = scaled_size(
+            UVec2::new(320, 200),
+            UVec2::new(1024, 768),
+            PixelScaling::Fit,
+        );
+        assert_eq!(fit, Vec2::new(1024.0, 640.0));
+    }
+
+    #[test]
+    fn test_default_view_keeps_game_view_at_the_screens_aspect() {
+        assert_eq!(default_view(UVec2::new(640, 480)), GAME_VIEW);
+        assert_eq!(default_view(UVec2::new(320, 200)), Vec2::new(1228.8, 768.0));
+        assert_eq!(
+            default_view(UVec2::new(768, 1024)),
+            Vec2::new(1024.0, 1365.3334)
+        );
+    }
+
+    #[test]
+    fn test_cursor_maps_through_letterbox() {
+        let screen = VirtualScreen {
+            resolution: UVec2::new(320, 200),
+            scaling: PixelScaling::Integer,
+            image: Handle::default(),
+            rect: Rect::new(32.0, 84.0, 992.0, 684.0),
+        };
+        assert_eq!(screen.to_target(Vec2::new(32.0, 84.0)), Vec2::ZERO);
+        assert_eq!(
+            screen.to_target(Vec2::new(512.0, 384.0)),
+            Vec2::new(160.0, 100.0)
+        );
+    }
+
+    #[test]
+    fn test_game_camera_renders_offscreen_while_playing() {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            AssetPlugin::default(),
+            VirtualScreenPlugin,
+        ))
+        .init_asset::<Image>()
+        .init_state::<GameState>()
+        .add_sub_state::<PlayingState>()
+        .add_plugins(StateCleanupPlugin::<GameState>::default())
+        .init_resource::<GameRegistry>();
+        app.world_mut()
+            .resource_mut::<GameRegistry>()
+            .register(GameInfo {
+                id: MiniGameId {
+                    era: Era::The80s,
+                    index: 0,
+                },
+                playing_state: PlayingState::TunnelMiner,
+                name: "Test",
+                description: "",
+                thumbnail: "",
+                completion_threshold: 0,
+                starting_lives: 3,
+                virtual_resolution: UVec2::new(320, 200),
+                view: Vec2::new(640.0, 400.0),
+                score: |_| 0,
+            });
+        let camera = app.world_mut().spawn(Camera2d).id();
+        app.update();
+
+        app.world_mut()
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::Playing);
+        app.update();
+        let screen = app.world().resource::<VirtualScreen>().clone();
+        assert_eq!(screen.resolution, UVec2::new(320, 200));
+        let target = app.world().get::<RenderTarget>(camera).unwrap();
+        assert_eq!(target.as_image(), Some(&screen.image));
+        let Some(Projection::Orthographic(projection)) = app.world().get::<Projection>(camera)
+        else {
+            panic!("game camera lost its orthographic projection");
+        };
+        assert!(matches!(
+            projection.scaling_mode,
+            ScalingMode::Fixed {
+                width: 640.0,
+                height: 400.0
+            }
+        ));
+        let screen_cameras = app
+            .world_mut()
+            .query_filtered::<(), With<ScreenCamera>>()
+            .iter(app.world())
+            .count();
+        assert_eq!(screen_cameras, 1);
+
+        app.world_mut()
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::Results);
+        app.update();
+        assert!(!app.world().contains_resource::<VirtualScreen>());
+        let target = app.world().get::<RenderTarget>(camera).unwrap();
+        assert!(matches!(target, RenderTarget::Window(WindowRef::Primary)));
+        assert!(app.world().get::<CrtCamera>(camera).is_some());
+        let screen_cameras = app
+            .world_mut()
+            .query_filtered::<(), With<ScreenCamera>>()
+            .iter(app.world())
+            .count();
+        assert_eq!(screen_cameras, 0);
+    }
+}
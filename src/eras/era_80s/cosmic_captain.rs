@@ -1,8 +1,14 @@
-use crate::core::states::{GameState, PlayingState};
-use crate::shared::components::{Health, Player, Score, Velocity};
-use crate::ui::colors;
-use crate::ui::results::GameResults;
+use crate::core::input::{GameAction, GameInput, GraceWindow, InputBuffer};
+use crate::core::progression::{MiniGameFinished, MiniGameOutcome};
+use crate::core::states::{Era, MiniGameId, PlayingState};
+use crate::core::stats::StatEvent;
+use crate::eras::shared::{MiniGame, MiniGamePlugin};
+use crate::shared::components::{Health, Lives, Player, Score, Velocity};
+use crate::ui::{StateCleanup, StateScopedResourceExt, colors};
+use bevy::ecs::schedule::ScheduleConfigs;
+use bevy::ecs::system::ScheduleSystem;
 use bevy::prelude::*;
+use std::time::Duration;
 
 /// Cosmic Captain — inspired by Captain Comic (1988).
 /// Side-scrolling platformer with gravity and collectible abilities.
@@ -10,23 +16,46 @@ pub struct CosmicCaptainPlugin;
 
 impl Plugin for CosmicCaptainPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            OnEnter(GameState::Playing),
-            setup_captain.run_if(in_state(PlayingState::CosmicCaptain)),
-        )
-        .add_systems(
-            Update,
-            (
-                captain_movement,
-                platform_collision,
-                collect_items,
-                enemy_ai,
-                update_hud,
-                handle_pause,
-            )
-                .run_if(in_state(PlayingState::CosmicCaptain)),
+        app.add_plugins(MiniGamePlugin::<CosmicCaptain>::default())
+            .add_state_scoped_resource::<Score>(CosmicCaptain::PLAYING_STATE)
+            .add_state_scoped_resource::<Lives>(CosmicCaptain::PLAYING_STATE);
+    }
+}
+
+/// Registry entry for Cosmic Captain.
+pub struct CosmicCaptain;
+
+impl MiniGame for CosmicCaptain {
+    const ID: MiniGameId = GAME_ID;
+    const PLAYING_STATE: PlayingState = PlayingState::CosmicCaptain;
+    const NAME: &'static str = "Cosmic Captain";
+    const DESCRIPTION: &'static str = "Side-scrolling exploration across alien worlds";
+    const THUMBNAIL: &'static str = "ui/thumbnails/cosmic_captain.png";
+    const COMPLETION_THRESHOLD: u64 = 500;
+
+    fn setup() -> ScheduleConfigs<ScheduleSystem> {
+        setup_captain.into_configs()
+    }
+
+    fn gameplay() -> ScheduleConfigs<ScheduleSystem> {
+        (
+            captain_movement,
+            platform_collision,
+            collect_items,
+            check_fall,
+            check_exit,
+            enemy_ai,
         )
-        .add_systems(OnExit(GameState::Playing), cleanup_captain);
+            .chain()
+            .into_configs()
+    }
+
+    fn frame() -> ScheduleConfigs<ScheduleSystem> {
+        update_hud.into_configs()
+    }
+
+    fn score(world: &World) -> u64 {
+        world.get_resource::<Score>().map_or(0, |score| score.value)
     }
 }
 
@@ -35,17 +64,40 @@ impl Plugin for CosmicCaptainPlugin {
 const GRAVITY: f32 = -600.0;
 const JUMP_FORCE: f32 = 300.0;
 const MOVE_SPEED: f32 = 180.0;
+/// How long after walking off a ledge a jump is still allowed.
+const COYOTE_TIME: Duration = Duration::from_millis(100);
+/// How early a jump press before landing still counts.
+const JUMP_BUFFER: Duration = Duration::from_millis(120);
 const TILE_SIZE: f32 = 32.0;
+/// Where the Captain starts, and comes back to after a fall.
+const START: Vec3 = Vec3::new(-200.0, 0.0, 1.0);
+/// Falling below this costs a life.
+const FALL_LIMIT: f32 = -400.0;
+/// Ground tiles left out to make a pit before the exit.
+const PIT: std::ops::Range<i32> = 6..8;
+const EXIT_X: f32 = 560.0;
+const ITEM_POINTS: u64 = 100;
+const EXIT_POINTS: u64 = 300;
+
+const GAME_ID: MiniGameId = MiniGameId {
+    era: Era::The80s,
+    index: 1,
+};
 
 // ─── Components ───────────────────────────────────────────────────
 
+/// Tags every entity the game spawns so it is despawned on exit.
 #[derive(Component)]
-struct CaptainEntity;
+#[require(StateCleanup<PlayingState> = StateCleanup(CosmicCaptain::PLAYING_STATE))]
+pub struct CaptainEntity;
 
 #[derive(Component)]
 struct Captain {
     can_shoot: bool,
     has_boots: bool,
+    /// Landed on a platform since the last movement tick.
+    grounded: bool,
+    coyote: GraceWindow,
 }
 
 #[derive(Component)]
@@ -61,6 +113,10 @@ enum ItemKind {
     Boots,        // Higher jump
 }
 
+/// The door out of the level.
+#[derive(Component)]
+struct LevelExit;
+
 #[derive(Component)]
 struct CaptainHud;
 
@@ -69,6 +125,9 @@ struct CaptainHud;
 fn setup_captain(mut commands: Commands) {
     info!("Setting up Cosmic Captain...");
 
+    commands.insert_resource(Score::default());
+    commands.insert_resource(Lives::new(CosmicCaptain::STARTING_LIVES));
+
     // Background (Dark blue space)
     commands.spawn((
         CaptainEntity,
@@ -81,7 +140,7 @@ fn setup_captain(mut commands: Commands) {
     ));
 
     // Ground & Platforms
-    for i in -20..20 {
+    for i in (-20..20).filter(|i| !PIT.contains(i)) {
         commands.spawn((
             CaptainEntity,
             Platform,
@@ -116,6 +175,8 @@ fn setup_captain(mut commands: Commands) {
         Captain {
             can_shoot: false,
             has_boots: false,
+            grounded: false,
+            coyote: GraceWindow::new(COYOTE_TIME),
         },
         Health::new(12),
         Velocity::default(),
@@ -124,7 +185,7 @@ fn setup_captain(mut commands: Commands) {
             custom_size: Some(Vec2::new(28.0, 36.0)),
             ..default()
         },
-        Transform::from_xyz(-200.0, 0.0, 1.0),
+        Transform::from_translation(START),
     ));
 
     // Items
@@ -140,6 +201,30 @@ fn setup_captain(mut commands: Commands) {
         },
         Transform::from_xyz(100.0, 60.0, 0.5),
     ));
+    commands.spawn((
+        CaptainEntity,
+        Item {
+            kind: ItemKind::Boots,
+        },
+        Sprite {
+            color: colors::EGA_MAGENTA,
+            custom_size: Some(Vec2::new(20.0, 16.0)),
+            ..default()
+        },
+        Transform::from_xyz(-100.0, -110.0, 0.5),
+    ));
+
+    // Exit, past the pit
+    commands.spawn((
+        CaptainEntity,
+        LevelExit,
+        Sprite {
+            color: colors::EGA_BRIGHT_GREEN,
+            custom_size: Some(Vec2::new(24.0, 48.0)),
+            ..default()
+        },
+        Transform::from_xyz(EXIT_X, -110.0, 0.5),
+    ));
 
     spawn_captain_hud(&mut commands);
 }
@@ -189,17 +274,12 @@ struct CaptainStatsText;
 
 fn captain_movement(
     time: Res<Time>,
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mut query: Query<(&mut Velocity, &mut Transform, &Captain), With<Player>>,
+    input: Res<GameInput>,
+    mut buffer: ResMut<InputBuffer>,
+    mut query: Query<(&mut Velocity, &mut Transform, &mut Captain), With<Player>>,
 ) {
-    for (mut vel, mut transform, captain) in &mut query {
-        let mut move_dir = 0.0;
-        if keyboard.pressed(KeyCode::ArrowLeft) || keyboard.pressed(KeyCode::KeyA) {
-            move_dir -= 1.0;
-        }
-        if keyboard.pressed(KeyCode::ArrowRight) || keyboard.pressed(KeyCode::KeyD) {
-            move_dir += 1.0;
-        }
+    for (mut vel, mut transform, mut captain) in &mut query {
+        let move_dir = input.horizontal();
 
         transform.translation.x += move_dir * MOVE_SPEED * time.delta_secs();
 
@@ -207,37 +287,37 @@ fn captain_movement(
         vel.y += GRAVITY * time.delta_secs();
         transform.translation.y += vel.y * time.delta_secs();
 
-        // Jump
-        if keyboard.just_pressed(KeyCode::Space)
-            || keyboard.just_pressed(KeyCode::ArrowUp)
-            || keyboard.just_pressed(KeyCode::KeyW)
-        {
-            // Jump if on ground (simple threshold)
-            if transform.translation.y <= -132.0 || transform.translation.y == -18.0 {
-                vel.y = if captain.has_boots {
-                    JUMP_FORCE * 1.3
-                } else {
-                    JUMP_FORCE
-                };
-            }
+        // Jump if on ground, with coyote time and a buffered press so
+        // early or late jumps still land.
+        let grounded = std::mem::take(&mut captain.grounded);
+        captain.coyote.update(grounded, time.delta());
+        if buffer.pressed_within(GameAction::Jump, JUMP_BUFFER) && captain.coyote.is_open() {
+            vel.y = if captain.has_boots {
+                JUMP_FORCE * 1.3
+            } else {
+                JUMP_FORCE
+            };
+            buffer.consume(GameAction::Jump);
+            captain.coyote.close();
         }
     }
 }
 
 fn platform_collision(
-    mut query: Query<(&mut Transform, &mut Velocity), With<Player>>,
+    mut query: Query<(&mut Transform, &mut Velocity, &mut Captain), With<Player>>,
     plat_query: Query<&Transform, (With<Platform>, Without<Player>)>,
 ) {
-    for (mut p_transform, mut vel) in &mut query {
+    for (mut p_transform, mut vel, mut captain) in &mut query {
         for plat_transform in &plat_query {
             let dx = p_transform.translation.x - plat_transform.translation.x;
             let dy = p_transform.translation.y - plat_transform.translation.y;
 
-            if dx.abs() < 24.0 && dy.abs() < 30.0 {
+            if dx.abs() < 24.0 && dy.abs() <= 32.0 {
                 // Land on top
                 if vel.y < 0.0 && p_transform.translation.y > plat_transform.translation.y {
                     p_transform.translation.y = plat_transform.translation.y + 32.0;
                     vel.y = 0.0;
+                    captain.grounded = true;
                 }
             }
         }
@@ -249,6 +329,8 @@ fn collect_items(
     player_query: Query<&Transform, With<Player>>,
     item_query: Query<(Entity, &Transform, &Item)>,
     mut captain_query: Query<&mut Captain>,
+    mut score: ResMut<Score>,
+    mut stats: MessageWriter<StatEvent>,
 ) {
     let p_transform = match player_query.single() {
         Ok(t) => t,
@@ -258,6 +340,7 @@ fn collect_items(
     for (entity, i_transform, item) in &item_query {
         if (p_transform.translation - i_transform.translation).length() < 25.0 {
             commands.entity(entity).despawn();
+            score.add(ITEM_POINTS);
             if let Ok(mut captain) = captain_query.single_mut() {
                 match item.kind {
                     ItemKind::BlastolaCola => {
@@ -269,11 +352,68 @@ fn collect_items(
                         info!("Found Boots! Jump higher!");
                     }
                 }
+                let found = captain.can_shoot as u64 + captain.has_boots as u64;
+                stats.write(StatEvent::best("cosmic_captain.items_found", found));
             }
         }
     }
 }
 
+/// Falling into the pit costs a life; the Captain starts over from
+/// `START` until none are left.
+fn check_fall(
+    mut player_query: Query<(&mut Transform, &mut Velocity), With<Player>>,
+    mut lives: ResMut<Lives>,
+    score: Res<Score>,
+    mut finished: MessageWriter<MiniGameFinished>,
+) {
+    let Ok((mut transform, mut vel)) = player_query.single_mut() else {
+        return;
+    };
+    if lives.is_game_over() || transform.translation.y > FALL_LIMIT {
+        return;
+    }
+
+    lives.lose_one();
+    info!("Fell into the pit! Lives remaining: {}", lives.count);
+    if lives.is_game_over() {
+        finished.write(MiniGameFinished {
+            game: GAME_ID,
+            score: score.value,
+            outcome: MiniGameOutcome::Defeat,
+        });
+    } else {
+        transform.translation = START;
+        vel.y = 0.0;
+    }
+}
+
+/// Reaching the exit clears the level.
+fn check_exit(
+    mut commands: Commands,
+    player_query: Query<&Transform, With<Player>>,
+    exit_query: Query<(Entity, &Transform), With<LevelExit>>,
+    mut score: ResMut<Score>,
+    mut finished: MessageWriter<MiniGameFinished>,
+) {
+    let Ok(p_transform) = player_query.single() else {
+        return;
+    };
+    for (exit, e_transform) in &exit_query {
+        if (p_transform.translation - e_transform.translation).length() < 30.0 {
+            // Despawned so the level only ends once.
+            commands.entity(exit).despawn();
+            score.add(EXIT_POINTS);
+            info!("Level complete! Final score: {}", score.value);
+            finished.write(MiniGameFinished {
+                game: GAME_ID,
+                score: score.value,
+                outcome: MiniGameOutcome::Victory,
+            });
+        }
+    }
+}
+
 fn enemy_ai() {
     // Placeholder for simple patrol enemies
 }
@@ -292,20 +432,3 @@ fn update_hud(
         }
     }
 }
-
-fn handle_pause(
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mut next_state: ResMut<NextState<GameState>>,
-    mut results: ResMut<GameResults>,
-) {
-    if keyboard.just_pressed(KeyCode::Escape) {
-        results.game_name = "Cosmic Captain".to_string();
-        next_state.set(GameState::Results);
-    }
-}
-
-fn cleanup_captain(mut commands: Commands, query: Query<Entity, With<CaptainEntity>>) {
-    for entity in &query {
-        commands.entity(entity).despawn();
-    }
-}
@@ -1,8 +1,16 @@
-use crate::core::states::{GameState, PlayingState};
+use crate::core::input::{GameAction, GameInput};
+use crate::core::progression::{MiniGameFinished, MiniGameOutcome};
+use crate::core::rng::GameRng;
+use crate::core::states::{Era, MiniGameId, PlayingState};
+use crate::core::stats::StatEvent;
+use crate::eras::shared::{MiniGame, MiniGamePlugin};
 use crate::shared::components::{Health, Player, Velocity};
-use crate::ui::colors;
-use crate::ui::results::GameResults;
+use crate::ui::{StateCleanup, StateScopedResourceExt, colors};
+use avian2d::prelude::TransformInterpolation;
+use bevy::ecs::schedule::ScheduleConfigs;
+use bevy::ecs::system::ScheduleSystem;
 use bevy::prelude::*;
+use rand::{Rng, RngExt};
 
 /// Star Goose — inspired by Star Goose (1988).
 /// Vertical-scrolling shooter with resource management.
@@ -10,30 +18,50 @@ pub struct StarGoosePlugin;
 
 impl Plugin for StarGoosePlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<StarGooseResources>()
-            .init_resource::<TunnelState>()
-            .add_systems(
-                OnEnter(GameState::Playing),
-                setup_star_goose.run_if(in_state(PlayingState::StarGoose)),
-            )
-            .add_systems(
-                Update,
-                (
-                    ship_movement,
-                    ship_shoot,
-                    missile_movement,
-                    scroll_tunnel,
-                    wall_collision,
-                    combat_collisions,
-                    resource_drain,
-                    collection_system,
-                    enemy_system,
-                    update_hud,
-                    handle_pause,
-                )
-                    .run_if(in_state(PlayingState::StarGoose)),
-            )
-            .add_systems(OnExit(GameState::Playing), cleanup_star_goose);
+        app.add_plugins(MiniGamePlugin::<StarGoose>::default())
+            .init_state_scoped_resource::<StarGooseResources>(StarGoose::PLAYING_STATE)
+            .add_state_scoped_resource::<TunnelState>(StarGoose::PLAYING_STATE);
+    }
+}
+
+/// Registry entry for Star Goose.
+pub struct StarGoose;
+
+impl MiniGame for StarGoose {
+    const ID: MiniGameId = GAME_ID;
+    const PLAYING_STATE: PlayingState = PlayingState::StarGoose;
+    const NAME: &'static str = "Star Goose";
+    const DESCRIPTION: &'static str = "Vertical-scrolling shooter over alien terrain";
+    const THUMBNAIL: &'static str = "ui/thumbnails/star_goose.png";
+    const COMPLETION_THRESHOLD: u64 = 1000;
+
+    fn setup() -> ScheduleConfigs<ScheduleSystem> {
+        setup_star_goose.into_configs()
+    }
+
+    fn gameplay() -> ScheduleConfigs<ScheduleSystem> {
+        (
+            ship_movement,
+            ship_shoot,
+            missile_movement,
+            scroll_tunnel,
+            wall_collision,
+            combat_collisions,
+            resource_drain,
+            collection_system,
+            enemy_system,
+        )
+            .into_configs()
+    }
+
+    fn frame() -> ScheduleConfigs<ScheduleSystem> {
+        update_hud.into_configs()
+    }
+
+    fn score(world: &World) -> u64 {
+        world
+            .get_resource::<StarGooseResources>()
+            .map_or(0, |resources| resources.score as u64)
     }
 }
 
@@ -45,24 +73,36 @@ const MISSILE_SPEED: f32 = 500.0;
 const BLOCK_SIZE: f32 = 64.0; // Larger blocks for the "chunky" look
 const TUNNEL_WIDTH_BLOCKS: i32 = 8; // Inner width in blocks
 
+const GAME_ID: MiniGameId = MiniGameId {
+    era: Era::The80s,
+    index: 2,
+};
+
 // ─── Components ───────────────────────────────────────────────────
 
+/// Tags every entity the game spawns so it is despawned on exit.
 #[derive(Component)]
-struct StarGooseEntity;
+#[require(StateCleanup<PlayingState> = StateCleanup(StarGoose::PLAYING_STATE))]
+pub struct StarGooseEntity;
 
 #[derive(Component)]
+#[require(TransformInterpolation)]
 struct Crystal;
 
 #[derive(Component)]
+#[require(TransformInterpolation)]
 struct FuelPod;
 
 #[derive(Component)]
+#[require(TransformInterpolation)]
 struct Missile;
 
 #[derive(Component)]
+#[require(TransformInterpolation)]
 struct WallBlock;
 
 #[derive(Component)]
+#[require(TransformInterpolation)]
 struct TunnelSegment;
 
 #[derive(Component)]
@@ -82,6 +122,7 @@ enum HudField {
 }
 
 #[derive(Component)]
+#[require(TransformInterpolation)]
 struct Enemy {
     enemy_type: EnemyType,
     health: i32,
@@ -135,17 +176,14 @@ impl Default for TunnelState {
 
 // ─── Setup ─────────────────────────────────────────────────────────
 
-fn setup_star_goose(mut commands: Commands) {
+fn setup_star_goose(mut commands: Commands, mut rng: ResMut<GameRng>) {
     info!("Setting up Star Goose...");
 
-    commands.insert_resource(StarGooseResources::default());
-
     let mut tunnel_state = TunnelState::default();
-    tunnel_state.next_y = -400.0;
 
     // Pre-spawn tunnel
     while tunnel_state.next_y < 600.0 {
-        spawn_tunnel_row(&mut commands, &mut tunnel_state);
+        spawn_tunnel_row(&mut commands, &mut tunnel_state, rng.procgen());
     }
 
     commands.insert_resource(tunnel_state);
@@ -178,7 +216,7 @@ fn setup_star_goose(mut commands: Commands) {
     spawn_goose_hud(&mut commands);
 }
 
-fn spawn_tunnel_row(commands: &mut Commands, state: &mut TunnelState) {
+fn spawn_tunnel_row(commands: &mut Commands, state: &mut TunnelState, rng: &mut impl Rng) {
     let y = state.next_y;
     let cx = state.center_x_index as f32 * BLOCK_SIZE;
 
@@ -250,10 +288,10 @@ fn spawn_tunnel_row(commands: &mut Commands, state: &mut TunnelState) {
     ));
 
     // Spawning Items/Enemies
-    let rng = rand::random::<f32>();
-    if rng < 0.05 {
+    let roll = rng.random::<f32>();
+    if roll < 0.05 {
         // Crystal
-        let offset = (rand::random::<f32>() - 0.5) * (half_width * 1.5);
+        let offset = (rng.random::<f32>() - 0.5) * (half_width * 1.5);
         commands.spawn((
             StarGooseEntity,
             Crystal,
@@ -264,9 +302,9 @@ fn spawn_tunnel_row(commands: &mut Commands, state: &mut TunnelState) {
             },
             Transform::from_xyz(cx + offset, y, 0.5),
         ));
-    } else if rng < 0.08 {
+    } else if roll < 0.08 {
         // Fuel
-        let offset = (rand::random::<f32>() - 0.5) * (half_width * 1.5);
+        let offset = (rng.random::<f32>() - 0.5) * (half_width * 1.5);
         commands.spawn((
             StarGooseEntity,
             FuelPod,
@@ -277,9 +315,9 @@ fn spawn_tunnel_row(commands: &mut Commands, state: &mut TunnelState) {
             },
             Transform::from_xyz(cx + offset, y, 0.5),
         ));
-    } else if rng < 0.12 {
+    } else if roll < 0.12 {
         // Mine Enemy
-        let offset = (rand::random::<f32>() - 0.5) * (half_width * 1.4);
+        let offset = (rng.random::<f32>() - 0.5) * (half_width * 1.4);
         commands.spawn((
             StarGooseEntity,
             Enemy {
@@ -293,9 +331,9 @@ fn spawn_tunnel_row(commands: &mut Commands, state: &mut TunnelState) {
             },
             Transform::from_xyz(cx + offset, y, 0.6),
         ));
-    } else if rng < 0.14 {
+    } else if roll < 0.14 {
         // Chaser Enemy
-        let offset = (rand::random::<f32>() - 0.5) * (half_width * 1.0);
+        let offset = (rng.random::<f32>() - 0.5) * (half_width * 1.0);
         commands.spawn((
             StarGooseEntity,
             Enemy {
@@ -316,7 +354,7 @@ fn spawn_tunnel_row(commands: &mut Commands, state: &mut TunnelState) {
     state.steps_until_turn -= 1;
 
     if state.steps_until_turn <= 0 {
-        let r = rand::random::<f32>();
+        let r = rng.random::<f32>();
         if r < 0.3 {
             state.current_direction = -1;
         } else if r < 0.6 {
@@ -324,7 +362,7 @@ fn spawn_tunnel_row(commands: &mut Commands, state: &mut TunnelState) {
         } else {
             state.current_direction = 0;
         }
-        state.steps_until_turn = rand::random::<i32>() % 4 + 2;
+        state.steps_until_turn = rng.random_range(2..=5);
     }
 
     state.center_x_index += state.current_direction;
@@ -466,9 +504,11 @@ fn spawn_goose_hud(commands: &mut Commands) {
 
 // ─── Systems ───────────────────────────────────────────────────────
 
+#[allow(clippy::type_complexity)]
 fn scroll_tunnel(
     time: Res<Time>,
     mut commands: Commands,
+    mut rng: ResMut<GameRng>,
     mut query: Query<
         (Entity, &mut Transform),
         Or<(
@@ -494,7 +534,7 @@ fn scroll_tunnel(
     state.next_y -= move_dist;
 
     while state.next_y < 600.0 {
-        spawn_tunnel_row(&mut commands, &mut state);
+        spawn_tunnel_row(&mut commands, &mut state, rng.procgen());
     }
 }
 
@@ -539,7 +579,8 @@ fn combat_collisions(
     mut enemy_query: Query<(Entity, &Transform, &mut Enemy)>,
     missile_query: Query<(Entity, &Transform), With<Missile>>,
     mut resources: ResMut<StarGooseResources>,
-    mut next_state: ResMut<NextState<GameState>>,
+    mut finished: MessageWriter<MiniGameFinished>,
+    mut stats: MessageWriter<StatEvent>,
 ) {
     let (mut p_health, p_transform) = match player_query.iter_mut().next() {
         Some(p) => p,
@@ -556,7 +597,11 @@ fn combat_collisions(
                 p_health.damage(10);
             }
             if p_health.current <= 0 {
-                next_state.set(GameState::Results);
+                finished.write(MiniGameFinished {
+                    game: GAME_ID,
+                    score: resources.score as u64,
+                    outcome: MiniGameOutcome::Defeat,
+                });
             }
         }
     }
@@ -570,6 +615,7 @@ fn combat_collisions(
                 if enemy.health <= 0 {
                     commands.entity(e_entity).despawn();
                     resources.score += 50;
+                    stats.write(StatEvent::add("star_goose.kills", 1));
                 }
                 break;
             }
@@ -579,28 +625,11 @@ fn combat_collisions(
 
 fn ship_movement(
     time: Res<Time>,
-    keyboard: Res<ButtonInput<KeyCode>>,
+    input: Res<GameInput>,
     mut query: Query<&mut Transform, With<Player>>,
 ) {
     for mut transform in &mut query {
-        let mut delta = Vec2::ZERO;
-        if keyboard.pressed(KeyCode::ArrowLeft) || keyboard.pressed(KeyCode::KeyA) {
-            delta.x -= 1.0;
-        }
-        if keyboard.pressed(KeyCode::ArrowRight) || keyboard.pressed(KeyCode::KeyD) {
-            delta.x += 1.0;
-        }
-        if keyboard.pressed(KeyCode::ArrowUp) || keyboard.pressed(KeyCode::KeyW) {
-            delta.y += 1.0;
-        }
-        if keyboard.pressed(KeyCode::ArrowDown) || keyboard.pressed(KeyCode::KeyS) {
-            delta.y -= 1.0;
-        }
-
-        if delta != Vec2::ZERO {
-            delta = delta.normalize();
-        }
-
+        let delta = input.move_direction;
         transform.translation += delta.extend(0.0) * SHIP_SPEED * time.delta_secs();
         transform.translation.y = transform.translation.y.clamp(-350.0, 350.0);
     }
@@ -608,30 +637,29 @@ fn ship_movement(
 
 fn ship_shoot(
     mut commands: Commands,
-    keyboard: Res<ButtonInput<KeyCode>>,
+    input: Res<GameInput>,
     player_query: Query<&Transform, With<Player>>,
     mut resources: ResMut<StarGooseResources>,
 ) {
-    if keyboard.just_pressed(KeyCode::Space) {
-        if resources.ammo > 0 {
-            if let Some(player_transform) = player_query.iter().next() {
-                commands.spawn((
-                    StarGooseEntity,
-                    Missile,
-                    Sprite {
-                        color: colors::EGA_BRIGHT_YELLOW,
-                        custom_size: Some(Vec2::new(8.0, 20.0)),
-                        ..default()
-                    },
-                    Transform::from_xyz(
-                        player_transform.translation.x,
-                        player_transform.translation.y + 20.0,
-                        0.0,
-                    ),
-                ));
-                resources.ammo -= 1;
-            }
-        }
+    if input.just_pressed(GameAction::Fire)
+        && resources.ammo > 0
+        && let Some(player_transform) = player_query.iter().next()
+    {
+        commands.spawn((
+            StarGooseEntity,
+            Missile,
+            Sprite {
+                color: colors::EGA_BRIGHT_YELLOW,
+                custom_size: Some(Vec2::new(8.0, 20.0)),
+                ..default()
+            },
+            Transform::from_xyz(
+                player_transform.translation.x,
+                player_transform.translation.y + 20.0,
+                0.0,
+            ),
+        ));
+        resources.ammo -= 1;
     }
 }
 
@@ -651,12 +679,16 @@ fn missile_movement(
 fn resource_drain(
     time: Res<Time>,
     mut resources: ResMut<StarGooseResources>,
-    mut next_state: ResMut<NextState<GameState>>,
+    mut finished: MessageWriter<MiniGameFinished>,
 ) {
     resources.fuel -= 2.0 * time.delta_secs();
     if resources.fuel <= 0.0 {
         info!("Out of fuel!");
-        next_state.set(GameState::Results);
+        finished.write(MiniGameFinished {
+            game: GAME_ID,
+            score: resources.score as u64,
+            outcome: MiniGameOutcome::Defeat,
+        });
     }
 }
 
@@ -665,6 +697,7 @@ fn collection_system(
     player_query: Query<&Transform, With<Player>>,
     item_query: Query<(Entity, &Transform, Option<&Crystal>, Option<&FuelPod>)>,
     mut resources: ResMut<StarGooseResources>,
+    mut stats: MessageWriter<StatEvent>,
 ) {
     let p_pos = match player_query.iter().next() {
         Some(t) => t.translation,
@@ -676,6 +709,7 @@ fn collection_system(
             commands.entity(entity).despawn();
             if crystal.is_some() {
                 resources.score += 100;
+                stats.write(StatEvent::add("star_goose.crystals", 1));
             }
             if fuel.is_some() {
                 resources.fuel = (resources.fuel + 15.0).min(100.0);
@@ -720,20 +754,3 @@ fn update_hud(resources: Res<StarGooseResources>, mut texts: Query<(&mut Text, &
         }
     }
 }
-
-fn handle_pause(
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mut next_state: ResMut<NextState<GameState>>,
-    mut results: ResMut<GameResults>,
-) {
-    if keyboard.just_pressed(KeyCode::Escape) {
-        results.game_name = "Star Goose".to_string();
-        next_state.set(GameState::Results);
-    }
-}
-
-fn cleanup_star_goose(mut commands: Commands, query: Query<Entity, With<StarGooseEntity>>) {
-    for entity in &query {
-        commands.entity(entity).despawn();
-    }
-}
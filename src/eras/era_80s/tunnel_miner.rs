@@ -1,10 +1,16 @@
+use bevy::ecs::schedule::ScheduleConfigs;
+use bevy::ecs::system::ScheduleSystem;
 use bevy::prelude::*;
-
-use crate::core::progression::PlayerProgress;
-use crate::core::states::{Era, GameState, MiniGameId, PlayingState};
+use std::time::Duration;
+
+use crate::core::input::{GameAction, GameInput, InputBuffer};
+use crate::core::progression::{MiniGameFinished, MiniGameOutcome};
+use crate::core::settings::{AudioChannel, UserSettings};
+use crate::core::states::{Era, MiniGameId, PlayingState};
+use crate::core::stats::StatEvent;
+use crate::eras::shared::{MiniGame, MiniGamePlugin};
 use crate::shared::components::{GridPosition, Health, Lives, Player, Score};
-use crate::ui::colors;
-use crate::ui::results::GameResults;
+use crate::ui::{StateCleanup, StateScopedResourceExt, colors};
 
 /// Tunnel Miner — inspired by Digger (1983).
 /// Grid-based digging, emerald collection, enemy avoidance.
@@ -12,32 +18,53 @@ pub struct TunnelMinerPlugin;
 
 impl Plugin for TunnelMinerPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            OnEnter(GameState::Playing),
-            setup_tunnel_miner.run_if(in_state(PlayingState::TunnelMiner)),
-        )
-        .add_systems(
-            Update,
-            (
-                player_movement,
-                animate_sprites,
-                dig_system,
-                collect_emeralds,
-                enemy_ai,
-                enemy_spawner,
-                gold_bag_physics,
-                weapon_system,
-                fireball_collision,
-                cherry_system,
-                collect_cherry,
-                level_progression,
-                check_death,
-                update_hud,
-                handle_pause,
-            )
-                .run_if(in_state(PlayingState::TunnelMiner)),
+        app.add_plugins(MiniGamePlugin::<TunnelMiner>::default())
+            .add_state_scoped_resource::<TunnelMinerState>(TunnelMiner::PLAYING_STATE)
+            .add_state_scoped_resource::<EnemySpawner>(TunnelMiner::PLAYING_STATE)
+            .add_state_scoped_resource::<Score>(TunnelMiner::PLAYING_STATE)
+            .add_state_scoped_resource::<Lives>(TunnelMiner::PLAYING_STATE);
+    }
+}
+
+/// Registry entry for Tunnel Miner.
+pub struct TunnelMiner;
+
+impl MiniGame for TunnelMiner {
+    const ID: MiniGameId = GAME_ID;
+    const PLAYING_STATE: PlayingState = PlayingState::TunnelMiner;
+    const NAME: &'static str = "Tunnel Miner";
+    const DESCRIPTION: &'static str = "Dig tunnels, collect emeralds, crush enemies!";
+    const THUMBNAIL: &'static str = "ui/thumbnails/tunnel_miner.png";
+    const COMPLETION_THRESHOLD: u64 = 1000;
+
+    fn setup() -> ScheduleConfigs<ScheduleSystem> {
+        setup_tunnel_miner.into_configs()
+    }
+
+    fn gameplay() -> ScheduleConfigs<ScheduleSystem> {
+        (
+            player_movement,
+            dig_system,
+            collect_emeralds,
+            enemy_ai,
+            enemy_spawner,
+            gold_bag_physics,
+            weapon_system,
+            fireball_collision,
+            cherry_system,
+            collect_cherry,
+            level_progression,
+            check_death,
         )
-        .add_systems(OnExit(GameState::Playing), cleanup_tunnel_miner);
+            .into_configs()
+    }
+
+    fn frame() -> ScheduleConfigs<ScheduleSystem> {
+        (animate_sprites, update_hud).into_configs()
+    }
+
+    fn score(world: &World) -> u64 {
+        world.get_resource::<Score>().map_or(0, |score| score.value)
     }
 }
 
@@ -47,6 +74,8 @@ const GRID_WIDTH: i32 = 15;
 const GRID_HEIGHT: i32 = 10;
 const TILE_SIZE: f32 = 48.0;
 const MOVE_SPEED: f32 = 5.0; // tiles per second
+/// A tapped direction is remembered for one grid step so turns aren't dropped.
+const TURN_BUFFER: Duration = Duration::from_millis(200);
 
 // Scoring
 const EMERALD_POINTS: u64 = 25;
@@ -55,7 +84,11 @@ const GOLD_PILE_POINTS: u64 = 500;
 const ENEMY_KILL_POINTS: u64 = 250;
 const ENEMY_CRUSH_POINTS: u64 = 250;
 const CHERRY_POINTS: u64 = 1000;
-const COMPLETION_THRESHOLD: u64 = 1000;
+
+const GAME_ID: MiniGameId = MiniGameId {
+    era: Era::The80s,
+    index: 0,
+};
 
 const MAP_80S_GEMS: [[u8; 15]; 10] = [
     [1, 1, 1, 0, 1, 1, 1, 0, 1, 1, 1, 0, 1, 1, 1],
@@ -72,8 +105,10 @@ const MAP_80S_GEMS: [[u8; 15]; 10] = [
 
 // ─── Components ────────────────────────────────────────────────────
 
+/// Tags every entity the game spawns so it is despawned on exit.
 #[derive(Component)]
-struct TunnelMinerEntity; // Marker for cleanup
+#[require(StateCleanup<PlayingState> = StateCleanup(TunnelMiner::PLAYING_STATE))]
+pub struct TunnelMinerEntity; // Marker for cleanup
 
 #[derive(Component)]
 struct TunnelMinerPlayer {
@@ -115,7 +150,7 @@ struct Fireball {
 }
 
 #[derive(Component)]
-struct Nobbin {
+pub struct Nobbin {
     hobbin: bool, // transformed into a Hobbin?
     move_timer: Timer,
     time_alive: f32,
@@ -129,6 +164,7 @@ struct EnemySpawner {
     active_count: u32,
 }
 
+#[allow(dead_code)] // Fire-ball state is tracked but not yet read back.
 #[derive(Component)]
 struct Weapon {
     active: bool,
@@ -179,7 +215,11 @@ enum AnimationFn {
 
 // ─── Setup ─────────────────────────────────────────────────────────
 
-fn setup_tunnel_miner(mut commands: Commands, asset_server: Res<AssetServer>) {
+fn setup_tunnel_miner(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    settings: Res<UserSettings>,
+) {
     info!("Setting up Tunnel Miner level...");
 
     // Initialize game state
@@ -201,12 +241,13 @@ fn setup_tunnel_miner(mut commands: Commands, asset_server: Res<AssetServer>) {
 
     commands.spawn((
         AudioPlayer::new(asset_server.load("music/Pixel Popcorn Rush.mp3")),
-        PlaybackSettings::LOOP,
+        PlaybackSettings::LOOP.with_volume(settings.volume(AudioChannel::Music)),
+        AudioChannel::Music,
         TunnelMinerEntity,
     ));
 
     commands.insert_resource(Score::default());
-    commands.insert_resource(Lives::new(3));
+    commands.insert_resource(Lives::new(TunnelMiner::STARTING_LIVES));
 
     // Grid origin (center the grid on screen)
     let origin_x = -(GRID_WIDTH as f32 * TILE_SIZE) / 2.0;
@@ -419,7 +460,7 @@ fn setup_tunnel_miner(mut commands: Commands, asset_server: Res<AssetServer>) {
     */
 
     commands.insert_resource(TunnelMinerState {
-        emeralds_remaining: emerald_count as u32,
+        emeralds_remaining: emerald_count,
         level: 1,
         bonus_mode_active: false,
         bonus_mode_timer: Timer::from_seconds(10.0, TimerMode::Once),
@@ -433,6 +474,7 @@ fn setup_tunnel_miner(mut commands: Commands, asset_server: Res<AssetServer>) {
     info!("Tunnel Miner: {} emeralds placed", emerald_count);
 }
 
+#[allow(dead_code)] // Levels are laid out from MAP_80S_GEMS for now.
 fn should_place_emerald(x: i32, y: i32) -> bool {
     // Deterministic pattern for emerald placement
     let hash = ((x * 31 + y * 17 + 7) % 5) as u32;
@@ -506,20 +548,21 @@ fn animate_sprites(
 ) {
     for (mut state, frames, mut sprite) in &mut query {
         state.timer.tick(time.delta());
-        if state.timer.just_finished() {
-            if let Some(anim_frames) = frames.animations.get(&state.current_animation) {
-                if !anim_frames.is_empty() {
-                    state.frame_index = (state.frame_index + 1) % anim_frames.len();
-                    sprite.image = anim_frames[state.frame_index].clone();
-                }
-            }
+        if state.timer.just_finished()
+            && let Some(anim_frames) = frames.animations.get(&state.current_animation)
+            && !anim_frames.is_empty()
+        {
+            state.frame_index = (state.frame_index + 1) % anim_frames.len();
+            sprite.image = anim_frames[state.frame_index].clone();
         }
     }
 }
 
+#[allow(clippy::type_complexity)]
 fn player_movement(
     time: Res<Time>,
-    keyboard: Res<ButtonInput<KeyCode>>,
+    input: Res<GameInput>,
+    mut buffer: ResMut<InputBuffer>,
     mut player_query: Query<
         (
             &mut TunnelMinerPlayer,
@@ -537,7 +580,7 @@ fn player_movement(
     // Index obstacles for push validation
     let earth_positions: std::collections::HashSet<(i32, i32)> =
         other_queries.p1().iter().map(|p| (p.x, p.y)).collect();
-    let mut bag_positions: std::collections::HashSet<(i32, i32)> = other_queries
+    let bag_positions: std::collections::HashSet<(i32, i32)> = other_queries
         .p0()
         .iter()
         .filter(|(_, _, b)| !b.is_pile)
@@ -548,26 +591,27 @@ fn player_movement(
         player.move_timer.tick(time.delta());
 
         let mut moving = false;
-        let mut target_anim = anim_state.current_animation;
+        let target_anim;
         let mut target_facing = player.facing;
         let mut target_pos = *grid_pos;
+        let steer = steer_direction(&input, &buffer);
 
-        if keyboard.pressed(KeyCode::ArrowLeft) || keyboard.pressed(KeyCode::KeyA) {
+        if steer == Some(Direction::Left) {
             target_pos.x -= 1;
             target_facing = Direction::Left;
             target_anim = AnimationFn::MoveLeft;
             moving = true;
-        } else if keyboard.pressed(KeyCode::ArrowRight) || keyboard.pressed(KeyCode::KeyD) {
+        } else if steer == Some(Direction::Right) {
             target_pos.x += 1;
             target_facing = Direction::Right;
             target_anim = AnimationFn::MoveRight;
             moving = true;
-        } else if keyboard.pressed(KeyCode::ArrowUp) || keyboard.pressed(KeyCode::KeyW) {
+        } else if steer == Some(Direction::Up) {
             target_pos.y += 1;
             target_facing = Direction::Up;
             target_anim = AnimationFn::MoveUp;
             moving = true;
-        } else if keyboard.pressed(KeyCode::ArrowDown) || keyboard.pressed(KeyCode::KeyS) {
+        } else if steer == Some(Direction::Down) {
             target_pos.y -= 1;
             target_facing = Direction::Down;
             target_anim = AnimationFn::MoveDown;
@@ -646,6 +690,7 @@ fn player_movement(
                     transform.translation.y =
                         origin_y + grid_pos.y as f32 * TILE_SIZE + TILE_SIZE / 2.0;
                     player.move_timer.reset();
+                    buffer.consume(direction_action(target_facing));
                 }
             } else if !bag_positions.contains(&(target_pos.x, target_pos.y)) {
                 // Simple move (earth digging handled separately or implicitly)
@@ -655,11 +700,47 @@ fn player_movement(
                 transform.translation.y =
                     origin_y + grid_pos.y as f32 * TILE_SIZE + TILE_SIZE / 2.0;
                 player.move_timer.reset();
+                buffer.consume(direction_action(target_facing));
             }
         }
     }
 }
 
+const DIRECTIONS: [Direction; 4] = [
+    Direction::Left,
+    Direction::Right,
+    Direction::Up,
+    Direction::Down,
+];
+
+fn direction_action(direction: Direction) -> GameAction {
+    match direction {
+        Direction::Up => GameAction::Up,
+        Direction::Down => GameAction::Down,
+        Direction::Left => GameAction::Left,
+        Direction::Right => GameAction::Right,
+    }
+}
+
+/// A held direction wins; otherwise the most recent tap still in the buffer.
+fn steer_direction(input: &GameInput, buffer: &InputBuffer) -> Option<Direction> {
+    DIRECTIONS
+        .into_iter()
+        .find(|d| input.pressed(direction_action(*d)))
+        .or_else(|| {
+            DIRECTIONS
+                .into_iter()
+                .filter_map(|d| {
+                    buffer
+                        .since_pressed(direction_action(d))
+                        .filter(|elapsed| *elapsed <= TURN_BUFFER)
+                        .map(|elapsed| (elapsed, d))
+                })
+                .min_by_key(|(elapsed, _)| *elapsed)
+                .map(|(_, d)| d)
+        })
+}
+
 fn dig_system(
     mut commands: Commands,
     player_query: Query<&GridPosition, With<Player>>,
@@ -680,6 +761,7 @@ fn collect_emeralds(
     mut score: ResMut<Score>,
     mut player_query: Query<(&GridPosition, &mut TunnelMinerPlayer), With<Player>>,
     emerald_query: Query<(Entity, &GridPosition), With<Emerald>>,
+    mut stats: MessageWriter<StatEvent>,
 ) {
     if let Some((player_pos, mut player)) = player_query.iter_mut().next() {
         for (entity, emerald_pos) in &emerald_query {
@@ -689,6 +771,11 @@ fn collect_emeralds(
                 tunnel_state.emeralds_remaining = tunnel_state.emeralds_remaining.saturating_sub(1);
 
                 player.emerald_streak += 1;
+                stats.write(StatEvent::add("tunnel_miner.emeralds", 1));
+                stats.write(StatEvent::best(
+                    "tunnel_miner.emerald_streak",
+                    player.emerald_streak as u64,
+                ));
                 if player.emerald_streak >= 8 {
                     score.add(EMERALD_STREAK_BONUS);
                     player.emerald_streak = 0;
@@ -769,6 +856,7 @@ fn enemy_spawner(
     }
 }
 
+#[allow(clippy::type_complexity)]
 fn enemy_ai(
     mut commands: Commands,
     time: Res<Time>,
@@ -867,7 +955,7 @@ fn enemy_ai(
             let next_x = grid_pos.x + odx;
             let next_y = grid_pos.y + ody;
 
-            if next_x < 0 || next_x >= GRID_WIDTH || next_y < 0 || next_y >= GRID_HEIGHT {
+            if !(0..GRID_WIDTH).contains(&next_x) || !(0..GRID_HEIGHT).contains(&next_y) {
                 continue;
             }
 
@@ -881,28 +969,25 @@ fn enemy_ai(
             if !blocked || can_dig {
                 // Perform Digging
                 if blocked && can_dig {
-                    if is_earth {
-                        if let Some((ent, _)) = earth_tiles
+                    if is_earth
+                        && let Some((ent, _)) = earth_tiles
                             .iter()
                             .find(|(_, p)| p.x == next_x && p.y == next_y)
-                        {
-                            commands.entity(*ent).despawn();
-                        }
+                    {
+                        commands.entity(*ent).despawn();
                     }
-                    if is_emerald {
-                        if let Some((ent, _)) = emeralds
+                    if is_emerald
+                        && let Some((ent, _)) = emeralds
                             .iter()
                             .find(|(_, p)| p.x == next_x && p.y == next_y)
-                        {
-                            commands.entity(*ent).despawn();
-                        }
+                    {
+                        commands.entity(*ent).despawn();
                     }
-                    if is_bag {
-                        if let Some((ent, _)) =
+                    if is_bag
+                        && let Some((ent, _)) =
                             bags.iter().find(|(_, p)| p.x == next_x && p.y == next_y)
-                        {
-                            commands.entity(*ent).despawn();
-                        }
+                    {
+                        commands.entity(*ent).despawn();
                     }
                 }
 
@@ -930,6 +1015,7 @@ fn enemy_ai(
     }
 }
 
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 fn gold_bag_physics(
     mut commands: Commands,
     time: Res<Time>,
@@ -952,12 +1038,12 @@ fn gold_bag_physics(
         // If it's already a pile, it doesn't fall or crush, it just waits to be collected
         if bag.is_pile {
             // Check collision with player for collection
-            if let Some((_, player_pos)) = player_query.iter().next() {
-                if *player_pos == *grid_pos {
-                    score.add(GOLD_PILE_POINTS);
-                    commands.entity(entity).despawn();
-                    info!("Gold pile collected! +{}", GOLD_PILE_POINTS);
-                }
+            if let Some((_, player_pos)) = player_query.iter().next()
+                && *player_pos == *grid_pos
+            {
+                score.add(GOLD_PILE_POINTS);
+                commands.entity(entity).despawn();
+                info!("Gold pile collected! +{}", GOLD_PILE_POINTS);
             }
             continue;
         }
@@ -989,17 +1075,16 @@ fn gold_bag_physics(
                     bag.fall_distance += 1;
 
                     // Update transform
-                    let origin_x = -(GRID_WIDTH as f32 * TILE_SIZE) / 2.0;
                     let origin_y = -(GRID_HEIGHT as f32 * TILE_SIZE) / 2.0;
                     transform.translation.y =
                         origin_y + grid_pos.y as f32 * TILE_SIZE + TILE_SIZE / 2.0;
 
                     // Check crushing
                     // Player
-                    if let Some((_, player_pos)) = player_query.iter().next() {
-                        if *player_pos == *grid_pos {
-                            // Kill player
-                        }
+                    if let Some((_, player_pos)) = player_query.iter().next()
+                        && *player_pos == *grid_pos
+                    {
+                        // Kill player
                     }
 
                     // Enemy
@@ -1040,14 +1125,14 @@ fn gold_bag_physics(
 fn weapon_system(
     mut commands: Commands,
     time: Res<Time>,
-    keyboard: Res<ButtonInput<KeyCode>>,
+    input: Res<GameInput>,
     mut player_query: Query<(&mut TunnelMinerPlayer, &GridPosition, &Transform)>,
     asset_server: Res<AssetServer>,
 ) {
     if let Ok((mut player, grid_pos, transform)) = player_query.single_mut() {
         player.weapon_cooldown.tick(time.delta());
 
-        if keyboard.just_pressed(KeyCode::F1) && player.weapon_cooldown.remaining_secs() <= 0.0 {
+        if input.just_pressed(GameAction::Fire) && player.weapon_cooldown.remaining_secs() <= 0.0 {
             // Fire!
             player.weapon_cooldown.reset();
 
@@ -1076,6 +1161,7 @@ fn weapon_system(
     }
 }
 
+#[allow(clippy::type_complexity)]
 fn fireball_collision(
     mut commands: Commands,
     time: Res<Time>,
@@ -1197,25 +1283,13 @@ fn level_progression(
     mut tunnel_state: ResMut<TunnelMinerState>,
     spawner: Res<EnemySpawner>,
     enemy_query: Query<&Nobbin>,
-    mut next_state: ResMut<NextState<GameState>>,
-    mut results: ResMut<GameResults>,
+    mut finished: MessageWriter<MiniGameFinished>,
     score: Res<Score>,
-    progress: Res<PlayerProgress>,
 ) {
     if (tunnel_state.emeralds_remaining == 0 && tunnel_state.emeralds_remaining != 999)
         || (spawner.spawned_count >= spawner.total_to_spawn && enemy_query.iter().count() == 0)
     {
         // Level Complete!
-        let game_id = MiniGameId {
-            era: Era::The80s,
-            index: 0,
-        };
-
-        results.game_name = "Tunnel Miner".to_string();
-        results.score = score.value;
-        results.high_score = progress.high_score(game_id);
-        results.is_new_high = score.value > results.high_score;
-
         if tunnel_state.emeralds_remaining == 0 {
             info!(
                 "All emeralds collected! Level {} complete.",
@@ -1229,7 +1303,11 @@ fn level_progression(
         }
 
         tunnel_state.emeralds_remaining = 999; // Prevent multiple triggers
-        next_state.set(GameState::Results);
+        finished.write(MiniGameFinished {
+            game: GAME_ID,
+            score: score.value,
+            outcome: MiniGameOutcome::Victory,
+        });
     }
 }
 
@@ -1238,10 +1316,8 @@ fn check_death(
     player_query: Query<&GridPosition, With<Player>>,
     enemy_query: Query<(Entity, &GridPosition), With<Nobbin>>,
     mut lives: ResMut<Lives>,
-    mut next_state: ResMut<NextState<GameState>>,
+    mut finished: MessageWriter<MiniGameFinished>,
     mut score: ResMut<Score>,
-    mut results: ResMut<crate::ui::results::GameResults>,
-    progress: Res<PlayerProgress>,
     mut tunnel_state: ResMut<TunnelMinerState>,
 ) {
     let player_pos = match player_query.iter().next() {
@@ -1264,18 +1340,11 @@ fn check_death(
 
                 if lives.is_game_over() {
                     info!("Game Over! Final score: {}", score.value);
-
-                    // Populate results
-                    let game_id = crate::core::states::MiniGameId {
-                        era: crate::core::states::Era::The80s,
-                        index: 0,
-                    };
-                    results.game_name = "Tunnel Miner".to_string();
-                    results.score = score.value;
-                    results.high_score = progress.high_score(game_id);
-                    results.is_new_high = score.value > results.high_score;
-
-                    next_state.set(GameState::Results);
+                    finished.write(MiniGameFinished {
+                        game: GAME_ID,
+                        score: score.value,
+                        outcome: MiniGameOutcome::Defeat,
+                    });
                 }
             }
             return;
@@ -1298,32 +1367,3 @@ fn update_hud(
         **text = format!("Lives: {}", hearts);
     }
 }
-
-fn handle_pause(
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mut next_state: ResMut<NextState<GameState>>,
-    score: Res<Score>,
-    mut results: ResMut<GameResults>,
-    progress: Res<PlayerProgress>,
-) {
-    if keyboard.just_pressed(KeyCode::Escape) {
-        let game_id = MiniGameId {
-            era: Era::The80s,
-            index: 0,
-        };
-        results.game_name = "Tunnel Miner".to_string();
-        results.score = score.value;
-        results.high_score = progress.high_score(game_id);
-
-        next_state.set(GameState::Results);
-    }
-}
-
-// ─── Cleanup ───────────────────────────────────────────────────────
-
-fn cleanup_tunnel_miner(mut commands: Commands, query: Query<Entity, With<TunnelMinerEntity>>) {
-    for entity in &query {
-        commands.entity(entity).despawn();
-    }
-    commands.remove_resource::<TunnelMinerState>();
-}
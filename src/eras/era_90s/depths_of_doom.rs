@@ -1,9 +1,16 @@
-use crate::core::states::{GameState, PlayingState};
-use crate::shared::components::{GridPosition, Health, Player, Score};
-use crate::ui::colors;
-use crate::ui::results::GameResults;
+use crate::core::input::GameInput;
+use crate::core::progression::{MiniGameFinished, MiniGameOutcome};
+use crate::core::rng::GameRng;
+use crate::core::states::{Era, MiniGameId, PlayingState};
+use crate::core::stats::StatEvent;
+use crate::eras::shared::{MiniGame, MiniGamePlugin};
+use crate::shared::components::{GridPosition, Health, Player};
+use crate::ui::{StateCleanup, StateScopedResourceExt, colors};
+use bevy::ecs::schedule::ScheduleConfigs;
+use bevy::ecs::system::ScheduleSystem;
 use bevy::prelude::*;
-use std::collections::HashMap;
+use rand::RngExt;
+use std::collections::{HashMap, HashSet, VecDeque};
 
 /// Depths of Doom — inspired by ADOM (1994).
 /// Turn-based roguelike RPG with procedural dungeons.
@@ -11,42 +18,72 @@ pub struct DepthsOfDoomPlugin;
 
 impl Plugin for DepthsOfDoomPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<DungeonMap>()
-            .add_systems(
-                OnEnter(GameState::Playing),
-                setup_dungeon.run_if(in_state(PlayingState::DepthsOfDoom)),
-            )
-            .add_systems(
-                Update,
-                (
-                    handle_input,
-                    monster_ai,
-                    check_death,
-                    update_hud,
-                    handle_pause,
-                )
-                    .run_if(in_state(PlayingState::DepthsOfDoom)),
-            )
-            .add_systems(OnExit(GameState::Playing), cleanup_dungeon);
+        app.add_plugins(MiniGamePlugin::<DepthsOfDoom>::default())
+            .init_state_scoped_resource::<DungeonMap>(DepthsOfDoom::PLAYING_STATE);
+    }
+}
+
+/// Registry entry for Depths of Doom.
+pub struct DepthsOfDoom;
+
+impl MiniGame for DepthsOfDoom {
+    const ID: MiniGameId = GAME_ID;
+    const PLAYING_STATE: PlayingState = PlayingState::DepthsOfDoom;
+    const NAME: &'static str = "Depths of Doom";
+    const DESCRIPTION: &'static str = "Turn-based roguelike dungeon crawler";
+    const THUMBNAIL: &'static str = "ui/thumbnails/depths_of_doom.png";
+    const COMPLETION_THRESHOLD: u64 = 500;
+
+    fn setup() -> ScheduleConfigs<ScheduleSystem> {
+        setup_dungeon.into_configs()
+    }
+
+    fn gameplay() -> ScheduleConfigs<ScheduleSystem> {
+        (handle_input, descend, monster_ai, check_death)
+            .chain()
+            .into_configs()
+    }
+
+    fn frame() -> ScheduleConfigs<ScheduleSystem> {
+        update_hud.into_configs()
     }
 }
 
 // ─── Constants ─────────────────────────────────────────────────────
 
+#[allow(dead_code)] // setup_dungeon still spells out the bounds.
 const MAP_WIDTH: i32 = 40;
+#[allow(dead_code)]
 const MAP_HEIGHT: i32 = 30;
 const TILE_SIZE: f32 = 24.0;
+/// Where the player arrives on every floor.
+const START: (i32, i32) = (0, 0);
+
+const GAME_ID: MiniGameId = MiniGameId {
+    era: Era::The90s,
+    index: 2,
+};
 
 // ─── Components ───────────────────────────────────────────────────
 
+/// Tags every entity the game spawns so it is despawned on exit.
 #[derive(Component)]
-struct DoomEntity;
+#[require(StateCleanup<PlayingState> = StateCleanup(DepthsOfDoom::PLAYING_STATE))]
+pub struct DoomEntity;
 
 #[derive(Component)]
 struct Monster;
 
 #[derive(Component)]
-struct Wall;
+pub struct Wall;
+
+/// The way down to the next floor.
+#[derive(Component)]
+pub struct Stairs;
+
+/// Belongs to the current floor and goes when the player descends.
+#[derive(Component)]
+struct FloorEntity;
 
 #[derive(Component)]
 struct DungeonHud;
@@ -56,26 +93,62 @@ struct DungeonHud;
 #[derive(Resource, Default)]
 struct DungeonMap {
     tiles: HashMap<(i32, i32), TileType>,
+    /// Current floor, starting at 1.
+    depth: u64,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum TileType {
     Floor,
     Wall,
+    Stairs,
 }
 
 // ─── Setup ─────────────────────────────────────────────────────────
 
-fn setup_dungeon(mut commands: Commands, mut map: ResMut<DungeonMap>) {
+fn setup_dungeon(
+    mut commands: Commands,
+    mut map: ResMut<DungeonMap>,
+    mut rng: ResMut<GameRng>,
+    mut stats: MessageWriter<StatEvent>,
+) {
     info!("Generating Depths of Doom...");
+    generate_floor(&mut commands, &mut map, &mut rng, &mut stats);
+
+    // Spawn Player
+    commands.spawn((
+        DoomEntity,
+        Player,
+        GridPosition::new(START.0, START.1),
+        Health::new(100),
+        Sprite {
+            color: colors::EGA_BRIGHT_CYAN,
+            custom_size: Some(Vec2::new(TILE_SIZE * 0.8, TILE_SIZE * 0.8)),
+            ..default()
+        },
+        Transform::from_xyz(0.0, 0.0, 1.0),
+    ));
+
+    spawn_doom_hud(&mut commands);
+}
+
+/// Build the next floor down: walls, the stairs and its monsters.
+fn generate_floor(
+    commands: &mut Commands,
+    map: &mut DungeonMap,
+    rng: &mut GameRng,
+    stats: &mut MessageWriter<StatEvent>,
+) {
+    map.depth += 1;
+    stats.write(StatEvent::best("depths_of_doom.depth", map.depth));
 
     map.tiles.clear();
 
     // Simple procedural dungeon: random walls
     for x in -20..20 {
         for y in -15..15 {
-            let is_wall =
-                (x == -20 || x == 19 || y == -15 || y == 14) || (rand::random::<f32>() < 0.15);
+            let is_wall = (x == -20 || x == 19 || y == -15 || y == 14)
+                || (rng.procgen().random_bool(0.15) && (x, y) != START);
             let tile = if is_wall {
                 TileType::Wall
             } else {
@@ -88,6 +161,7 @@ fn setup_dungeon(mut commands: Commands, mut map: ResMut<DungeonMap>) {
             if is_wall {
                 commands.spawn((
                     DoomEntity,
+                    FloorEntity,
                     Wall,
                     GridPosition::new(x, y),
                     Sprite {
@@ -100,6 +174,7 @@ fn setup_dungeon(mut commands: Commands, mut map: ResMut<DungeonMap>) {
             } else {
                 commands.spawn((
                     DoomEntity,
+                    FloorEntity,
                     Sprite {
                         color: Color::srgb(0.1, 0.1, 0.1),
                         custom_size: Some(Vec2::new(TILE_SIZE - 1.0, TILE_SIZE - 1.0)),
@@ -111,28 +186,30 @@ fn setup_dungeon(mut commands: Commands, mut map: ResMut<DungeonMap>) {
         }
     }
 
-    // Spawn Player
+    let (sx, sy) = farthest_floor(&map.tiles);
+    map.tiles.insert((sx, sy), TileType::Stairs);
     commands.spawn((
         DoomEntity,
-        Player,
-        GridPosition::new(0, 0),
-        Health::new(100),
+        FloorEntity,
+        Stairs,
+        GridPosition::new(sx, sy),
         Sprite {
-            color: colors::EGA_BRIGHT_CYAN,
-            custom_size: Some(Vec2::new(TILE_SIZE * 0.8, TILE_SIZE * 0.8)),
+            color: colors::EGA_BRIGHT_YELLOW,
+            custom_size: Some(Vec2::new(TILE_SIZE * 0.6, TILE_SIZE * 0.6)),
             ..default()
         },
-        Transform::from_xyz(0.0, 0.0, 1.0),
+        Transform::from_xyz(sx as f32 * TILE_SIZE, sy as f32 * TILE_SIZE, 0.5),
     ));
 
     // Spawn Monsters
-    for i in 0..5 {
-        let mx = (rand::random::<f32>() * 30.0 - 15.0) as i32;
-        let my = (rand::random::<f32>() * 20.0 - 10.0) as i32;
+    for _i in 0..5 {
+        let mx = (rng.procgen().random::<f32>() * 30.0 - 15.0) as i32;
+        let my = (rng.procgen().random::<f32>() * 20.0 - 10.0) as i32;
 
-        if map.tiles.get(&(mx, my)) == Some(&TileType::Floor) {
+        if map.tiles.get(&(mx, my)) == Some(&TileType::Floor) && (mx, my) != START {
             commands.spawn((
                 DoomEntity,
+                FloorEntity,
                 Monster,
                 GridPosition::new(mx, my),
                 Health::new(20),
@@ -145,8 +222,23 @@ fn setup_dungeon(mut commands: Commands, mut map: ResMut<DungeonMap>) {
             ));
         }
     }
+}
 
-    spawn_doom_hud(&mut commands);
+/// The floor tile the longest walk away from `START`, so the stairs are
+/// always reachable.
+fn farthest_floor(tiles: &HashMap<(i32, i32), TileType>) -> (i32, i32) {
+    let mut seen = HashSet::from([START]);
+    let mut queue = VecDeque::from([START]);
+    let mut last = START;
+    while let Some((x, y)) = queue.pop_front() {
+        last = (x, y);
+        for next in [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)] {
+            if tiles.get(&next) == Some(&TileType::Floor) && seen.insert(next) {
+                queue.push_back(next);
+            }
+        }
+    }
+    last
 }
 
 fn spawn_doom_hud(commands: &mut Commands) {
@@ -176,6 +268,7 @@ fn spawn_doom_hud(commands: &mut Commands) {
                 TextColor(colors::EGA_BRIGHT_GREEN),
             ));
             hud.spawn((
+                DoomDepthText,
                 Text::new("DEPTHS OF DOOM — B1"),
                 TextFont {
                     font_size: 14.0,
@@ -189,10 +282,14 @@ fn spawn_doom_hud(commands: &mut Commands) {
 #[derive(Component)]
 struct DoomHpText;
 
+#[derive(Component)]
+struct DoomDepthText;
+
 // ─── Systems ───────────────────────────────────────────────────────
 
+#[allow(clippy::type_complexity)]
 fn handle_input(
-    keyboard: Res<ButtonInput<KeyCode>>,
+    input: Res<GameInput>,
     map: Res<DungeonMap>,
     mut player_query: Query<
         (&mut GridPosition, &mut Transform, &Health),
@@ -203,16 +300,17 @@ fn handle_input(
         (With<Monster>, Without<Player>),
     >,
     mut commands: Commands,
+    mut stats: MessageWriter<StatEvent>,
 ) {
     let mut move_delta = (0, 0);
 
-    if keyboard.just_pressed(KeyCode::ArrowUp) || keyboard.just_pressed(KeyCode::KeyW) {
+    if input.just_up {
         move_delta.1 += 1;
-    } else if keyboard.just_pressed(KeyCode::ArrowDown) || keyboard.just_pressed(KeyCode::KeyS) {
+    } else if input.just_down {
         move_delta.1 -= 1;
-    } else if keyboard.just_pressed(KeyCode::ArrowLeft) || keyboard.just_pressed(KeyCode::KeyA) {
+    } else if input.just_left {
         move_delta.0 -= 1;
-    } else if keyboard.just_pressed(KeyCode::ArrowRight) || keyboard.just_pressed(KeyCode::KeyD) {
+    } else if input.just_right {
         move_delta.0 += 1;
     }
 
@@ -234,13 +332,17 @@ fn handle_input(
             m_hp.damage(10);
             if m_hp.is_dead() {
                 commands.entity(m_entity).despawn();
+                stats.write(StatEvent::add("depths_of_doom.kills", 1));
             }
             return; // Attack ends the turn move
         }
     }
 
     // Check for walls
-    if map.tiles.get(&target_pos) == Some(&TileType::Floor) {
+    if matches!(
+        map.tiles.get(&target_pos),
+        Some(TileType::Floor | TileType::Stairs)
+    ) {
         pos.x = target_pos.0;
         pos.y = target_pos.1;
         transform.translation.x = pos.x as f32 * TILE_SIZE;
@@ -248,10 +350,38 @@ fn handle_input(
     }
 }
 
+/// Standing on the stairs takes the player down to a fresh floor.
+fn descend(
+    mut commands: Commands,
+    mut map: ResMut<DungeonMap>,
+    mut rng: ResMut<GameRng>,
+    mut stats: MessageWriter<StatEvent>,
+    mut player_query: Query<(&mut GridPosition, &mut Transform), With<Player>>,
+    floor: Query<Entity, With<FloorEntity>>,
+) {
+    let Ok((mut pos, mut transform)) = player_query.single_mut() else {
+        return;
+    };
+    if map.tiles.get(&(pos.x, pos.y)) != Some(&TileType::Stairs) {
+        return;
+    }
+
+    for entity in &floor {
+        commands.entity(entity).despawn();
+    }
+    generate_floor(&mut commands, &mut map, &mut rng, &mut stats);
+    info!("Descending to B{}", map.depth);
+
+    *pos = GridPosition::new(START.0, START.1);
+    transform.translation.x = START.0 as f32 * TILE_SIZE;
+    transform.translation.y = START.1 as f32 * TILE_SIZE;
+}
+
+#[allow(clippy::type_complexity)]
 fn monster_ai(
-    player_query: Query<&GridPosition, (With<Player>, Without<Monster>)>,
-    mut monster_query: Query<(&mut GridPosition, &mut Transform), (With<Monster>, Without<Player>)>,
-    map: Res<DungeonMap>,
+    _player_query: Query<&GridPosition, (With<Player>, Without<Monster>)>,
+    _monster_query: Query<(&mut GridPosition, &mut Transform), (With<Monster>, Without<Player>)>,
+    _map: Res<DungeonMap>,
 ) {
     // Basic chase AI if turn-based was implemented with a turn resource.
     // For now, moves randomly on update (simplified).
@@ -259,39 +389,34 @@ fn monster_ai(
 
 fn check_death(
     player_query: Query<&Health, With<Player>>,
-    mut next_state: ResMut<NextState<GameState>>,
+    mut finished: MessageWriter<MiniGameFinished>,
 ) {
-    if let Ok(hp) = player_query.single() {
-        if hp.is_dead() {
-            next_state.set(GameState::Results);
-        }
+    if let Ok(hp) = player_query.single()
+        && hp.is_dead()
+    {
+        finished.write(MiniGameFinished {
+            game: GAME_ID,
+            score: 0,
+            outcome: MiniGameOutcome::Defeat,
+        });
     }
 }
 
+#[allow(clippy::type_complexity)]
 fn update_hud(
     player_query: Query<&Health, With<Player>>,
-    mut hp_text: Query<&mut Text, With<DoomHpText>>,
+    map: Res<DungeonMap>,
+    mut hp_text: Query<&mut Text, (With<DoomHpText>, Without<DoomDepthText>)>,
+    mut depth_text: Query<&mut Text, (With<DoomDepthText>, Without<DoomHpText>)>,
 ) {
     if let Ok(hp) = player_query.single() {
         for mut text in &mut hp_text {
             **text = format!("HP: {}/{}", hp.current, hp.max);
         }
     }
-}
-
-fn handle_pause(
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mut next_state: ResMut<NextState<GameState>>,
-    mut results: ResMut<GameResults>,
-) {
-    if keyboard.just_pressed(KeyCode::Escape) {
-        results.game_name = "Depths of Doom".to_string();
-        next_state.set(GameState::Results);
-    }
-}
-
-fn cleanup_dungeon(mut commands: Commands, query: Query<Entity, With<DoomEntity>>) {
-    for entity in &query {
-        commands.entity(entity).despawn();
+    if map.is_changed() {
+        for mut text in &mut depth_text {
+            **text = format!("DEPTHS OF DOOM — B{}", map.depth);
+        }
     }
 }
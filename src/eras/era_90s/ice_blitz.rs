@@ -1,7 +1,12 @@
-use crate::core::states::{GameState, PlayingState};
-use crate::shared::components::{Player, Score, Velocity};
-use crate::ui::colors;
-use crate::ui::results::GameResults;
+use crate::core::input::GameInput;
+use crate::core::progression::{MiniGameFinished, MiniGameOutcome};
+use crate::core::states::{Era, MiniGameId, PlayingState};
+use crate::core::stats::StatEvent;
+use crate::eras::shared::{MiniGame, MiniGamePlugin};
+use crate::shared::components::{Player, Velocity};
+use crate::ui::{StateCleanup, StateScopedResourceExt, colors};
+use bevy::ecs::schedule::ScheduleConfigs;
+use bevy::ecs::system::ScheduleSystem;
 use bevy::prelude::*;
 
 /// Ice Blitz — inspired by NHL 98 (1997).
@@ -10,25 +15,45 @@ pub struct IceBlitzPlugin;
 
 impl Plugin for IceBlitzPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<Scores>()
-            .add_systems(
-                OnEnter(GameState::Playing),
-                setup_hockey.run_if(in_state(PlayingState::IceBlitz)),
-            )
-            .add_systems(
-                Update,
-                (
-                    player_skating,
-                    ai_behavior,
-                    puck_physics,
-                    stick_handling,
-                    goal_system,
-                    update_hud,
-                    handle_pause,
-                )
-                    .run_if(in_state(PlayingState::IceBlitz)),
-            )
-            .add_systems(OnExit(GameState::Playing), cleanup_hockey);
+        app.add_plugins(MiniGamePlugin::<IceBlitz>::default())
+            .init_state_scoped_resource::<Scores>(IceBlitz::PLAYING_STATE);
+    }
+}
+
+/// Registry entry for Ice Blitz.
+pub struct IceBlitz;
+
+impl MiniGame for IceBlitz {
+    const ID: MiniGameId = GAME_ID;
+    const PLAYING_STATE: PlayingState = PlayingState::IceBlitz;
+    const NAME: &'static str = "Ice Blitz";
+    const DESCRIPTION: &'static str = "Fast-paced top-down arcade ice hockey";
+    const THUMBNAIL: &'static str = "ui/thumbnails/ice_blitz.png";
+    const COMPLETION_THRESHOLD: u64 = 300;
+    /// The rink and its boards, at 1.5 world units per pixel.
+    const VIEW: Vec2 = Vec2::new(960.0, 720.0);
+
+    fn setup() -> ScheduleConfigs<ScheduleSystem> {
+        setup_hockey.into_configs()
+    }
+
+    fn gameplay() -> ScheduleConfigs<ScheduleSystem> {
+        (
+            player_skating,
+            ai_behavior,
+            puck_physics,
+            stick_handling,
+            goal_system,
+        )
+            .into_configs()
+    }
+
+    fn frame() -> ScheduleConfigs<ScheduleSystem> {
+        update_hud.into_configs()
+    }
+
+    fn score(world: &World) -> u64 {
+        world.get_resource::<Scores>().map_or(0, Scores::points)
     }
 }
 
@@ -36,16 +61,32 @@ impl Plugin for IceBlitzPlugin {
 
 const RINK_WIDTH: f32 = 800.0;
 const RINK_HEIGHT: f32 = 500.0;
+#[allow(dead_code)] // The puck isn't speed-capped yet.
 const PUCK_SPEED_MAX: f32 = 600.0;
 const SKATING_ACCEL: f32 = 400.0;
-const SKATING_FRICTION: f32 = 0.98;
+/// Fraction of a skater's speed left after a second of gliding (2% lost
+/// per 60 Hz tick), so friction doesn't depend on the tick rate.
+const SKATING_FRICTION: f32 = 0.2976;
+/// Same for the puck, which slides further.
+const PUCK_FRICTION: f32 = 0.5472;
 const GOAL_SIZE: f32 = 120.0;
+const GOAL_POINTS: u64 = 100;
+/// The first team to score this many goals wins the match.
+const GOALS_TO_WIN: u32 = 3;
+
+const GAME_ID: MiniGameId = MiniGameId {
+    era: Era::The90s,
+    index: 1,
+};
 
 // ─── Components ───────────────────────────────────────────────────
 
+/// Tags every entity the game spawns so it is despawned on exit.
 #[derive(Component)]
-struct IceBlitzEntity;
+#[require(StateCleanup<PlayingState> = StateCleanup(IceBlitz::PLAYING_STATE))]
+pub struct IceBlitzEntity;
 
+#[allow(dead_code)] // Possession is not tracked yet.
 #[derive(Component)]
 struct HockeyPlayer {
     team_id: u8,
@@ -53,11 +94,12 @@ struct HockeyPlayer {
 }
 
 #[derive(Component)]
-struct Puck;
+pub struct Puck;
 
+/// The goal team `team_id` defends.
 #[derive(Component)]
-struct Goal {
-    team_id: u8,
+pub struct Goal {
+    pub team_id: u8,
 }
 
 #[derive(Component)]
@@ -228,33 +270,20 @@ struct TeamScoreText {
 
 fn player_skating(
     time: Res<Time>,
-    keyboard: Res<ButtonInput<KeyCode>>,
+    input: Res<GameInput>,
     mut query: Query<(&mut Velocity, &mut Transform), With<Player>>,
 ) {
     for (mut vel, mut transform) in &mut query {
-        let mut input = Vec2::ZERO;
-        if keyboard.pressed(KeyCode::ArrowUp) || keyboard.pressed(KeyCode::KeyW) {
-            input.y += 1.0;
-        }
-        if keyboard.pressed(KeyCode::ArrowDown) || keyboard.pressed(KeyCode::KeyS) {
-            input.y -= 1.0;
-        }
-        if keyboard.pressed(KeyCode::ArrowLeft) || keyboard.pressed(KeyCode::KeyA) {
-            input.x -= 1.0;
-        }
-        if keyboard.pressed(KeyCode::ArrowRight) || keyboard.pressed(KeyCode::KeyD) {
-            input.x += 1.0;
-        }
-
-        if input.length_squared() > 0.0 {
-            let accel = input.normalize() * SKATING_ACCEL * time.delta_secs();
+        if input.move_direction != Vec2::ZERO {
+            let accel = input.move_direction * SKATING_ACCEL * time.delta_secs();
             vel.x += accel.x;
             vel.y += accel.y;
         }
 
         // Apply friction
-        vel.x *= SKATING_FRICTION;
-        vel.y *= SKATING_FRICTION;
+        let friction = SKATING_FRICTION.powf(time.delta_secs());
+        vel.x *= friction;
+        vel.y *= friction;
 
         transform.translation.x += vel.x * time.delta_secs();
         transform.translation.y += vel.y * time.delta_secs();
@@ -271,6 +300,7 @@ fn player_skating(
     }
 }
 
+#[allow(clippy::type_complexity)]
 fn ai_behavior(
     time: Res<Time>,
     puck_query: Query<&Transform, (With<Puck>, Without<HockeyPlayer>)>,
@@ -289,8 +319,9 @@ fn ai_behavior(
             vel.y += accel.y;
         }
 
-        vel.x *= SKATING_FRICTION;
-        vel.y *= SKATING_FRICTION;
+        let friction = SKATING_FRICTION.powf(time.delta_secs());
+        vel.x *= friction;
+        vel.y *= friction;
 
         transform.translation.x += vel.x * time.delta_secs();
         transform.translation.y += vel.y * time.delta_secs();
@@ -315,21 +346,23 @@ fn puck_physics(
             transform.translation.y = (RINK_HEIGHT / 2.0 - 5.1) * transform.translation.y.signum();
         }
 
-        vel.x *= 0.99; // Less friction for puck
-        vel.y *= 0.99;
+        let friction = PUCK_FRICTION.powf(time.delta_secs());
+        vel.x *= friction;
+        vel.y *= friction;
     }
 }
 
+#[allow(clippy::type_complexity)]
 fn stick_handling(
     mut puck_query: Query<(&mut Velocity, &mut Transform), (With<Puck>, Without<HockeyPlayer>)>,
     mut players_query: Query<(&Transform, &mut HockeyPlayer, &Velocity), Without<Puck>>,
 ) {
-    let (mut puck_vel, mut puck_transform) = match puck_query.single_mut() {
+    let (mut puck_vel, puck_transform) = match puck_query.single_mut() {
         Ok(v) => v,
         Err(_) => return,
     };
 
-    for (player_transform, mut player, player_vel) in &mut players_query {
+    for (player_transform, _player, player_vel) in &mut players_query {
         let dist = (puck_transform.translation - player_transform.translation).length();
         if dist < 30.0 {
             // Stick interaction: snap puck to player or push it
@@ -339,11 +372,17 @@ fn stick_handling(
     }
 }
 
+#[allow(clippy::type_complexity)]
 fn goal_system(
     mut puck_query: Query<(&mut Velocity, &mut Transform), (With<Puck>, Without<Goal>)>,
     goal_query: Query<(&Transform, &Goal), Without<Puck>>,
     mut scores: ResMut<Scores>,
+    mut stats: MessageWriter<StatEvent>,
+    mut finished: MessageWriter<MiniGameFinished>,
 ) {
+    if scores.winner().is_some() {
+        return;
+    }
     let (mut puck_vel, mut puck_transform) = match puck_query.single_mut() {
         Ok(v) => v,
         Err(_) => return,
@@ -359,20 +398,57 @@ fn goal_system(
                 scores.team1 += 1;
             } else {
                 scores.team0 += 1;
+                stats.write(StatEvent::add("ice_blitz.goals", 1));
+                stats.write(StatEvent::best(
+                    "ice_blitz.goals_in_game",
+                    scores.team0 as u64,
+                ));
             }
 
             // Reset puck
             puck_transform.translation = Vec3::new(0.0, 0.0, 2.0);
             puck_vel.x = 0.0;
             puck_vel.y = 0.0;
+
+            if let Some(winner) = scores.winner() {
+                info!("Team {} wins the match!", winner);
+                finished.write(MiniGameFinished {
+                    game: GAME_ID,
+                    score: scores.points(),
+                    outcome: if winner == 0 {
+                        MiniGameOutcome::Victory
+                    } else {
+                        MiniGameOutcome::Defeat
+                    },
+                });
+            }
+            return;
         }
     }
 }
 
 #[derive(Resource, Default)]
-struct Scores {
-    team0: u32,
-    team1: u32,
+pub struct Scores {
+    pub team0: u32,
+    pub team1: u32,
+}
+
+impl Scores {
+    /// The player's score: points for each of the blue team's goals.
+    pub fn points(&self) -> u64 {
+        self.team0 as u64 * GOAL_POINTS
+    }
+
+    /// The team that reached `GOALS_TO_WIN`, once the match is decided.
+    pub fn winner(&self) -> Option<u8> {
+        if self.team0 >= GOALS_TO_WIN {
+            Some(0)
+        } else if self.team1 >= GOALS_TO_WIN {
+            Some(1)
+        } else {
+            None
+        }
+    }
 }
 
 fn update_hud(scores: Res<Scores>, mut query: Query<(&mut Text, &TeamScoreText)>) {
@@ -384,20 +460,3 @@ fn update_hud(scores: Res<Scores>, mut query: Query<(&mut Text, &TeamScoreText)>
         }
     }
 }
-
-fn handle_pause(
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mut next_state: ResMut<NextState<GameState>>,
-    mut results: ResMut<GameResults>,
-) {
-    if keyboard.just_pressed(KeyCode::Escape) {
-        results.game_name = "Ice Blitz".to_string();
-        next_state.set(GameState::Results);
-    }
-}
-
-fn cleanup_hockey(mut commands: Commands, query: Query<Entity, With<IceBlitzEntity>>) {
-    for entity in &query {
-        commands.entity(entity).despawn();
-    }
-}
@@ -1,8 +1,15 @@
-use crate::core::states::{GameState, PlayingState};
-use crate::shared::components::{Health, Player, Score, Velocity};
-use crate::ui::colors;
-use crate::ui::results::GameResults;
+use crate::core::input::{GameAction, GameInput};
+use crate::core::progression::{MiniGameFinished, MiniGameOutcome};
+use crate::core::rng::GameRng;
+use crate::core::states::{Era, MiniGameId, PlayingState};
+use crate::core::stats::StatEvent;
+use crate::eras::shared::{MiniGame, MiniGamePlugin};
+use crate::shared::components::{Health, Player, Velocity};
+use crate::ui::{StateCleanup, StateScopedResourceExt, colors};
+use bevy::ecs::schedule::ScheduleConfigs;
+use bevy::ecs::system::ScheduleSystem;
 use bevy::prelude::*;
+use rand::RngExt;
 use std::collections::VecDeque;
 
 /// Worm Wars — inspired by Worms (1995).
@@ -11,25 +18,51 @@ pub struct WormWarsPlugin;
 
 impl Plugin for WormWarsPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<WormWarsState>()
-            .add_systems(
-                OnEnter(GameState::Playing),
-                setup_worms.run_if(in_state(PlayingState::WormWars)),
-            )
-            .add_systems(
-                Update,
-                (
-                    turn_logic,
-                    worm_movement,
-                    gravity_system,
-                    projectile_system,
-                    explosion_system,
-                    update_hud,
-                    handle_pause,
-                )
-                    .run_if(in_state(PlayingState::WormWars)),
-            )
-            .add_systems(OnExit(GameState::Playing), cleanup_worms);
+        app.add_plugins(MiniGamePlugin::<WormWars>::default())
+            .init_state_scoped_resource::<WormWarsState>(WormWars::PLAYING_STATE);
+    }
+}
+
+/// Registry entry for Worm Wars.
+pub struct WormWars;
+
+impl MiniGame for WormWars {
+    const ID: MiniGameId = GAME_ID;
+    const PLAYING_STATE: PlayingState = PlayingState::WormWars;
+    const NAME: &'static str = "Worm Wars";
+    const DESCRIPTION: &'static str = "Turn-based artillery with destructible terrain";
+    const THUMBNAIL: &'static str = "ui/thumbnails/worm_wars.png";
+    const COMPLETION_THRESHOLD: u64 = 500;
+
+    fn setup() -> ScheduleConfigs<ScheduleSystem> {
+        setup_worms.into_configs()
+    }
+
+    fn gameplay() -> ScheduleConfigs<ScheduleSystem> {
+        (
+            turn_logic,
+            worm_movement,
+            gravity_system,
+            projectile_system,
+            explosion_system,
+            check_victory,
+        )
+            .into_configs()
+    }
+
+    fn frame() -> ScheduleConfigs<ScheduleSystem> {
+        update_hud.into_configs()
+    }
+
+    fn score(world: &World) -> u64 {
+        let Some(mut worms) = world.try_query::<(&Worm, &Health)>() else {
+            return 0;
+        };
+        let survivors = worms
+            .iter(world)
+            .filter(|(worm, health)| worm.team_id == 0 && !health.is_dead())
+            .count();
+        survivors as u64 * SURVIVOR_POINTS
     }
 }
 
@@ -40,22 +73,31 @@ const GRAVITY: f32 = -500.0;
 const WALK_SPEED: f32 = 100.0;
 const JUMP_IMPULSE: f32 = 250.0;
 const TURN_DURATION: f32 = 30.0;
+/// Points for each of the player's worms still alive at the end.
+const SURVIVOR_POINTS: u64 = 250;
+
+const GAME_ID: MiniGameId = MiniGameId {
+    era: Era::The90s,
+    index: 0,
+};
 
 // ─── Components ───────────────────────────────────────────────────
 
+/// Tags every entity the game spawns so it is despawned on exit.
 #[derive(Component)]
-struct WormWarsEntity;
+#[require(StateCleanup<PlayingState> = StateCleanup(WormWars::PLAYING_STATE))]
+pub struct WormWarsEntity;
 
 #[derive(Component)]
 struct Worm {
     team_id: u8,
-    is_active: bool,
 }
 
 #[derive(Component)]
 struct Projectile {
+    /// Worms this close to the impact take `damage`.
     radius: f32,
-    damage: f32,
+    damage: i32,
 }
 
 #[derive(Component)]
@@ -66,6 +108,7 @@ struct WormHud;
 
 // ─── Resources ─────────────────────────────────────────────────────
 
+#[allow(dead_code)]
 #[derive(Resource)]
 struct WormWarsState {
     teams_count: u8,
@@ -74,6 +117,8 @@ struct WormWarsState {
     turn_queue: VecDeque<Entity>,
     is_windy: bool,
     wind_force: f32,
+    /// Set once the match result has been sent.
+    match_over: bool,
 }
 
 impl Default for WormWarsState {
@@ -85,6 +130,7 @@ impl Default for WormWarsState {
             turn_queue: VecDeque::new(),
             is_windy: true,
             wind_force: 50.0,
+            match_over: false,
         }
     }
 }
@@ -98,6 +144,7 @@ fn setup_worms(mut commands: Commands, mut state: ResMut<WormWarsState>) {
     state.active_team = 0;
     state.turn_timer.reset();
     state.turn_queue.clear();
+    state.match_over = false;
 
     // Camera
     // (Already spawned in main menu boot, but individual games might want their own)
@@ -126,10 +173,7 @@ fn setup_worms(mut commands: Commands, mut state: ResMut<WormWarsState>) {
         let worm = commands
             .spawn((
                 WormWarsEntity,
-                Worm {
-                    team_id: team,
-                    is_active: false,
-                },
+                Worm { team_id: team },
                 Health::new(100),
                 Velocity::default(),
                 Sprite {
@@ -144,6 +188,9 @@ fn setup_worms(mut commands: Commands, mut state: ResMut<WormWarsState>) {
                 Transform::from_translation(pos.extend(1.0)),
             ))
             .id();
+        if team == 0 {
+            commands.entity(worm).insert(Player);
+        }
 
         state.turn_queue.push_back(worm);
     }
@@ -220,8 +267,10 @@ struct WindText;
 fn turn_logic(
     time: Res<Time>,
     mut state: ResMut<WormWarsState>,
+    mut rng: ResMut<GameRng>,
     mut commands: Commands,
     active_worm_query: Query<Entity, With<ActiveWorm>>,
+    health_query: Query<&Health, With<Worm>>,
 ) {
     state.turn_timer.tick(time.delta());
 
@@ -231,16 +280,25 @@ fn turn_logic(
             commands.entity(old_active).remove::<ActiveWorm>();
         }
 
-        // Rotate queue
-        if let Some(last) = state.turn_queue.pop_front() {
-            state.turn_queue.push_back(last);
+        // Rotate queue, passing over worms that are out of the match
+        for _ in 0..state.turn_queue.len() {
+            if let Some(last) = state.turn_queue.pop_front() {
+                state.turn_queue.push_back(last);
+            }
+            let next_alive = state
+                .turn_queue
+                .front()
+                .is_some_and(|worm| health_query.get(*worm).is_ok_and(|hp| !hp.is_dead()));
+            if next_alive {
+                break;
+            }
         }
 
         if let Some(next) = state.turn_queue.front() {
             commands.entity(*next).insert(ActiveWorm);
             state.turn_timer.reset();
             state.active_team = 1 - state.active_team; // Toggle for 2 teams
-            state.wind_force = (rand::random::<f32>() - 0.5) * 100.0;
+            state.wind_force = rng.gameplay().random_range(-50.0..50.0);
             info!("Turn switched to Team {}", state.active_team);
         }
     }
@@ -248,21 +306,15 @@ fn turn_logic(
 
 fn worm_movement(
     time: Res<Time>,
-    keyboard: Res<ButtonInput<KeyCode>>,
+    input: Res<GameInput>,
     mut query: Query<(&mut Transform, &mut Velocity), With<ActiveWorm>>,
 ) {
     for (mut transform, mut vel) in &mut query {
-        let mut move_dir = 0.0;
-        if keyboard.pressed(KeyCode::ArrowLeft) || keyboard.pressed(KeyCode::KeyA) {
-            move_dir -= 1.0;
-        }
-        if keyboard.pressed(KeyCode::ArrowRight) || keyboard.pressed(KeyCode::KeyD) {
-            move_dir += 1.0;
-        }
+        let move_dir = input.horizontal();
 
         transform.translation.x += move_dir * WALK_SPEED * time.delta_secs();
 
-        if keyboard.just_pressed(KeyCode::ArrowUp) || keyboard.just_pressed(KeyCode::KeyW) {
+        if input.just_pressed(GameAction::Jump) {
             // Jump if near ground
             if transform.translation.y < -190.0 {
                 vel.y = JUMP_IMPULSE;
@@ -289,45 +341,61 @@ fn gravity_system(
 
 fn projectile_system(
     mut commands: Commands,
-    keyboard: Res<ButtonInput<KeyCode>>,
+    input: Res<GameInput>,
     active_worm_query: Query<&Transform, (With<ActiveWorm>, Without<Projectile>)>,
-    mut projectile_query: Query<
-        (Entity, &mut Transform, &mut Velocity, &Projectile),
-        Without<ActiveWorm>,
-    >,
+    mut projectile_query: Query<(Entity, &Transform, &mut Velocity, &Projectile)>,
+    mut worm_query: Query<(Entity, &Transform, &mut Health, &mut Visibility), With<Worm>>,
     time: Res<Time>,
     state: Res<WormWarsState>,
 ) {
     // Fire bazooka
-    if keyboard.just_pressed(KeyCode::Space) {
-        if let Ok(worm_transform) = active_worm_query.single() {
-            commands.spawn((
-                WormWarsEntity,
-                Projectile {
-                    radius: 5.0,
-                    damage: 50.0,
-                },
-                Velocity::new(300.0, 300.0), // Hardcoded arc for now
-                Sprite {
-                    color: colors::EGA_BRIGHT_WHITE,
-                    custom_size: Some(Vec2::new(8.0, 8.0)),
-                    ..default()
-                },
-                Transform::from_translation(worm_transform.translation + Vec3::new(0.0, 20.0, 0.0)),
-            ));
-        }
+    if input.just_pressed(GameAction::Fire)
+        && let Ok(worm_transform) = active_worm_query.single()
+    {
+        commands.spawn((
+            WormWarsEntity,
+            Projectile {
+                radius: 40.0,
+                damage: 50,
+            },
+            Velocity::new(300.0, 300.0), // Hardcoded arc for now
+            Sprite {
+                color: colors::EGA_BRIGHT_WHITE,
+                custom_size: Some(Vec2::new(8.0, 8.0)),
+                ..default()
+            },
+            Transform::from_translation(worm_transform.translation + Vec3::new(0.0, 20.0, 0.0)),
+        ));
     }
 
     // Update projectiles
-    for (entity, mut transform, mut vel, _proj) in &mut projectile_query {
+    for (entity, transform, mut vel, proj) in &mut projectile_query {
         // Wind affect
         vel.x += state.wind_force * time.delta_secs();
 
-        // Collision check
-        if transform.translation.y < -200.0 {
-            info!("Projectile hit ground!");
-            commands.entity(entity).despawn();
-            // TODO: Trigger explosion
+        // Collision check: the ground or any worm sets it off
+        let impact = transform.translation.truncate();
+        let hit_worm = worm_query.iter().any(|(_, worm, health, _)| {
+            !health.is_dead() && worm.translation.truncate().distance(impact) < WORM_SIZE
+        });
+        if transform.translation.y > -200.0 && !hit_worm {
+            continue;
+        }
+
+        info!("Projectile exploded!");
+        commands.entity(entity).despawn();
+        for (worm, worm_transform, mut health, mut visibility) in &mut worm_query {
+            if health.is_dead()
+                || worm_transform.translation.truncate().distance(impact) > proj.radius
+            {
+                continue;
+            }
+            health.damage(proj.damage);
+            if health.is_dead() {
+                info!("A worm is out of the match!");
+                *visibility = Visibility::Hidden;
+                commands.entity(worm).remove::<ActiveWorm>();
+            }
         }
     }
 }
@@ -336,6 +404,44 @@ fn explosion_system() {
     // Placeholder for destructible terrain updates
 }
 
+/// End the match once a team has no worms left standing.
+fn check_victory(
+    worm_query: Query<(&Worm, &Health)>,
+    mut state: ResMut<WormWarsState>,
+    mut finished: MessageWriter<MiniGameFinished>,
+    mut stats: MessageWriter<StatEvent>,
+) {
+    if state.match_over {
+        return;
+    }
+    let (mut spawned, mut alive) = ([0u64; 2], [0u64; 2]);
+    for (worm, health) in &worm_query {
+        let team = usize::from(worm.team_id.min(1));
+        spawned[team] += 1;
+        if !health.is_dead() {
+            alive[team] += 1;
+        }
+    }
+    if spawned.contains(&0) || !alive.contains(&0) {
+        return;
+    }
+    state.match_over = true;
+
+    let outcome = if alive[1] == 0 {
+        if alive[0] == spawned[0] {
+            stats.write(StatEvent::add("worm_wars.flawless_wins", 1));
+        }
+        MiniGameOutcome::Victory
+    } else {
+        MiniGameOutcome::Defeat
+    };
+    finished.write(MiniGameFinished {
+        game: GAME_ID,
+        score: alive[0] * SURVIVOR_POINTS,
+        outcome,
+    });
+}
+
 fn update_hud(
     state: Res<WormWarsState>,
     mut timer_query: Query<&mut Text, (With<TurnTimerText>, Without<WindText>)>,
@@ -348,20 +454,3 @@ fn update_hud(
         **text = format!("Wind: {:.0}", state.wind_force);
     }
 }
-
-fn handle_pause(
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mut next_state: ResMut<NextState<GameState>>,
-    mut results: ResMut<GameResults>,
-) {
-    if keyboard.just_pressed(KeyCode::Escape) {
-        results.game_name = "Worm Wars".to_string();
-        next_state.set(GameState::Results);
-    }
-}
-
-fn cleanup_worms(mut commands: Commands, query: Query<Entity, With<WormWarsEntity>>) {
-    for entity in &query {
-        commands.entity(entity).despawn();
-    }
-}
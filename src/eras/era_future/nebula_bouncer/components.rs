@@ -1,20 +1,33 @@
 use avian2d::prelude::*;
 use bevy::prelude::*;
 
+use super::NebulaBouncer;
+use crate::core::states::PlayingState;
+use crate::eras::shared::MiniGame;
+use crate::ui::StateCleanup;
+
 #[derive(Component, Reflect, Default)]
 #[reflect(Component)]
+#[require(TransformInterpolation)]
 pub struct KineticOrb {
     pub active: bool,
     pub damage: f32,
     pub bounces_remaining: u32,
 }
 
+/// Tags every Nebula Bouncer entity so it is despawned on exit.
+#[derive(Component, Default)]
+#[require(StateCleanup<PlayingState> = StateCleanup(NebulaBouncer::PLAYING_STATE))]
+pub struct NebulaEntity;
+
 #[derive(Component, Reflect, Default)]
 #[reflect(Component)]
+#[require(TransformInterpolation)]
 pub struct PlayerShip;
 
 #[derive(Component, Reflect, Default)]
 #[reflect(Component)]
+#[require(TransformInterpolation)]
 pub struct Enemy;
 
 #[derive(Component, Reflect, Default)]
@@ -1,4 +1,6 @@
 use avian2d::prelude::*;
+use bevy::ecs::schedule::ScheduleConfigs;
+use bevy::ecs::system::ScheduleSystem;
 use bevy::prelude::*;
 
 pub mod components;
@@ -11,20 +13,21 @@ use procgen::*;
 use resources::*;
 use systems::*;
 
-use crate::core::states::PlayingState;
+use crate::core::states::{Era, MiniGameId, PlayingState};
+use crate::eras::shared::{MiniGame, MiniGamePlugin};
+use crate::shared::components::Score;
+use crate::ui::StateScopedResourceExt;
 
 /// Scaffold plugin for Nebula Bouncer in the Future era.
 pub struct NebulaBouncerPlugin;
 
 impl Plugin for NebulaBouncerPlugin {
     fn build(&self, app: &mut App) {
-        // Add Avian Physics.
-        // Note: This adds physics globally. If we need to isolate it, we might need
-        // to pause physics when not in NebulaBouncer state.
-        app.add_plugins(PhysicsPlugins::default());
+        app.add_plugins(MiniGamePlugin::<NebulaBouncer>::default());
+
         // Optional: Debug plugin for development
         #[cfg(debug_assertions)]
-        app.add_plugins(PhysicsDebugPlugin::default());
+        app.add_plugins(PhysicsDebugPlugin);
 
         // Register components for reflection
         app.register_type::<KineticOrb>()
@@ -35,43 +38,68 @@ impl Plugin for NebulaBouncerPlugin {
         // Initialize resources
         app.insert_resource(KineticOrbPool::new(KineticOrbPool::DEFAULT_CAPACITY))
             .insert_resource(Gravity(Vec2::ZERO)) // ensure 2D gravity is zero
-            .insert_resource(ChunkLibrary::default())
-            .insert_resource(ProcGenState::default())
-            .init_resource::<HitStop>();
+            .init_state_scoped_resource::<ChunkLibrary>(NebulaBouncer::PLAYING_STATE)
+            .init_state_scoped_resource::<ProcGenState>(NebulaBouncer::PLAYING_STATE)
+            .init_state_scoped_resource::<HitStop>(NebulaBouncer::PLAYING_STATE)
+            .init_state_scoped_resource::<Score>(NebulaBouncer::PLAYING_STATE);
 
         app.register_type::<ChunkLibrary>()
             .register_type::<ProcGenState>();
 
-        // Add systems
-        app.add_systems(
-            OnEnter(PlayingState::NebulaBouncer),
-            (setup_nebula_bouncer, spawn_orb_pool),
-        );
-
-        app.add_systems(
-            Update,
-            (
-                attach_screen_shake_to_cameras,
-                debug_telemetry_hotkey,
-                handle_orb_collisions,
-                systems::update_level_scrolling,
-                player_movement,
-                orient_player_to_cursor,
-                player_shoot,
-                orient_orbs_to_velocity,
-                update_trails,
-                apply_shake,
-                update_hit_stop,
-            )
-                .run_if(in_state(PlayingState::NebulaBouncer)),
-        );
-
-        app.add_systems(
-            OnExit(PlayingState::NebulaBouncer),
-            (cleanup_orb_pool, cleanup_camera_shake),
-        );
-
         // Add pre-solver hook if needed
         // app.add_systems(PostProcessCollisions, collision_hook_system.run_if(in_state(PlayingState::NebulaBouncer)));
     }
 }
+
+/// Registry entry for Nebula Bouncer.
+pub struct NebulaBouncer;
+
+impl MiniGame for NebulaBouncer {
+    const ID: MiniGameId = MiniGameId {
+        era: Era::Future,
+        index: 0,
+    };
+    const PLAYING_STATE: PlayingState = PlayingState::NebulaBouncer;
+    const NAME: &'static str = "Nebula Bouncer";
+    const DESCRIPTION: &'static str = "Ricochet-driven sci-fi shooter with buildcrafting";
+    const THUMBNAIL: &'static str = "ui/thumbnails/nebula_bouncer.png";
+    const COMPLETION_THRESHOLD: u64 = 1000;
+
+    fn setup() -> ScheduleConfigs<ScheduleSystem> {
+        (setup_nebula_bouncer, spawn_orb_pool).into_configs()
+    }
+
+    fn gameplay() -> ScheduleConfigs<ScheduleSystem> {
+        (
+            handle_orb_collisions,
+            ship_collisions,
+            systems::update_level_scrolling,
+            player_movement,
+            orient_player_to_cursor,
+            player_shoot,
+            orient_orbs_to_velocity,
+        )
+            .into_configs()
+    }
+
+    fn frame() -> ScheduleConfigs<ScheduleSystem> {
+        // Hit stop freezes virtual time, and with it the fixed ticks, so it
+        // has to count down per frame on the real clock.
+        (
+            attach_screen_shake_to_cameras,
+            debug_telemetry_hotkey,
+            update_trails,
+            apply_shake,
+            update_hit_stop,
+        )
+            .into_configs()
+    }
+
+    fn teardown() -> ScheduleConfigs<ScheduleSystem> {
+        (cleanup_orb_pool, cleanup_camera_shake).into_configs()
+    }
+
+    fn score(world: &World) -> u64 {
+        world.get_resource::<Score>().map_or(0, |score| score.value)
+    }
+}
@@ -1,15 +1,30 @@
+use crate::core::input::{GameAction, GameInput};
+use crate::core::progression::{MiniGameFinished, MiniGameOutcome};
+use crate::core::rng::GameRng;
+use crate::core::settings::UserSettings;
+use crate::core::stats::StatEvent;
+use crate::effects::virtual_screen::{ScreenCamera, VirtualScreen, cursor_to_world};
+use crate::eras::era_future::nebula_bouncer::NebulaBouncer;
 use crate::eras::era_future::nebula_bouncer::components::*;
 use crate::eras::era_future::nebula_bouncer::procgen::*;
 use crate::eras::era_future::nebula_bouncer::resources::{HitStop, KineticOrbPool};
-use crate::shared::components::Health;
+use crate::eras::shared::MiniGame;
+use crate::shared::components::{Health, Player, Score};
 use avian2d::prelude::*;
 use bevy::ecs::message::MessageReader;
 use bevy::prelude::*;
-// use rand::prelude::*; // Use explicit random calls
+use rand::RngExt;
 
 const SHIP_FORWARD_OFFSET_RADIANS: f32 = -std::f32::consts::FRAC_PI_2;
 const ORB_FORWARD_OFFSET_RADIANS: f32 = -std::f32::consts::FRAC_PI_2;
 const TELEMETRY_COOLDOWN_SECS: f32 = 0.25;
+/// Wall and enemy hits an orb survives before returning to the pool.
+const ORB_BOUNCES: u32 = 3;
+const SHIP_HEALTH: i32 = 100;
+/// Damage the ship takes when an enemy rams it.
+const RAM_DAMAGE: i32 = 25;
+/// Points for each enemy destroyed.
+const KILL_POINTS: u64 = 100;
 
 fn facing_angle(direction: Vec2, forward_offset: f32) -> Option<f32> {
     if direction.length_squared() <= f32::EPSILON {
@@ -23,6 +38,7 @@ pub fn setup_nebula_bouncer(
     mut commands: Commands,
     mut library: ResMut<ChunkLibrary>,
     mut procgen_state: ResMut<ProcGenState>,
+    mut rng: ResMut<GameRng>,
 ) {
     info!("Nebula Bouncer scaffold loaded (Avian 2D integrated).");
     // Ensure gravity is zero for top-down physics
@@ -30,7 +46,9 @@ pub fn setup_nebula_bouncer(
 
     // Spawn Player
     commands.spawn((
+        NebulaEntity,
         PlayerShip,
+        Player,
         RigidBody::Dynamic,
         Collider::circle(15.0),
         LinearVelocity::ZERO,
@@ -40,6 +58,8 @@ pub fn setup_nebula_bouncer(
             GameLayer::Player,
             [GameLayer::Enemy, GameLayer::Wall, GameLayer::Projectile],
         ),
+        CollisionEventsEnabled,
+        Health::new(SHIP_HEALTH),
         Restitution::new(0.5),
         Friction::new(0.1),
         Sprite {
@@ -224,7 +244,7 @@ pub fn setup_nebula_bouncer(
     procgen_state.chunks_in_current_pacing = 0;
 
     // Spawn first chunk
-    spawn_next_chunk(&mut commands, &mut procgen_state, &*library);
+    spawn_next_chunk(&mut commands, &mut procgen_state, &library, &mut rng);
 }
 
 pub fn spawn_orb_pool(mut commands: Commands, mut orb_pool: ResMut<KineticOrbPool>) {
@@ -235,6 +255,7 @@ pub fn spawn_orb_pool(mut commands: Commands, mut orb_pool: ResMut<KineticOrbPoo
         for _ in 0..needed {
             let id = commands
                 .spawn((
+                    NebulaEntity,
                     KineticOrb::default(),
                     RigidBody::Dynamic,
                     Collider::circle(5.0),
@@ -267,19 +288,9 @@ pub fn spawn_orb_pool(mut commands: Commands, mut orb_pool: ResMut<KineticOrbPoo
     }
 }
 
-pub fn cleanup_orb_pool(
-    mut commands: Commands,
-    mut orb_pool: ResMut<KineticOrbPool>,
-    q_orbs: Query<Entity, With<KineticOrb>>,
-    q_player: Query<Entity, With<PlayerShip>>,
-) {
-    // Optional: Despawn all orbs on exit to clean up memory
-    for entity in q_orbs.iter() {
-        commands.entity(entity).despawn();
-    }
-    for entity in q_player.iter() {
-        commands.entity(entity).despawn();
-    }
+/// The pooled orbs are despawned with the rest of the game's entities,
+/// so the pool only needs to forget them.
+pub fn cleanup_orb_pool(mut orb_pool: ResMut<KineticOrbPool>) {
     orb_pool.inactive.clear();
     orb_pool.active_count = 0;
     info!("Cleaned up Nebula Bouncer entities");
@@ -297,15 +308,17 @@ pub fn cleanup_camera_shake(
     }
 }
 
+#[allow(clippy::type_complexity)]
 pub fn attach_screen_shake_to_cameras(
     mut commands: Commands,
-    q_cameras: Query<Entity, (With<Camera>, Without<ScreenShake>)>,
+    q_cameras: Query<Entity, (With<Camera>, Without<ScreenShake>, Without<ScreenCamera>)>,
 ) {
     for entity in &q_cameras {
         commands.entity(entity).insert(ScreenShake::default());
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn handle_orb_collisions(
     mut commands: Commands,
     mut collision_events: MessageReader<CollisionStart>,
@@ -314,6 +327,8 @@ pub fn handle_orb_collisions(
     mut shake: Query<&mut ScreenShake>,
     mut hit_stop: ResMut<HitStop>,
     mut enemies: Query<(Entity, &mut Health), With<Enemy>>,
+    mut score: ResMut<Score>,
+    mut stats: MessageWriter<StatEvent>,
 ) {
     for event in collision_events.read() {
         let e1 = event.collider1;
@@ -326,52 +341,94 @@ pub fn handle_orb_collisions(
             (None, None)
         };
 
-        if let Some(entity) = orb_entity {
-            if let Ok((e, mut orb)) = orbs.get_mut(entity) {
-                // Determine what we hit
-                let other = if entity == e1 { e2 } else { e1 };
-
-                // Check if hit enemy
-                if let Ok((enemy_entity, mut hp)) = enemies.get_mut(other) {
-                    hp.damage(orb.damage as i32);
-
-                    // Trigger Hit Feedback
-                    hit_stop.timer = (orb.damage * 0.005).clamp(0.05, 0.2);
-                    if let Some(mut s) = shake.iter_mut().next() {
-                        s.intensity += orb.damage * 3.0;
-                        s.decay = 8.0;
-                    }
-
-                    if hp.is_dead() {
-                        commands.entity(enemy_entity).despawn();
-                        // Optional: Spawn explosion or particles here
-                        // commands.spawn(ExplosionBundle::new(transform...));
-                    }
-                } else {
-                    // Hit something else (Wall?)
-                    // Minor shake for wall hits
-                    if let Some(mut s) = shake.iter_mut().next() {
-                        s.intensity += 2.0;
-                        s.decay = 10.0;
-                    }
+        if let Some(entity) = orb_entity
+            && let Ok((e, mut orb)) = orbs.get_mut(entity)
+        {
+            // Determine what we hit
+            let other = if entity == e1 { e2 } else { e1 };
+
+            // Check if hit enemy
+            if let Ok((enemy_entity, mut hp)) = enemies.get_mut(other) {
+                hp.damage(orb.damage as i32);
+
+                // Trigger Hit Feedback
+                hit_stop.timer = (orb.damage * 0.005).clamp(0.05, 0.2);
+                if let Some(mut s) = shake.iter_mut().next() {
+                    s.intensity += orb.damage * 3.0;
+                    s.decay = 8.0;
                 }
 
-                if orb.bounces_remaining > 0 {
-                    orb.bounces_remaining -= 1;
-                    orb.damage *= 1.25; // Ramp up damage
-                } else {
-                    // Deactivate
-                    commands
-                        .entity(e)
-                        // .insert(RigidBody::Disabled)
-                        .insert(Visibility::Hidden)
-                        .remove::<LinearVelocity>();
-
-                    orb.active = false;
-                    orb_pool.inactive.push(e);
-                    orb_pool.active_count = orb_pool.active_count.saturating_sub(1);
+                if hp.is_dead() {
+                    commands.entity(enemy_entity).despawn();
+                    score.add(KILL_POINTS);
+                    let bounces = ORB_BOUNCES.saturating_sub(orb.bounces_remaining);
+                    stats.write(StatEvent::best(
+                        "nebula_bouncer.bounce_kill",
+                        bounces as u64,
+                    ));
+                    // Optional: Spawn explosion or particles here
+                    // commands.spawn(ExplosionBundle::new(transform...));
+                }
+            } else {
+                // Hit something else (Wall?)
+                // Minor shake for wall hits
+                if let Some(mut s) = shake.iter_mut().next() {
+                    s.intensity += 2.0;
+                    s.decay = 10.0;
                 }
             }
+
+            if orb.bounces_remaining > 0 {
+                orb.bounces_remaining -= 1;
+                orb.damage *= 1.25; // Ramp up damage
+            } else {
+                // Deactivate
+                commands
+                    .entity(e)
+                    // .insert(RigidBody::Disabled)
+                    .insert(Visibility::Hidden)
+                    .remove::<LinearVelocity>();
+
+                orb.active = false;
+                orb_pool.inactive.push(e);
+                orb_pool.active_count = orb_pool.active_count.saturating_sub(1);
+            }
+        }
+    }
+}
+
+/// Enemies that ram the ship damage it, and the run ends when its health
+/// runs out.
+pub fn ship_collisions(
+    mut collision_events: MessageReader<CollisionStart>,
+    mut ships: Query<&mut Health, With<PlayerShip>>,
+    enemies: Query<(), With<Enemy>>,
+    score: Res<Score>,
+    mut finished: MessageWriter<MiniGameFinished>,
+) {
+    for event in collision_events.read() {
+        let (ship, other) = if ships.contains(event.collider1) {
+            (event.collider1, event.collider2)
+        } else {
+            (event.collider2, event.collider1)
+        };
+        if !enemies.contains(other) {
+            continue;
+        }
+        let Ok(mut hp) = ships.get_mut(ship) else {
+            continue;
+        };
+        if hp.is_dead() {
+            continue;
+        }
+        hp.damage(RAM_DAMAGE);
+        if hp.is_dead() {
+            info!("Ship destroyed!");
+            finished.write(MiniGameFinished {
+                game: NebulaBouncer::ID,
+                score: score.value,
+                outcome: MiniGameOutcome::Defeat,
+            });
         }
     }
 }
@@ -381,6 +438,7 @@ pub fn update_level_scrolling(
     mut commands: Commands,
     mut procgen_state: ResMut<ProcGenState>,
     library: Res<ChunkLibrary>,
+    mut rng: ResMut<GameRng>,
     mut q_chunks: Query<(Entity, &mut Transform), With<ChunkMember>>,
 ) {
     const SCROLL_SPEED: f32 = 150.0;
@@ -399,11 +457,16 @@ pub fn update_level_scrolling(
 
     // Spawn when needed
     if procgen_state.next_spawn_y < 1200.0 {
-        spawn_next_chunk(&mut commands, &mut procgen_state, &*library);
+        spawn_next_chunk(&mut commands, &mut procgen_state, &library, &mut rng);
     }
 }
 
-pub fn spawn_next_chunk(commands: &mut Commands, state: &mut ProcGenState, library: &ChunkLibrary) {
+pub fn spawn_next_chunk(
+    commands: &mut Commands,
+    state: &mut ProcGenState,
+    library: &ChunkLibrary,
+    rng: &mut GameRng,
+) {
     // Determine next target pacing
     let target_pacing = match state.current_pacing {
         ChunkPacing::Open => {
@@ -433,8 +496,12 @@ pub fn spawn_next_chunk(commands: &mut Commands, state: &mut ProcGenState, libra
         }
     };
 
-    let Some(selected) = select_chunk(library, &state.last_chunk_bottom_profile, target_pacing)
-    else {
+    let Some(selected) = select_chunk(
+        library,
+        &state.last_chunk_bottom_profile,
+        target_pacing,
+        rng.procgen(),
+    ) else {
         warn!(
             "No candidates found for procgen chunk matching profile {:?} with pacing {:?}!",
             state.last_chunk_bottom_profile, target_pacing
@@ -457,6 +524,7 @@ pub fn spawn_next_chunk(commands: &mut Commands, state: &mut ProcGenState, libra
     // Spawn walls
     for wall in &selected.walls {
         commands.spawn((
+            NebulaEntity,
             Wall,
             ChunkMember,
             Sprite {
@@ -479,6 +547,7 @@ pub fn spawn_next_chunk(commands: &mut Commands, state: &mut ProcGenState, libra
         match spawn.spawn_type {
             SpawnType::Enemy => {
                 commands.spawn((
+                    NebulaEntity,
                     Enemy,
                     ChunkMember,
                     Sprite {
@@ -510,30 +579,11 @@ pub fn spawn_next_chunk(commands: &mut Commands, state: &mut ProcGenState, libra
 }
 
 pub fn player_movement(
-    _time: Res<Time>,
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mut query: Query<(&mut LinearVelocity, &mut Transform), With<PlayerShip>>,
+    input: Res<GameInput>,
+    mut query: Query<&mut LinearVelocity, With<PlayerShip>>,
 ) {
-    for (mut velocity, mut _transform) in &mut query {
-        let mut direction = Vec2::ZERO;
-        if keyboard.pressed(KeyCode::KeyW) || keyboard.pressed(KeyCode::ArrowUp) {
-            direction.y += 1.0;
-        }
-        if keyboard.pressed(KeyCode::KeyS) || keyboard.pressed(KeyCode::ArrowDown) {
-            direction.y -= 1.0;
-        }
-        if keyboard.pressed(KeyCode::KeyA) || keyboard.pressed(KeyCode::ArrowLeft) {
-            direction.x -= 1.0;
-        }
-        if keyboard.pressed(KeyCode::KeyD) || keyboard.pressed(KeyCode::ArrowRight) {
-            direction.x += 1.0;
-        }
-
-        if direction != Vec2::ZERO {
-            direction = direction.normalize();
-        }
-
-        velocity.0 = direction * 300.0;
+    for mut velocity in &mut query {
+        velocity.0 = input.move_direction * 300.0;
     }
 }
 
@@ -564,42 +614,55 @@ pub fn debug_telemetry_hotkey(
     *last_log_time = now;
 }
 
+/// Aim direction from the right stick, or towards the cursor when the
+/// stick is centred.
+fn aim_direction(
+    input: &GameInput,
+    camera: Option<(&Camera, &GlobalTransform)>,
+    screen: Option<&VirtualScreen>,
+    from: Vec2,
+) -> Option<Vec2> {
+    if input.aim_stick != Vec2::ZERO {
+        return Some(input.aim_stick.normalize());
+    }
+    let (camera, camera_transform) = camera?;
+    let cursor_pos = cursor_to_world(camera, camera_transform, screen, input.mouse_position)?;
+    Some(cursor_pos - from)
+}
+
 pub fn orient_player_to_cursor(
-    q_window: Query<&Window>,
-    q_camera: Query<(&Camera, &GlobalTransform)>,
+    input: Res<GameInput>,
+    q_camera: Query<(&Camera, &GlobalTransform), Without<ScreenCamera>>,
+    screen: Option<Res<VirtualScreen>>,
     mut q_player: Query<&mut Transform, With<PlayerShip>>,
 ) {
-    let Some(window) = q_window.iter().next() else {
-        return;
-    };
-    let Some((camera, camera_transform)) = q_camera.iter().next() else {
-        return;
-    };
-    let Some(cursor_pos) = window
-        .cursor_position()
-        .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor).ok())
-    else {
-        return;
-    };
-
     for mut player_transform in &mut q_player {
-        let aim_dir = cursor_pos - player_transform.translation.truncate();
+        let Some(aim_dir) = aim_direction(
+            &input,
+            q_camera.iter().next(),
+            screen.as_deref(),
+            player_transform.translation.truncate(),
+        ) else {
+            return;
+        };
         if let Some(angle) = facing_angle(aim_dir, SHIP_FORWARD_OFFSET_RADIANS) {
             player_transform.rotation = Quat::from_rotation_z(angle);
         }
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn player_shoot(
     mut commands: Commands,
-    mouse: Res<ButtonInput<MouseButton>>,
-    q_window: Query<&Window>,
-    q_camera: Query<(&Camera, &GlobalTransform)>,
+    input: Res<GameInput>,
+    q_camera: Query<(&Camera, &GlobalTransform), Without<ScreenCamera>>,
+    screen: Option<Res<VirtualScreen>>,
     q_player: Query<&Transform, With<PlayerShip>>,
     mut orb_pool: ResMut<KineticOrbPool>,
     q_enemies: Query<&Transform, With<Enemy>>,
+    mut stats: MessageWriter<StatEvent>,
 ) {
-    if mouse.just_pressed(MouseButton::Left) {
+    if input.just_pressed(GameAction::Fire) {
         let Some(player_transform) = q_player.iter().next() else {
             return;
         };
@@ -608,21 +671,13 @@ pub fn player_shoot(
         };
 
         // Use iter().next() for safety and simplicity
-        let Some(window) = q_window.iter().next() else {
-            orb_pool.push(orb_entity);
-            return;
-        };
-        let Some((camera, camera_transform)) = q_camera.iter().next() else {
-            orb_pool.push(orb_entity);
-            return;
-        };
-
-        if let Some(cursor_pos) = window
-            .cursor_position()
-            .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor).ok())
-        {
-            let mut direction =
-                (cursor_pos - player_transform.translation.truncate()).normalize_or_zero();
+        if let Some(aim_dir) = aim_direction(
+            &input,
+            q_camera.iter().next(),
+            screen.as_deref(),
+            player_transform.translation.truncate(),
+        ) {
+            let mut direction = aim_dir.normalize_or_zero();
 
             // AIM ASSIST
             let assist_cone = 30.0_f32.to_radians();
@@ -660,9 +715,8 @@ pub fn player_shoot(
                 RigidBody::Dynamic,
                 KineticOrb {
                     active: true,
-                    bounces_remaining: 3,
+                    bounces_remaining: ORB_BOUNCES,
                     damage: 10.0,
-                    ..default()
                 },
                 ProjectileTrail {
                     points: Vec::new(),
@@ -671,6 +725,7 @@ pub fn player_shoot(
                     color: Color::srgb(1.0, 0.5, 0.0),
                 },
             ));
+            stats.write(StatEvent::add("nebula_bouncer.orbs_fired", 1));
         } else {
             orb_pool.push(orb_entity);
         }
@@ -718,6 +773,8 @@ pub fn update_trails(
 
 pub fn apply_shake(
     time: Res<Time<Real>>,
+    settings: Res<UserSettings>,
+    mut rng: ResMut<GameRng>,
     mut query: Query<(&mut Transform, &mut ScreenShake), With<Camera>>,
 ) {
     let dt = time.delta_secs();
@@ -728,9 +785,10 @@ pub fn apply_shake(
         shake.last_offset = Vec2::ZERO;
 
         if shake.intensity > 0.0 {
+            let strength = shake.intensity * settings.screen_shake;
             let offset = Vec2::new(
-                (rand::random::<f32>() - 0.5) * shake.intensity,
-                (rand::random::<f32>() - 0.5) * shake.intensity,
+                (rng.cosmetic().random::<f32>() - 0.5) * strength,
+                (rng.cosmetic().random::<f32>() - 0.5) * strength,
             );
             transform.translation.x += offset.x;
             transform.translation.y += offset.y;
@@ -1,15 +1,203 @@
-/// Shared traits and utilities used across all era mini-games.
+//! Shared traits and utilities used across all era mini-games.
+
+use bevy::ecs::schedule::ScheduleConfigs;
+use bevy::ecs::system::ScheduleSystem;
+use bevy::prelude::*;
+use std::marker::PhantomData;
+
+use crate::core::cosmetics::CosmeticSlot;
+use crate::core::progression::{MiniGameFinished, MiniGameOutcome, PlayerProgress};
+use crate::core::registry::RegisterMiniGameExt;
+use crate::core::states::{MiniGameId, PauseState, PlayingState};
+use crate::effects::presentation::EraPresentation;
+use crate::effects::virtual_screen::default_view;
+use crate::shared::components::Player;
+
+/// Lifecycle contract for a mini-game. `MiniGamePlugin<T>` registers the
+/// game in the `GameRegistry` and wires these hooks to its `PlayingState`.
+pub trait MiniGame: Sized + Send + Sync + 'static {
+    /// Stable identifier used for saves and unlocks.
+    const ID: MiniGameId;
+
+    /// Playing sub-state that runs this mini-game.
+    const PLAYING_STATE: PlayingState;
 
-/// Trait that all mini-games should implement for consistent lifecycle.
-pub trait MiniGame {
     /// Human-readable name of the mini-game.
-    fn name(&self) -> &'static str;
+    const NAME: &'static str;
+
+    /// One-line pitch shown on the era select screen.
+    const DESCRIPTION: &'static str;
+
+    /// Asset path of the carousel thumbnail.
+    const THUMBNAIL: &'static str;
 
     /// Score threshold required to "complete" this mini-game.
-    fn completion_threshold(&self) -> u64;
+    const COMPLETION_THRESHOLD: u64;
 
     /// Number of starting lives.
-    fn starting_lives(&self) -> i32 {
-        3
+    const STARTING_LIVES: i32 = 3;
+
+    /// Pixel size the game is rendered at before being scaled up to the
+    /// window. Defaults to the era's resolution.
+    const VIRTUAL_RESOLUTION: UVec2 = EraPresentation::for_era(Self::ID.era).virtual_resolution;
+
+    /// World area the camera shows, which the game is laid out in. Defaults
+    /// to `GAME_VIEW` at the virtual resolution's aspect ratio.
+    const VIEW: Vec2 = default_view(Self::VIRTUAL_RESOLUTION);
+
+    /// Spawns the game world and resets per-run resources.
+    fn setup() -> ScheduleConfigs<ScheduleSystem>;
+
+    /// Gameplay systems, run once per fixed tick in `FixedUpdate` so the
+    /// simulation doesn't depend on frame rate. These only run while
+    /// unpaused.
+    fn gameplay() -> ScheduleConfigs<ScheduleSystem>;
+
+    /// Per-frame systems in `Update`: HUD text, sprite animation, camera
+    /// effects and anything else driven by the real clock rather than the
+    /// simulation. These only run while unpaused.
+    fn frame() -> ScheduleConfigs<ScheduleSystem> {
+        noop.into_configs()
+    }
+
+    /// Cleanup run when the playing state exits, before `StateCleanup`
+    /// entities and state-scoped resources are dropped.
+    fn teardown() -> ScheduleConfigs<ScheduleSystem> {
+        noop.into_configs()
+    }
+
+    /// Runs when the game is paused.
+    fn pause() -> ScheduleConfigs<ScheduleSystem> {
+        noop.into_configs()
+    }
+
+    /// Runs when the game is resumed.
+    fn resume() -> ScheduleConfigs<ScheduleSystem> {
+        noop.into_configs()
+    }
+
+    /// Runs on a `RestartMiniGame` message. By default the playing state is
+    /// re-entered, which runs teardown and then setup.
+    fn restart() -> ScheduleConfigs<ScheduleSystem> {
+        reenter_playing_state::<Self>.into_configs()
+    }
+
+    /// Score of the current run, read when the run ends early.
+    fn score(_world: &World) -> u64 {
+        0
     }
+
+    /// Builds the result sent when the run ends with `outcome`.
+    fn build_result(world: &World, outcome: MiniGameOutcome) -> MiniGameFinished {
+        MiniGameFinished {
+            game: Self::ID,
+            score: Self::score(world),
+            outcome,
+        }
+    }
+}
+
+/// Ask the running mini-game to start over.
+#[derive(Message, Debug, Clone, Copy, Default)]
+pub struct RestartMiniGame;
+
+/// Ask the running mini-game to end the run early. It reports a
+/// `MiniGameOutcome::Quit` result built from its current state.
+#[derive(Message, Debug, Clone, Copy, Default)]
+pub struct QuitMiniGame;
+
+// ─── Plugin ────────────────────────────────────────────────────────
+
+/// Registers a `MiniGame` and schedules its hooks against its playing state.
+pub struct MiniGamePlugin<T: MiniGame>(PhantomData<T>);
+
+impl<T: MiniGame> Default for MiniGamePlugin<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: MiniGame> Plugin for MiniGamePlugin<T> {
+    fn build(&self, app: &mut App) {
+        app.register_mini_game::<T>()
+            .add_message::<RestartMiniGame>()
+            .add_message::<QuitMiniGame>()
+            .add_systems(OnEnter(T::PLAYING_STATE), T::setup())
+            .add_systems(
+                OnExit(T::PLAYING_STATE),
+                (T::teardown(), restore_time_speed),
+            )
+            .add_systems(
+                OnEnter(PauseState::Paused),
+                T::pause().run_if(in_state(T::PLAYING_STATE)),
+            )
+            .add_systems(
+                OnExit(PauseState::Paused),
+                T::resume().run_if(in_state(T::PLAYING_STATE)),
+            )
+            .add_systems(
+                FixedUpdate,
+                T::gameplay().run_if(in_state(T::PLAYING_STATE).and(in_state(PauseState::Running))),
+            )
+            .add_systems(
+                Update,
+                (
+                    T::frame().run_if(in_state(PauseState::Running)),
+                    T::restart().run_if(on_message::<RestartMiniGame>),
+                    report_quit::<T>.run_if(on_message::<QuitMiniGame>),
+                    apply_player_skin::<T>,
+                )
+                    .run_if(in_state(T::PLAYING_STATE)),
+            );
+    }
+}
+
+// ─── Systems ───────────────────────────────────────────────────────
+
+fn noop() {}
+
+/// Put virtual time back to full speed, so slow-motion or hit stop a game
+/// left running can't stall the next one's fixed ticks.
+fn restore_time_speed(mut time: ResMut<Time<Virtual>>) {
+    time.set_relative_speed(1.0);
+}
+
+fn reenter_playing_state<T: MiniGame>(mut next_state: ResMut<NextState<PlayingState>>) {
+    next_state.set(T::PLAYING_STATE);
+}
+
+/// Color of the player skin sold in the shop as cosmetic `id`.
+pub fn skin_tint(id: &str) -> Option<Color> {
+    match id {
+        "skin_tunnel_miner_gold" => Some(Color::srgb(1.0, 0.84, 0.2)),
+        "skin_cosmic_captain_crimson" => Some(Color::srgb(0.9, 0.15, 0.2)),
+        "skin_star_goose_phoenix" => Some(Color::srgb(1.0, 0.5, 0.1)),
+        "skin_worm_wars_toxic" => Some(Color::srgb(0.5, 1.0, 0.2)),
+        "skin_ice_blitz_gold" => Some(Color::srgb(1.0, 0.8, 0.1)),
+        "skin_depths_of_doom_shadow" => Some(Color::srgb(0.45, 0.35, 0.6)),
+        "skin_nebula_bouncer_solar" => Some(Color::srgb(1.0, 0.75, 0.3)),
+        _ => None,
+    }
+}
+
+/// Recolor newly spawned `Player` sprites with the game's equipped skin.
+/// Textured sprites are tinted, flat ones take the color outright.
+fn apply_player_skin<T: MiniGame>(
+    progress: Res<PlayerProgress>,
+    mut players: Query<&mut Sprite, Added<Player>>,
+) {
+    let Some(tint) = progress
+        .equipped(CosmeticSlot::Skin(T::ID))
+        .and_then(|cosmetic| skin_tint(cosmetic.id))
+    else {
+        return;
+    };
+    for mut sprite in &mut players {
+        sprite.color = tint;
+    }
+}
+
+fn report_quit<T: MiniGame>(world: &mut World) {
+    let result = T::build_result(world, MiniGameOutcome::Quit);
+    world.write_message(result);
 }
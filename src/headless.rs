@@ -0,0 +1,309 @@
+//! Headless simulation for integration tests.
+//!
+//! `HeadlessGame` builds the same app as `main`, `DefaultPlugins` and
+//! `RetroGameGamePlugin`, but with no window, no GPU backend and muted
+//! audio, so it works on CI machines without a display. Games launch the way
+//! `--game` and `--seed` launch them, through the screen transition, and
+//! finished runs go on to the results screen as usual. Profiles, settings
+//! and replays are kept in a scratch directory, removed when the game is
+//! dropped. Input is scripted as a set of held `GameAction`s, and every step
+//! advances exactly one fixed tick, so a run is reproducible for a seed.
+
+use bevy::audio::{AudioPlugin, Volume};
+use bevy::ecs::query::QueryFilter;
+use bevy::log::LogPlugin;
+use bevy::prelude::*;
+use bevy::render::RenderPlugin;
+use bevy::render::settings::WgpuSettings;
+use bevy::time::TimeUpdateStrategy;
+use bevy::window::ExitCondition;
+use bevy::winit::WinitPlugin;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::RetroGameGamePlugin;
+use crate::core::input::{GameAction, GameInput, GameInputSystems};
+use crate::core::progression::{DataDir, MiniGameFinished};
+use crate::core::registry::GameRegistry;
+use crate::core::replay::{LastReplay, Replay, ReplayPlayback};
+use crate::core::states::PlayingState;
+use crate::effects::transitions::ScreenTransition;
+use crate::eras::shared::QuitMiniGame;
+use crate::launch::{LaunchOptions, LaunchPlugin};
+use crate::shared::components::Lives;
+use crate::ui::results::GameResults;
+
+/// Seed used by `HeadlessGame::new`.
+pub const DEFAULT_SEED: u64 = 0;
+
+/// Most updates a launch may take to get through its screen transition.
+const LAUNCH_UPDATES: u32 = 600;
+
+/// One mini-game running without a window.
+///
+/// ```no_run
+/// use retro_game_game::core::input::GameAction;
+/// use retro_game_game::core::states::PlayingState;
+/// use retro_game_game::headless::HeadlessGame;
+///
+/// let mut game = HeadlessGame::new(PlayingState::TunnelMiner);
+/// game.hold(&[GameAction::Left]);
+/// game.step(60);
+/// assert!(game.finished().is_empty());
+/// ```
+pub struct HeadlessGame {
+    app: App,
+    game: PlayingState,
+    dir: PathBuf,
+}
+
+/// Actions the script holds, and the input applied on the last tick.
+#[derive(Resource, Default)]
+struct ScriptedInput {
+    held: Vec<GameAction>,
+    last: GameInput,
+}
+
+/// Every run that ended since the game started.
+#[derive(Resource, Default)]
+struct FinishedRuns(Vec<MiniGameFinished>);
+
+impl HeadlessGame {
+    /// Start `game` with `DEFAULT_SEED`.
+    pub fn new(game: PlayingState) -> Self {
+        Self::with_seed(game, DEFAULT_SEED)
+    }
+
+    /// Start `game` with every run seeded from `seed`, as `--seed` does.
+    /// The game's setup has run and the transition into it has finished by
+    /// the time this returns.
+    pub fn with_seed(game: PlayingState, seed: u64) -> Self {
+        let (app, dir) = build_app();
+        let id = app
+            .world()
+            .resource::<GameRegistry>()
+            .by_state(game)
+            .map(|info| info.id);
+        let options = LaunchOptions {
+            game: id,
+            seed: Some(seed),
+            ..default()
+        };
+        Self::launch(app, dir, game, options)
+    }
+
+    /// Watch `replay`, as `--replay` does. Scripted input is ignored while
+    /// it plays; the watched run ends on the results screen, with
+    /// `GameResults::replay` saying how it compared with the recording.
+    pub fn watch(replay: &Replay) -> Self {
+        let (app, dir) = build_app();
+        let game = app
+            .world()
+            .resource::<GameRegistry>()
+            .get(replay.game)
+            .map(|info| info.playing_state)
+            .expect("replay of an unregistered game");
+        let path = dir.join("watch.json");
+        replay.write(&path).expect("could not write the replay");
+        let options = LaunchOptions {
+            replay: Some(path),
+            ..default()
+        };
+        Self::launch(app, dir, game, options)
+    }
+
+    fn launch(mut app: App, dir: PathBuf, game: PlayingState, options: LaunchOptions) -> Self {
+        app.add_plugins(LaunchPlugin(options));
+        // `App::run` would do this; physics builds its schedules here.
+        app.finish();
+        app.cleanup();
+
+        let mut headless = Self { app, game, dir };
+        for _ in 0..LAUNCH_UPDATES {
+            headless.app.update();
+            if headless.is_playing() && !headless.transition_running() {
+                return headless;
+            }
+        }
+        panic!("{game:?} didn't launch");
+    }
+
+    /// Hold exactly `actions` from the next tick on, until changed.
+    pub fn hold(&mut self, actions: &[GameAction]) {
+        self.app.world_mut().resource_mut::<ScriptedInput>().held = actions.to_vec();
+    }
+
+    /// Let go of every action.
+    pub fn release(&mut self) {
+        self.hold(&[]);
+    }
+
+    /// Advance `ticks` fixed ticks.
+    pub fn step(&mut self, ticks: u32) {
+        for _ in 0..ticks {
+            self.app.update();
+        }
+    }
+
+    /// Hold `actions` for a single tick, then release them.
+    pub fn tap(&mut self, actions: &[GameAction]) {
+        self.hold(actions);
+        self.step(1);
+        self.release();
+    }
+
+    /// The running game's score, as it would be reported at the end.
+    pub fn score(&self) -> u64 {
+        let world = self.app.world();
+        world
+            .resource::<GameRegistry>()
+            .by_state(self.game)
+            .map_or(0, |info| (info.score)(world))
+    }
+
+    /// Remaining lives, for games that track them.
+    pub fn lives(&self) -> Option<i32> {
+        self.app
+            .world()
+            .get_resource::<Lives>()
+            .map(|lives| lives.count)
+    }
+
+    /// Runs that have ended, oldest first.
+    pub fn finished(&self) -> &[MiniGameFinished] {
+        &self.app.world().resource::<FinishedRuns>().0
+    }
+
+    /// Number of entities matching the filter `F`.
+    pub fn count<F: QueryFilter>(&mut self) -> usize {
+        let world = self.app.world_mut();
+        world.query_filtered::<(), F>().iter(world).count()
+    }
+
+    /// Entities matching the filter `F`.
+    pub fn entities<F: QueryFilter>(&mut self) -> Vec<Entity> {
+        let world = self.app.world_mut();
+        world.query_filtered::<Entity, F>().iter(world).collect()
+    }
+
+    pub fn world(&self) -> &World {
+        self.app.world()
+    }
+
+    pub fn world_mut(&mut self) -> &mut World {
+        self.app.world_mut()
+    }
+
+    pub fn app_mut(&mut self) -> &mut App {
+        &mut self.app
+    }
+
+    /// Whether the launched game is the running `PlayingState`.
+    pub fn is_playing(&self) -> bool {
+        self.app
+            .world()
+            .get_resource::<State<PlayingState>>()
+            .is_some_and(|state| *state.get() == self.game)
+    }
+
+    /// End the run early, as quitting from the pause menu does.
+    pub fn quit(&mut self) {
+        self.app.world_mut().write_message(QuitMiniGame);
+    }
+
+    /// The recording of the last run that ended.
+    pub fn last_replay(&self) -> Option<&Replay> {
+        self.app
+            .world()
+            .get_resource::<LastReplay>()
+            .map(|LastReplay(replay)| replay)
+    }
+
+    /// What the results screen shows for the last run that ended.
+    pub fn results(&self) -> &GameResults {
+        self.app.world().resource::<GameResults>()
+    }
+
+    fn transition_running(&self) -> bool {
+        self.app.world().resource::<ScreenTransition>().is_running()
+    }
+}
+
+impl Drop for HeadlessGame {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// `main`'s app, without a window, GPU or sound, keeping its files in a
+/// scratch directory. Launch options are left to the caller.
+fn build_app() -> (App, PathBuf) {
+    let dir = scratch_dir();
+    let mut app = App::new();
+    app.add_plugins(
+        DefaultPlugins
+            .build()
+            .disable::<WinitPlugin>()
+            // Tests share a process, and with it the global logger.
+            .disable::<LogPlugin>()
+            .set(WindowPlugin {
+                primary_window: None,
+                exit_condition: ExitCondition::DontExit,
+                ..default()
+            })
+            .set(RenderPlugin {
+                render_creation: WgpuSettings {
+                    backends: None,
+                    ..default()
+                }
+                .into(),
+                ..default()
+            })
+            .set(AudioPlugin {
+                global_volume: GlobalVolume::new(Volume::SILENT),
+                ..default()
+            })
+            .set(AssetPlugin {
+                file_path: format!("{}/assets", env!("CARGO_MANIFEST_DIR")),
+                ..default()
+            }),
+    )
+    .add_plugins(RetroGameGamePlugin)
+    .insert_resource(DataDir(dir.clone()))
+    .insert_resource(TimeUpdateStrategy::FixedTimesteps(1))
+    .init_resource::<ScriptedInput>()
+    .init_resource::<FinishedRuns>()
+    .add_systems(
+        PreUpdate,
+        apply_script
+            .after(GameInputSystems::Read)
+            .before(GameInputSystems::Derive)
+            .run_if(not(resource_exists::<ReplayPlayback>)),
+    )
+    .add_systems(Last, collect_finished_runs);
+    (app, dir)
+}
+
+/// A data directory of its own for each game, so games running in parallel
+/// tests don't share profiles or replays.
+fn scratch_dir() -> PathBuf {
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+    std::env::temp_dir().join(format!(
+        "retrogamegame-headless-{}-{}",
+        std::process::id(),
+        NEXT.fetch_add(1, Ordering::Relaxed)
+    ))
+}
+
+fn apply_script(mut script: ResMut<ScriptedInput>, mut input: ResMut<GameInput>) {
+    let ScriptedInput { held, last } = &mut *script;
+    last.hold(held);
+    *input = last.clone();
+}
+
+fn collect_finished_runs(
+    mut finished: MessageReader<MiniGameFinished>,
+    mut runs: ResMut<FinishedRuns>,
+) {
+    runs.0.extend(finished.read().copied());
+}
@@ -0,0 +1,359 @@
+//! Command-line launch options.
+//!
+//! Lets designers and QA skip the timeline and carousels: jump straight
+//! into a mini-game, pick a profile, fix the seed, watch a replay file or
+//! unlock everything for the session.
+
+use bevy::prelude::*;
+use bevy::window::{MonitorSelection, WindowMode, WindowResolution};
+use std::fmt;
+use std::path::PathBuf;
+
+use crate::core::profiles::ProfileStore;
+use crate::core::progression::PlayerProgress;
+use crate::core::registry::GameRegistry;
+use crate::core::replay::{Replay, ReplayPlayback};
+use crate::core::rng::FixedSeed;
+use crate::core::settings::UserSettings;
+use crate::core::states::{Era, GameState, MiniGameId, PlayingState};
+use crate::ui::era_select::CurrentEra;
+
+/// Help text printed for `--help` and after an invalid option.
+pub const USAGE: &str = "\
+Usage: retro-game-game [OPTIONS]
+
+Options:
+  --game <era>/<index>   Start in a mini-game, e.g. 80s/0 (index is 0-based)
+  --seed <n>             Play every run with this RNG seed
+  --profile <name>       Use this profile, creating it if needed
+  --windowed             Start in a window
+  --fullscreen           Start fullscreen
+  --resolution <w>x<h>   Window size, e.g. 1280x720
+  --replay <file>        Watch a recorded replay
+  --unlock-all           Unlock every era and game for this session
+  --help                 Show this help";
+
+/// Options parsed from the command line.
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct LaunchOptions {
+    pub game: Option<MiniGameId>,
+    pub seed: Option<u64>,
+    pub profile: Option<String>,
+    pub fullscreen: Option<bool>,
+    pub resolution: Option<UVec2>,
+    pub replay: Option<PathBuf>,
+    pub unlock_all: bool,
+}
+
+/// Why the command line couldn't be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LaunchError {
+    /// `--help` was given.
+    Help,
+    /// An option this build doesn't know.
+    UnknownOption(String),
+    /// An option that needs a value was last on the command line.
+    MissingValue(&'static str),
+    /// An option's value couldn't be parsed.
+    InvalidValue { option: &'static str, value: String },
+    /// Two options that can't be used together.
+    Conflict(&'static str, &'static str),
+}
+
+impl fmt::Display for LaunchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LaunchError::Help => f.write_str("help requested"),
+            LaunchError::UnknownOption(option) => write!(f, "unknown option {option:?}"),
+            LaunchError::MissingValue(option) => write!(f, "{option} needs a value"),
+            LaunchError::InvalidValue { option, value } => {
+                write!(f, "invalid value {value:?} for {option}")
+            }
+            LaunchError::Conflict(a, b) => write!(f, "{a} can't be combined with {b}"),
+        }
+    }
+}
+
+impl std::error::Error for LaunchError {}
+
+impl LaunchOptions {
+    /// Parse options, not including the program name. Values may follow as
+    /// the next argument (`--seed 42`) or after `=` (`--seed=42`).
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, LaunchError> {
+        let mut options = Self::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let (name, inline) = match arg.split_once('=') {
+                Some((name, value)) => (name.to_string(), Some(value.to_string())),
+                None => (arg, None),
+            };
+            let mut value = |option: &'static str| {
+                inline
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or(LaunchError::MissingValue(option))
+            };
+            match name.as_str() {
+                "--game" => options.game = Some(parsed("--game", value("--game")?, parse_game)?),
+                "--seed" => {
+                    options.seed = Some(parsed("--seed", value("--seed")?, |v| v.parse().ok())?);
+                }
+                "--profile" => options.profile = Some(value("--profile")?),
+                "--windowed" => options.fullscreen = Some(false),
+                "--fullscreen" => options.fullscreen = Some(true),
+                "--resolution" => {
+                    let resolution = value("--resolution")?;
+                    options.resolution =
+                        Some(parsed("--resolution", resolution, parse_resolution)?);
+                }
+                "--replay" => options.replay = Some(PathBuf::from(value("--replay")?)),
+                "--unlock-all" => options.unlock_all = true,
+                "--help" | "-h" => return Err(LaunchError::Help),
+                _ => return Err(LaunchError::UnknownOption(name)),
+            }
+        }
+        if options.game.is_some() && options.replay.is_some() {
+            return Err(LaunchError::Conflict("--game", "--replay"));
+        }
+        Ok(options)
+    }
+
+    /// The primary window: the saved `settings`, with any window options
+    /// given on the command line taking precedence for this session.
+    pub fn window(&self, settings: &UserSettings) -> Window {
+        let size = self.resolution.unwrap_or(settings.resolution);
+        Window {
+            title: "RetroGameGame".into(),
+            resolution: WindowResolution::new(size.x, size.y),
+            resizable: true,
+            mode: if self.fullscreen.unwrap_or(settings.fullscreen) {
+                WindowMode::BorderlessFullscreen(MonitorSelection::Current)
+            } else {
+                WindowMode::Windowed
+            },
+            ..default()
+        }
+    }
+}
+
+/// Parse `option`'s `value` with `parse`.
+fn parsed<T>(
+    option: &'static str,
+    value: String,
+    parse: impl FnOnce(&str) -> Option<T>,
+) -> Result<T, LaunchError> {
+    parse(&value).ok_or(LaunchError::InvalidValue { option, value })
+}
+
+/// `80s/0`, `1990s/2` or `4/0`: an era by label or timeline position, then
+/// the 0-based game index within it.
+fn parse_game(value: &str) -> Option<MiniGameId> {
+    let (era, index) = value.split_once('/')?;
+    let era = match era.to_ascii_lowercase().as_str() {
+        "80s" | "1980s" => Era::The80s,
+        "90s" | "1990s" => Era::The90s,
+        "00s" | "2000s" => Era::The2000s,
+        "10s" | "2010s" => Era::The2010s,
+        "future" => Era::Future,
+        position => *Era::ALL.get(position.parse::<usize>().ok()?)?,
+    };
+    Some(MiniGameId {
+        era,
+        index: index.parse().ok()?,
+    })
+}
+
+/// `1280x720`.
+fn parse_resolution(value: &str) -> Option<UVec2> {
+    let (width, height) = value.split_once(['x', 'X'])?;
+    let size = UVec2::new(width.parse().ok()?, height.parse().ok()?);
+    (size.x > 0 && size.y > 0).then_some(size)
+}
+
+// ─── Plugin ────────────────────────────────────────────────────────
+
+/// Applies `LaunchOptions` once the rest of the game has started up.
+pub struct LaunchPlugin(pub LaunchOptions);
+
+impl Plugin for LaunchPlugin {
+    fn build(&self, app: &mut App) {
+        if let Some(seed) = self.0.seed {
+            app.insert_resource(FixedSeed(seed));
+        }
+        app.insert_resource(self.0.clone())
+            .add_systems(PostStartup, (use_profile, launch).chain())
+            .add_systems(
+                Update,
+                keep_unlocked.run_if(resource_changed::<PlayerProgress>),
+            );
+    }
+}
+
+/// Switch to the `--profile` profile, creating it if it doesn't exist.
+fn use_profile(
+    options: Res<LaunchOptions>,
+    store: Option<ResMut<ProfileStore>>,
+    mut progress: ResMut<PlayerProgress>,
+) {
+    let (Some(name), Some(mut store)) = (&options.profile, store) else {
+        return;
+    };
+    let id = match store.find_by_name(name).map(|p| p.id) {
+        Some(id) => Ok(id),
+        None => store.create(name),
+    };
+    let loaded = id.and_then(|id| {
+        let loaded = store.load_progress(id)?;
+        store.set_active(id)?;
+        Ok(loaded)
+    });
+    match loaded {
+        Ok(loaded) => {
+            info!("Using profile {:?}", name);
+            *progress = loaded;
+        }
+        Err(err) => error!("Could not use profile {:?}: {}", name, err),
+    }
+}
+
+/// Enter the `--game` mini-game or start the `--replay`. An unknown game or
+/// unreadable replay exits with an error rather than leaving a scripted
+/// launch sitting on the timeline.
+fn launch(
+    mut commands: Commands,
+    options: Res<LaunchOptions>,
+    registry: Res<GameRegistry>,
+    mut current_era: ResMut<CurrentEra>,
+    mut game_state: ResMut<NextState<GameState>>,
+    mut playing_state: ResMut<NextState<PlayingState>>,
+    mut exit: MessageWriter<AppExit>,
+) {
+    let game = if let Some(path) = &options.replay {
+        match Replay::read(path) {
+            Ok(Some(replay)) => {
+                let game = replay.game;
+                commands.insert_resource(ReplayPlayback::new(replay));
+                game
+            }
+            Ok(None) => {
+                error!("Replay {} does not exist", path.display());
+                exit.write(AppExit::error());
+                return;
+            }
+            Err(err) => {
+                error!("Could not read replay {}: {}", path.display(), err);
+                exit.write(AppExit::error());
+                return;
+            }
+        }
+    } else if let Some(game) = options.game {
+        game
+    } else {
+        return;
+    };
+
+    let Some(info) = registry.get(game) else {
+        error!(
+            "No mini-game {}/{} is registered",
+            game.era.label(),
+            game.index
+        );
+        exit.write(AppExit::error());
+        return;
+    };
+    info!("Launching {}", info.name);
+    current_era.era = game.era;
+    playing_state.set(info.playing_state);
+    game_state.set(GameState::Playing);
+}
+
+/// `--unlock-all` lasts the whole session, even across profile switches
+/// that reload `PlayerProgress`.
+fn keep_unlocked(options: Res<LaunchOptions>, mut progress: ResMut<PlayerProgress>) {
+    if options.unlock_all && !progress.unlock_all {
+        progress.unlock_all = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<LaunchOptions, LaunchError> {
+        LaunchOptions::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_parse_every_option() {
+        let options = parse(&[
+            "--game",
+            "90s/1",
+            "--seed=42",
+            "--profile",
+            "QA",
+            "--fullscreen",
+            "--resolution",
+            "1280x720",
+            "--unlock-all",
+        ])
+        .unwrap();
+
+        assert_eq!(
+            options.game,
+            Some(MiniGameId {
+                era: Era::The90s,
+                index: 1
+            })
+        );
+        assert_eq!(options.seed, Some(42));
+        assert_eq!(options.profile.as_deref(), Some("QA"));
+        assert_eq!(options.fullscreen, Some(true));
+        assert_eq!(options.resolution, Some(UVec2::new(1280, 720)));
+        assert!(options.unlock_all);
+        assert_eq!(parse(&[]).unwrap(), LaunchOptions::default());
+    }
+
+    #[test]
+    fn test_game_accepts_era_labels_and_positions() {
+        let future = Some(MiniGameId {
+            era: Era::Future,
+            index: 0,
+        });
+        assert_eq!(parse_game("future/0"), future);
+        assert_eq!(parse_game("4/0"), future);
+        assert_eq!(parse_game("1980s/2").map(|g| g.era), Some(Era::The80s));
+        assert_eq!(parse_game("70s/0"), None);
+        assert_eq!(parse_game("80s"), None);
+    }
+
+    #[test]
+    fn test_invalid_command_lines_are_rejected() {
+        assert_eq!(
+            parse(&["--seed", "soon"]),
+            Err(LaunchError::InvalidValue {
+                option: "--seed",
+                value: "soon".into()
+            })
+        );
+        assert_eq!(
+            parse(&["--profile"]),
+            Err(LaunchError::MissingValue("--profile"))
+        );
+        assert_eq!(
+            parse(&["--resolution", "0x720"]),
+            Err(LaunchError::InvalidValue {
+                option: "--resolution",
+                value: "0x720".into()
+            })
+        );
+        assert_eq!(
+            parse(&["--turbo"]),
+            Err(LaunchError::UnknownOption("--turbo".into()))
+        );
+        assert_eq!(
+            parse(&["--game", "80s/0", "--replay", "run.json"]),
+            Err(LaunchError::Conflict("--game", "--replay"))
+        );
+        assert_eq!(parse(&["--help"]), Err(LaunchError::Help));
+    }
+}
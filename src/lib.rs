@@ -1,6 +1,8 @@
 pub mod core;
 pub mod effects;
 pub mod eras;
+pub mod headless;
+pub mod launch;
 pub mod shared;
 pub mod ui;
 
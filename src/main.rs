@@ -1,21 +1,43 @@
 use bevy::prelude::*;
 use retro_game_game::RetroGameGamePlugin;
+use retro_game_game::core::progression::data_dir;
+use retro_game_game::core::settings::{UserSettings, settings_path};
+use retro_game_game::launch::{LaunchError, LaunchOptions, LaunchPlugin, USAGE};
+
+fn main() -> AppExit {
+    let options = match LaunchOptions::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(LaunchError::Help) => {
+            println!("{}", USAGE);
+            return AppExit::Success;
+        }
+        Err(err) => {
+            eprintln!("{}\n\n{}", err, USAGE);
+            return AppExit::error();
+        }
+    };
+
+    // Read before the app exists so the window opens at the saved size.
+    let settings = match UserSettings::read(&settings_path(&data_dir())) {
+        Ok(settings) => settings.unwrap_or_default(),
+        Err(err) => {
+            eprintln!("Could not load settings, using defaults: {}", err);
+            UserSettings::default()
+        }
+    };
 
-fn main() {
     let asset_root = format!("{}/assets", env!("CARGO_MANIFEST_DIR"));
     println!("Starting RetroGameGame...");
     println!("Asset root: {}", asset_root);
+    if let Some(seed) = options.seed {
+        println!("Using fixed seed {}", seed);
+    }
 
     App::new()
         .add_plugins(
             DefaultPlugins
                 .set(WindowPlugin {
-                    primary_window: Some(Window {
-                        title: "RetroGameGame".into(),
-                        resolution: (1024, 768).into(),
-                        resizable: true,
-                        ..default()
-                    }),
+                    primary_window: Some(options.window(&settings)),
                     ..default()
                 })
                 .set(AssetPlugin {
@@ -23,6 +45,6 @@ fn main() {
                     ..default()
                 }),
         )
-        .add_plugins(RetroGameGamePlugin)
-        .run();
+        .add_plugins((RetroGameGamePlugin, LaunchPlugin(options)))
+        .run()
 }
@@ -1,3 +1,4 @@
+use avian2d::prelude::TransformInterpolation;
 use bevy::prelude::*;
 
 // ─── Core game components shared across mini-games ─────────────────
@@ -75,8 +76,10 @@ impl Lives {
     }
 }
 
-/// Velocity component for simple 2D movement.
+/// Velocity component for simple 2D movement. Moving entities are drawn
+/// interpolated between fixed ticks.
 #[derive(Component, Debug, Clone, Default)]
+#[require(TransformInterpolation)]
 pub struct Velocity {
     pub x: f32,
     pub y: f32,
@@ -1,13 +1,18 @@
+use avian2d::prelude::*;
 use bevy::prelude::*;
 
 use super::components::Velocity;
 
 /// Simple 2D physics plugin — applies velocity to transform.
+///
+/// Also adds Avian, which Nebula Bouncer simulates with. Entities opt in
+/// to `TransformInterpolation` to smooth fixed-tick movement between ticks.
 pub struct PhysicsPlugin;
 
 impl Plugin for PhysicsPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, apply_velocity);
+        app.add_plugins(PhysicsPlugins::default())
+            .add_systems(FixedUpdate, apply_velocity);
     }
 }
 
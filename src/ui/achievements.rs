@@ -0,0 +1,359 @@
+//! Achievement toasts and the achievement gallery.
+//!
+//! Every `AchievementUnlocked` message pops a toast in the top-right corner
+//! that stays up for a few seconds, over menus and mini-games alike. The
+//! gallery, opened from the timeline hub, lists every achievement with
+//! either its unlock or how close the player is.
+
+use bevy::prelude::*;
+
+use super::{StateCleanup, colors};
+use crate::core::achievements::{Achievement, AchievementList, AchievementUnlocked};
+use crate::core::input::GameInput;
+use crate::core::progression::PlayerProgress;
+use crate::core::states::TimelineState;
+
+/// How long a toast stays on screen, in real seconds.
+const TOAST_SECS: f32 = 4.0;
+
+/// Plugin for achievement toasts and the gallery.
+pub struct AchievementsUiPlugin;
+
+impl Plugin for AchievementsUiPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GallerySelection>()
+            .add_systems(Startup, spawn_toast_stack)
+            .add_systems(Update, (show_toasts, expire_toasts))
+            .add_systems(OnEnter(TimelineState::Achievements), setup_gallery)
+            .add_systems(
+                Update,
+                (gallery_mouse, gallery_navigation, update_gallery)
+                    .chain()
+                    .run_if(in_state(TimelineState::Achievements)),
+            );
+    }
+}
+
+// ─── Components ────────────────────────────────────────────────────
+
+#[derive(Component)]
+struct ToastStack;
+
+#[derive(Component)]
+struct Toast {
+    timer: Timer,
+}
+
+#[derive(Component)]
+struct GalleryCount;
+
+#[derive(Component)]
+struct GalleryDetail;
+
+#[derive(Component)]
+struct GalleryRow(usize);
+
+#[derive(Resource, Default)]
+struct GallerySelection(usize);
+
+// ─── Toasts ────────────────────────────────────────────────────────
+
+fn spawn_toast_stack(mut commands: Commands) {
+    commands.spawn((
+        ToastStack,
+        // Above every screen, below the transition wipe.
+        GlobalZIndex(90),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(16.0),
+            right: Val::Px(16.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::End,
+            row_gap: Val::Px(8.0),
+            ..default()
+        },
+        Pickable::IGNORE,
+    ));
+}
+
+fn show_toasts(
+    mut commands: Commands,
+    mut unlocked: MessageReader<AchievementUnlocked>,
+    stack: Query<Entity, With<ToastStack>>,
+) {
+    let Ok(stack) = stack.single() else {
+        unlocked.clear();
+        return;
+    };
+    for AchievementUnlocked(achievement) in unlocked.read() {
+        commands.entity(stack).with_children(|stack| {
+            stack
+                .spawn((
+                    Toast {
+                        timer: Timer::from_seconds(TOAST_SECS, TimerMode::Once),
+                    },
+                    Node {
+                        width: Val::Px(300.0),
+                        flex_direction: FlexDirection::Column,
+                        padding: UiRect::all(Val::Px(12.0)),
+                        border: UiRect::all(Val::Px(2.0)),
+                        row_gap: Val::Px(4.0),
+                        border_radius: BorderRadius::all(Val::Px(8.0)),
+                        ..default()
+                    },
+                    BackgroundColor(colors::PANEL_BG),
+                    BorderColor::all(colors::GOLD),
+                ))
+                .with_children(|toast| {
+                    toast.spawn((
+                        Text::new(format!("🏆 {}", achievement.name)),
+                        TextFont {
+                            font_size: 18.0,
+                            ..default()
+                        },
+                        TextColor(colors::GOLD),
+                    ));
+                    toast.spawn((
+                        Text::new(achievement.description.clone()),
+                        TextFont {
+                            font_size: 13.0,
+                            ..default()
+                        },
+                        TextColor(colors::TEXT_PRIMARY),
+                    ));
+                });
+        });
+    }
+}
+
+/// Toasts run on real time so a pause or hit-stop doesn't hold them up.
+fn expire_toasts(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    mut toasts: Query<(Entity, &mut Toast)>,
+) {
+    for (entity, mut toast) in &mut toasts {
+        if toast.timer.tick(time.delta()).is_finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+// ─── Gallery ───────────────────────────────────────────────────────
+
+fn setup_gallery(
+    mut commands: Commands,
+    list: Res<AchievementList>,
+    progress: Res<PlayerProgress>,
+    mut selection: ResMut<GallerySelection>,
+) {
+    selection.0 = 0;
+
+    commands
+        .spawn((
+            StateCleanup(TimelineState::Achievements),
+            GlobalZIndex(10),
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.85)),
+        ))
+        .with_children(|root| {
+            root.spawn((
+                Node {
+                    width: Val::Px(640.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    padding: UiRect::all(Val::Px(24.0)),
+                    border: UiRect::all(Val::Px(2.0)),
+                    row_gap: Val::Px(12.0),
+                    border_radius: BorderRadius::all(Val::Px(12.0)),
+                    ..default()
+                },
+                BackgroundColor(colors::PANEL_BG),
+                BorderColor::all(colors::PANEL_BORDER),
+            ))
+            .with_children(|panel| {
+                panel.spawn((
+                    Text::new("ACHIEVEMENTS"),
+                    TextFont {
+                        font_size: 36.0,
+                        ..default()
+                    },
+                    TextColor(colors::TEXT_ACCENT),
+                ));
+
+                panel.spawn((
+                    GalleryCount,
+                    Text::new(""),
+                    TextFont {
+                        font_size: 18.0,
+                        ..default()
+                    },
+                    TextColor(colors::GOLD),
+                ));
+
+                panel
+                    .spawn(Node {
+                        width: Val::Percent(100.0),
+                        flex_direction: FlexDirection::Column,
+                        row_gap: Val::Px(4.0),
+                        ..default()
+                    })
+                    .with_children(|rows| {
+                        for (index, achievement) in list.0.iter().enumerate() {
+                            spawn_gallery_row(rows, index, achievement, &progress);
+                        }
+                    });
+
+                panel.spawn((
+                    GalleryDetail,
+                    Text::new(""),
+                    TextFont {
+                        font_size: 14.0,
+                        ..default()
+                    },
+                    TextColor(colors::TEXT_PRIMARY),
+                    TextLayout::new_with_justify(Justify::Center),
+                ));
+
+                panel.spawn((
+                    Text::new("↑↓ select — Esc back"),
+                    TextFont {
+                        font_size: 14.0,
+                        ..default()
+                    },
+                    TextColor(colors::TEXT_SECONDARY),
+                ));
+            });
+        });
+}
+
+fn spawn_gallery_row(
+    rows: &mut ChildSpawnerCommands,
+    index: usize,
+    achievement: &Achievement,
+    progress: &PlayerProgress,
+) {
+    let unlocked = progress.has_achievement(&achievement.id);
+    rows.spawn((
+        GalleryRow(index),
+        Interaction::default(),
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Px(30.0),
+            justify_content: JustifyContent::SpaceBetween,
+            align_items: AlignItems::Center,
+            padding: UiRect::horizontal(Val::Px(12.0)),
+            border: UiRect::all(Val::Px(2.0)),
+            border_radius: BorderRadius::all(Val::Px(4.0)),
+            ..default()
+        },
+        BackgroundColor(colors::BUTTON_NORMAL),
+        BorderColor::all(colors::PANEL_BORDER),
+    ))
+    .with_children(|line| {
+        line.spawn((
+            Text::new(achievement.name.clone()),
+            TextFont {
+                font_size: 16.0,
+                ..default()
+            },
+            TextColor(if unlocked {
+                colors::TEXT_PRIMARY
+            } else {
+                colors::TEXT_SECONDARY
+            }),
+        ));
+        let (status, color) = if unlocked {
+            ("UNLOCKED".to_string(), colors::EGA_BRIGHT_GREEN)
+        } else {
+            let value = progress.stat(&achievement.stat).min(achievement.goal);
+            (
+                format!("{} / {}", value, achievement.goal),
+                colors::TEXT_SECONDARY,
+            )
+        };
+        line.spawn((
+            Text::new(status),
+            TextFont {
+                font_size: 16.0,
+                ..default()
+            },
+            TextColor(color),
+        ));
+    });
+}
+
+/// Hovering a row selects it.
+fn gallery_mouse(
+    rows: Query<(&Interaction, &GalleryRow), Changed<Interaction>>,
+    mut selection: ResMut<GallerySelection>,
+) {
+    for (interaction, row) in &rows {
+        if *interaction != Interaction::None {
+            selection.0 = row.0;
+        }
+    }
+}
+
+/// Up/down select an achievement and cancel goes back to the hub.
+fn gallery_navigation(
+    input: Res<GameInput>,
+    list: Res<AchievementList>,
+    mut selection: ResMut<GallerySelection>,
+    mut next_timeline: ResMut<NextState<TimelineState>>,
+) {
+    let count = list.0.len().max(1);
+    if input.just_up {
+        selection.0 = (selection.0 + count - 1) % count;
+    } else if input.just_down {
+        selection.0 = (selection.0 + 1) % count;
+    } else if input.cancel {
+        next_timeline.set(TimelineState::Hub);
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn update_gallery(
+    selection: Res<GallerySelection>,
+    list: Res<AchievementList>,
+    progress: Res<PlayerProgress>,
+    mut texts: ParamSet<(
+        Query<&mut Text, With<GalleryCount>>,
+        Query<&mut Text, With<GalleryDetail>>,
+    )>,
+    mut rows: Query<(&GalleryRow, &mut BackgroundColor, &mut BorderColor)>,
+    added: Query<(), Added<GalleryDetail>>,
+) {
+    if !selection.is_changed() && added.is_empty() {
+        return;
+    }
+    if let Ok(mut text) = texts.p0().single_mut() {
+        let unlocked = list
+            .0
+            .iter()
+            .filter(|achievement| progress.has_achievement(&achievement.id))
+            .count();
+        **text = format!("{} / {} unlocked", unlocked, list.0.len());
+    }
+    if let Ok(mut text) = texts.p1().single_mut() {
+        **text = list
+            .0
+            .get(selection.0)
+            .map_or(String::new(), |achievement| achievement.description.clone());
+    }
+    for (row, mut bg, mut border) in &mut rows {
+        let (background, edge) = if row.0 == selection.0 {
+            (colors::BUTTON_HOVER, colors::EGA_BRIGHT_CYAN)
+        } else {
+            (colors::BUTTON_NORMAL, colors::PANEL_BORDER)
+        };
+        *bg = BackgroundColor(background);
+        *border = BorderColor::all(edge);
+    }
+}
@@ -0,0 +1,310 @@
+//! Arcade-style attract mode.
+//!
+//! Left alone on the main menu for `ATTRACT_IDLE_SECS`, the game starts a
+//! demo of an unlocked mini-game: the player's latest recorded run of it
+//! when there is one, otherwise a scripted run that wanders about pressing
+//! buttons. Demos play through `ReplayPlayback`, so nothing is recorded or
+//! scored. Any key, click or button press, or `DEMO_SECS` passing, ends the
+//! demo and returns to the menu, where the idle clock starts again and the
+//! next game takes its turn.
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use rand::RngExt;
+use std::time::Duration;
+
+use super::era_select::CurrentEra;
+use super::{StateCleanup, colors};
+use crate::core::input::{GameAction, GameInput};
+use crate::core::progression::{DataDir, MiniGameFinished, PlayerProgress};
+use crate::core::registry::GameRegistry;
+use crate::core::replay::{Replay, ReplayPlayback, replay_path};
+use crate::core::rng::GameRng;
+use crate::core::states::{GameState, MenuState, MiniGameId, PlayingState};
+
+/// Seconds without input on the main menu before a demo starts.
+pub const ATTRACT_IDLE_SECS: f32 = 30.0;
+
+/// Longest a demo runs before going back to the menu.
+pub const DEMO_SECS: f32 = 30.0;
+
+/// Frame length of scripted demos.
+const DEMO_FRAME: Duration = Duration::from_micros(16_667);
+
+/// Plugin for the attract-mode demo loop.
+pub struct AttractPlugin;
+
+impl Plugin for AttractPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AttractMode>()
+            .add_systems(OnEnter(MenuState::Main), reset_idle)
+            .add_systems(
+                Update,
+                wait_for_demo
+                    .run_if(in_state(MenuState::Main).and(not(resource_exists::<DemoRun>))),
+            )
+            .add_systems(OnEnter(GameState::Playing), setup_demo_banner)
+            .add_systems(
+                Update,
+                (watch_demo, blink_demo_banner, end_demo)
+                    .run_if(in_state(GameState::Playing).and(resource_exists::<DemoRun>)),
+            )
+            .add_systems(OnExit(GameState::Playing), clear_demo);
+    }
+}
+
+// ─── Resources ─────────────────────────────────────────────────────
+
+/// Idle time on the main menu and whose demo is next.
+#[derive(Resource, Debug, Default)]
+pub struct AttractMode {
+    idle: f32,
+    /// Index into the unlocked games, wrapping.
+    next: usize,
+}
+
+/// Present while a demo is playing.
+#[derive(Resource, Debug, Default)]
+pub struct DemoRun {
+    elapsed: f32,
+}
+
+/// Raw device buttons, for "press any key".
+#[derive(SystemParam)]
+struct AnyInput<'w, 's> {
+    keys: Res<'w, ButtonInput<KeyCode>>,
+    mouse: Res<'w, ButtonInput<MouseButton>>,
+    gamepads: Query<'w, 's, &'static Gamepad>,
+}
+
+impl AnyInput<'_, '_> {
+    fn just_pressed(&self) -> bool {
+        self.keys.get_just_pressed().next().is_some()
+            || self.mouse.get_just_pressed().next().is_some()
+            || self
+                .gamepads
+                .iter()
+                .any(|pad| pad.get_just_pressed().next().is_some())
+    }
+}
+
+// ─── Components ────────────────────────────────────────────────────
+
+#[derive(Component)]
+struct DemoBanner;
+
+// ─── Demos ─────────────────────────────────────────────────────────
+
+/// A made-up run of `game` for when there is no recording: pick a direction
+/// every half second to second and a half, sometimes tapping the action
+/// buttons on the way. Its seed and moves come from `rng`'s cosmetic
+/// stream, so streams seeded alike script the same run.
+pub fn scripted_demo(game: MiniGameId, rng: &mut GameRng) -> Replay {
+    const DIRECTIONS: [&[GameAction]; 9] = [
+        &[],
+        &[GameAction::Up],
+        &[GameAction::Down],
+        &[GameAction::Left],
+        &[GameAction::Right],
+        &[GameAction::Up, GameAction::Left],
+        &[GameAction::Up, GameAction::Right],
+        &[GameAction::Down, GameAction::Left],
+        &[GameAction::Down, GameAction::Right],
+    ];
+    const BUTTONS: [GameAction; 3] = [GameAction::Fire, GameAction::ActionA, GameAction::Jump];
+
+    let rng = rng.cosmetic();
+    let mut replay = Replay::new(game, rng.random());
+    let mut input = GameInput::default();
+    let frames = (DEMO_SECS / DEMO_FRAME.as_secs_f32()).ceil() as u32 + 60;
+
+    let mut direction: &[GameAction] = &[];
+    let mut tapping = false;
+    let mut frames_left = 0;
+    for frame in 0..frames {
+        if frames_left == 0 {
+            direction = DIRECTIONS[rng.random_range(0..DIRECTIONS.len())];
+            tapping = rng.random_bool(0.6);
+            frames_left = rng.random_range(30..90);
+        }
+        frames_left -= 1;
+
+        let mut held = direction.to_vec();
+        // Buttons go down and up again, so each tap counts as a press.
+        if tapping && frame % 12 < 6 {
+            held.extend(BUTTONS);
+        }
+        input.hold(&held);
+        replay.push(DEMO_FRAME, &input);
+    }
+    replay
+}
+
+// ─── Systems ───────────────────────────────────────────────────────
+
+fn reset_idle(mut attract: ResMut<AttractMode>) {
+    attract.idle = 0.0;
+}
+
+/// Count idle time on the menu and start the next demo when it runs out.
+#[allow(clippy::too_many_arguments)]
+fn wait_for_demo(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    any_input: AnyInput,
+    mut attract: ResMut<AttractMode>,
+    registry: Res<GameRegistry>,
+    progress: Res<PlayerProgress>,
+    dir: Res<DataDir>,
+    mut rng: ResMut<GameRng>,
+    mut current_era: ResMut<CurrentEra>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut playing_state: ResMut<NextState<PlayingState>>,
+) {
+    if any_input.just_pressed() {
+        attract.idle = 0.0;
+        return;
+    }
+    attract.idle += time.delta_secs();
+    if attract.idle < ATTRACT_IDLE_SECS {
+        return;
+    }
+    attract.idle = 0.0;
+
+    let games: Vec<_> = registry
+        .iter()
+        .filter(|game| progress.is_game_unlocked(game.id))
+        .collect();
+    if games.is_empty() {
+        return;
+    }
+    let info = games[attract.next % games.len()];
+    attract.next = attract.next.wrapping_add(1);
+
+    let replay = match Replay::read(&replay_path(&dir.0, info.id)) {
+        Ok(Some(replay)) => replay,
+        Ok(None) => scripted_demo(info.id, &mut rng),
+        Err(err) => {
+            warn!("Could not read the replay of {}: {}", info.name, err);
+            scripted_demo(info.id, &mut rng)
+        }
+    };
+    info!("Attract mode: showing {}", info.name);
+
+    commands.insert_resource(ReplayPlayback::demo(replay));
+    commands.insert_resource(DemoRun::default());
+    current_era.era = info.id.era;
+    playing_state.set(info.playing_state);
+    next_state.set(GameState::Playing);
+}
+
+fn setup_demo_banner(mut commands: Commands, demo: Option<Res<DemoRun>>) {
+    if demo.is_none() {
+        return;
+    }
+    commands
+        .spawn((
+            StateCleanup(GameState::Playing),
+            GlobalZIndex(20),
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                bottom: Val::Px(24.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+        ))
+        .with_children(|banner| {
+            banner.spawn((
+                Text::new("DEMO"),
+                TextFont {
+                    font_size: 32.0,
+                    ..default()
+                },
+                TextColor(colors::EGA_BRIGHT_YELLOW),
+            ));
+            banner.spawn((
+                DemoBanner,
+                Text::new("PRESS ANY KEY"),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(colors::TEXT_PRIMARY),
+            ));
+        });
+}
+
+fn blink_demo_banner(time: Res<Time<Real>>, mut banners: Query<&mut Visibility, With<DemoBanner>>) {
+    let visible = time.elapsed_secs().fract() < 0.6;
+    for mut visibility in &mut banners {
+        visibility.set_if_neq(if visible {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        });
+    }
+}
+
+/// Stop the demo on any input or once it has run long enough. The run then
+/// quits like an exhausted replay.
+fn watch_demo(
+    time: Res<Time<Real>>,
+    any_input: AnyInput,
+    mut demo: ResMut<DemoRun>,
+    playback: Option<ResMut<ReplayPlayback>>,
+) {
+    demo.elapsed += time.delta_secs();
+    if let Some(mut playback) = playback
+        && !playback.is_finished()
+        && (any_input.just_pressed() || demo.elapsed >= DEMO_SECS)
+    {
+        playback.stop();
+    }
+}
+
+/// A finished demo goes back to the menu rather than the results screen.
+fn end_demo(
+    mut finished: MessageReader<MiniGameFinished>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if finished.read().last().is_some() {
+        next_state.set(GameState::Menu);
+    }
+}
+
+fn clear_demo(mut commands: Commands) {
+    commands.remove_resource::<DemoRun>();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::states::Era;
+
+    const GAME: MiniGameId = MiniGameId {
+        era: Era::The80s,
+        index: 0,
+    };
+
+    #[test]
+    fn test_scripted_demo_is_repeatable() {
+        let moves = |replay: &Replay| -> Vec<Vec2> {
+            replay
+                .frames()
+                .map(|(_, input)| input.move_direction)
+                .collect()
+        };
+        let a = scripted_demo(GAME, &mut GameRng::new(9));
+        let b = scripted_demo(GAME, &mut GameRng::new(9));
+
+        assert_eq!(a.seed, b.seed);
+        assert_eq!(moves(&a), moves(&b));
+        assert!(a.len() as f32 * DEMO_FRAME.as_secs_f32() > DEMO_SECS);
+        assert!(
+            a.frames()
+                .any(|(_, input)| input.just_pressed(GameAction::Fire))
+        );
+    }
+}
@@ -0,0 +1,118 @@
+//! Arcade cabinet frames around the mini-game screen.
+//!
+//! A frame equipped from the token shop is drawn as a thick rounded border
+//! hugging the `VirtualScreen`, out in the letterbox, for as long as a
+//! mini-game plays.
+
+use bevy::prelude::*;
+
+use super::StateCleanup;
+use crate::core::cosmetics::CosmeticSlot;
+use crate::core::progression::PlayerProgress;
+use crate::core::states::GameState;
+use crate::effects::virtual_screen::VirtualScreen;
+
+/// Plugin for the cabinet frame cosmetic.
+pub struct CabinetPlugin;
+
+impl Plugin for CabinetPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Playing), spawn_cabinet_frame)
+            .add_systems(
+                Update,
+                layout_cabinet_frame.run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+/// Look of a cabinet frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CabinetFrame {
+    pub color: Color,
+    /// Thin line around the outside edge.
+    pub trim: Color,
+    /// Border width in logical pixels.
+    pub thickness: f32,
+    pub radius: f32,
+}
+
+impl CabinetFrame {
+    /// The frame sold in the shop as cosmetic `id`.
+    pub fn cosmetic(id: &str) -> Option<CabinetFrame> {
+        match id {
+            "frame_woodgrain" => Some(CabinetFrame {
+                color: Color::srgb(0.36, 0.22, 0.12),
+                trim: Color::srgb(0.2, 0.12, 0.06),
+                thickness: 18.0,
+                radius: 6.0,
+            }),
+            "frame_chrome" => Some(CabinetFrame {
+                color: Color::srgb(0.72, 0.74, 0.78),
+                trim: Color::srgb(0.95, 0.95, 1.0),
+                thickness: 12.0,
+                radius: 10.0,
+            }),
+            "frame_neon" => Some(CabinetFrame {
+                color: Color::srgb(0.08, 0.02, 0.1),
+                trim: Color::srgb(1.0, 0.2, 0.9),
+                thickness: 6.0,
+                radius: 14.0,
+            }),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Component)]
+struct CabinetFrameNode {
+    thickness: f32,
+}
+
+fn spawn_cabinet_frame(mut commands: Commands, progress: Res<PlayerProgress>) {
+    let Some(frame) = progress
+        .equipped(CosmeticSlot::CabinetFrame)
+        .and_then(|cosmetic| CabinetFrame::cosmetic(cosmetic.id))
+    else {
+        return;
+    };
+    commands.spawn((
+        CabinetFrameNode {
+            thickness: frame.thickness,
+        },
+        StateCleanup(GameState::Playing),
+        Node {
+            position_type: PositionType::Absolute,
+            border: UiRect::all(Val::Px(frame.thickness)),
+            border_radius: BorderRadius::all(Val::Px(frame.radius)),
+            ..default()
+        },
+        BorderColor::all(frame.color),
+        Outline::new(Val::Px(2.0), Val::ZERO, frame.trim),
+        Visibility::Hidden,
+    ));
+}
+
+/// Keep the frame wrapped around wherever the screen is shown.
+fn layout_cabinet_frame(
+    screen: Option<Res<VirtualScreen>>,
+    mut frames: Query<(&CabinetFrameNode, &mut Node, &mut Visibility)>,
+) {
+    let rect = screen.map_or(Rect::default(), |screen| screen.rect);
+    for (frame, mut node, mut visibility) in &mut frames {
+        if rect.is_empty() {
+            visibility.set_if_neq(Visibility::Hidden);
+            continue;
+        }
+        let outer = rect.inflate(frame.thickness);
+        let placed = (
+            Val::Px(outer.min.x),
+            Val::Px(outer.min.y),
+            Val::Px(outer.width()),
+            Val::Px(outer.height()),
+        );
+        if (node.left, node.top, node.width, node.height) != placed {
+            (node.left, node.top, node.width, node.height) = placed;
+        }
+        visibility.set_if_neq(Visibility::Inherited);
+    }
+}
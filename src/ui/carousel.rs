@@ -1,4 +1,6 @@
-use crate::core::states::GameState;
+use crate::core::registry::GameRegistry;
+use crate::core::states::{GameState, MenuState};
+use crate::ui::StateCleanup;
 use bevy::prelude::*;
 
 pub struct CarouselPlugin;
@@ -9,9 +11,12 @@ impl Plugin for CarouselPlugin {
             .add_systems(OnEnter(GameState::Menu), setup_carousel)
             .add_systems(
                 Update,
-                (carousel_input, animate_carousel).run_if(in_state(GameState::Menu)),
-            )
-            .add_systems(OnExit(GameState::Menu), cleanup_carousel);
+                (
+                    carousel_input.run_if(in_state(MenuState::Main)),
+                    animate_carousel,
+                )
+                    .run_if(in_state(GameState::Menu)),
+            );
     }
 }
 
@@ -33,49 +38,24 @@ pub struct CarouselItem {
     pub index: usize,
 }
 
-#[derive(Component)]
-pub struct CarouselRoot;
-
 fn setup_carousel(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
+    registry: Res<GameRegistry>,
     mut state: ResMut<CarouselState>,
 ) {
-    // Define items
-    state.items = vec![
-        // 80s
-        CarouselItemData {
-            title: "Tunnel Miner".into(),
-            image_path: "ui/thumbnails/tunnel_miner.png".into(),
-            era: "1980s DOSE".into(),
-        },
-        CarouselItemData {
-            title: "Cosmic Captain".into(),
-            image_path: "ui/thumbnails/cosmic_captain.png".into(),
-            era: "1980s DOSE".into(),
-        },
-        CarouselItemData {
-            title: "Star Goose".into(),
-            image_path: "ui/thumbnails/star_goose.png".into(),
-            era: "1980s DOSE".into(),
-        },
-        // 90s
-        CarouselItemData {
-            title: "Worm Wars".into(),
-            image_path: "ui/thumbnails/worm_wars.png".into(),
-            era: "1990s GOLDEN AGE".into(),
-        },
-        CarouselItemData {
-            title: "Ice Blitz".into(),
-            image_path: "ui/thumbnails/ice_blitz.png".into(),
-            era: "1990s GOLDEN AGE".into(),
-        },
-        CarouselItemData {
-            title: "Depths of Doom".into(),
-            image_path: "ui/thumbnails/depths_of_doom.png".into(),
-            era: "1990s GOLDEN AGE".into(),
-        },
-    ];
+    state.items = registry
+        .iter()
+        .map(|game| CarouselItemData {
+            title: game.name.into(),
+            image_path: game.thumbnail.into(),
+            era: format!(
+                "{} {}",
+                game.id.era.label(),
+                game.id.era.tagline().to_uppercase()
+            ),
+        })
+        .collect();
 
     state.current_index = 0;
     state.target_index = 0;
@@ -83,7 +63,7 @@ fn setup_carousel(
     // Root entity for easy cleanup
     commands
         .spawn((
-            CarouselRoot,
+            StateCleanup(GameState::Menu),
             Transform::from_translation(Vec3::new(0.0, -50.0, 0.0)),
             GlobalTransform::default(),
             Visibility::default(),
@@ -171,9 +151,3 @@ fn animate_carousel(
         transform.scale = transform.scale.lerp(target_scale, speed);
     }
 }
-
-fn cleanup_carousel(mut commands: Commands, query: Query<Entity, With<CarouselRoot>>) {
-    for entity in &query {
-        commands.entity(entity).despawn();
-    }
-}
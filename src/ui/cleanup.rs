@@ -0,0 +1,190 @@
+//! State-scoped entities and resources.
+//!
+//! Entities tagged `StateCleanup(s)` are despawned, and resources registered
+//! with `StateScopedResourceExt` are removed, when `s` exits. Cleanup runs
+//! after the `OnExit` schedules and before the `OnEnter` schedules, so
+//! teardown systems still see the old world and setup systems see a clean
+//! one. Re-entering the same state (a restart) cleans up as well.
+
+use bevy::prelude::*;
+use bevy::state::state::{FreelyMutableState, StateTransitionEvent, StateTransitionSystems};
+use std::marker::PhantomData;
+
+/// Marker for entities that should be despawned when state `S` exits.
+#[derive(Component)]
+pub struct StateCleanup<S: FreelyMutableState>(pub S);
+
+/// Despawns `StateCleanup<S>` entities when their state exits.
+/// Added once per state type.
+pub struct StateCleanupPlugin<S: FreelyMutableState>(PhantomData<S>);
+
+impl<S: FreelyMutableState> Default for StateCleanupPlugin<S> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<S: FreelyMutableState> Plugin for StateCleanupPlugin<S> {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            StateTransition,
+            despawn_on_exit::<S>.in_set(StateTransitionSystems::TransitionSchedules),
+        );
+    }
+}
+
+/// Registers resources whose lifetime is tied to a state value.
+pub trait StateScopedResourceExt {
+    /// Remove `R` whenever `state` exits. The resource is inserted by
+    /// whoever owns it, usually the state's setup system.
+    fn add_state_scoped_resource<R: Resource>(&mut self, state: impl States) -> &mut Self;
+
+    /// Insert a fresh `R` whenever `state` is entered and remove it when
+    /// `state` exits.
+    fn init_state_scoped_resource<R: Resource + FromWorld>(
+        &mut self,
+        state: impl States,
+    ) -> &mut Self;
+}
+
+impl StateScopedResourceExt for App {
+    fn add_state_scoped_resource<R: Resource>(&mut self, state: impl States) -> &mut Self {
+        self.add_systems(
+            StateTransition,
+            remove_on_exit::<_, R>(state).in_set(StateTransitionSystems::TransitionSchedules),
+        )
+    }
+
+    fn init_state_scoped_resource<R: Resource + FromWorld>(
+        &mut self,
+        state: impl States,
+    ) -> &mut Self {
+        self.add_systems(
+            StateTransition,
+            (
+                remove_on_exit::<_, R>(state.clone()),
+                init_on_enter::<_, R>(state),
+            )
+                .chain()
+                .in_set(StateTransitionSystems::TransitionSchedules),
+        )
+    }
+}
+
+// ─── Systems ───────────────────────────────────────────────────────
+
+/// The transition applied this frame, if it ran exit/enter schedules.
+fn applied_transition<S: States>(
+    transitions: &mut MessageReader<StateTransitionEvent<S>>,
+) -> Option<StateTransitionEvent<S>> {
+    // The state machine sends at most one transition per state type a frame.
+    transitions
+        .read()
+        .last()
+        .filter(|t| t.exited != t.entered || t.allow_same_state_transitions)
+        .cloned()
+}
+
+fn despawn_on_exit<S: FreelyMutableState>(
+    mut commands: Commands,
+    mut transitions: MessageReader<StateTransitionEvent<S>>,
+    query: Query<(Entity, &StateCleanup<S>)>,
+) {
+    let Some(exited) = applied_transition(&mut transitions).and_then(|t| t.exited) else {
+        return;
+    };
+    for (entity, cleanup) in &query {
+        if cleanup.0 == exited {
+            commands.entity(entity).try_despawn();
+        }
+    }
+}
+
+fn remove_on_exit<S: States, R: Resource>(
+    state: S,
+) -> impl FnMut(MessageReader<StateTransitionEvent<S>>, Commands) {
+    move |mut transitions, mut commands| {
+        if applied_transition(&mut transitions).is_some_and(|t| t.exited.as_ref() == Some(&state)) {
+            commands.remove_resource::<R>();
+        }
+    }
+}
+
+fn init_on_enter<S: States, R: Resource + FromWorld>(
+    state: S,
+) -> impl FnMut(MessageReader<StateTransitionEvent<S>>, Commands) {
+    move |mut transitions, mut commands| {
+        if applied_transition(&mut transitions).is_some_and(|t| t.entered.as_ref() == Some(&state))
+        {
+            commands.init_resource::<R>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::state::app::StatesPlugin;
+
+    #[derive(States, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+    enum TestState {
+        #[default]
+        A,
+        B,
+    }
+
+    #[derive(Resource, Default)]
+    struct Counter(u32);
+
+    fn test_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((StatesPlugin, StateCleanupPlugin::<TestState>::default()))
+            .init_state::<TestState>()
+            .init_state_scoped_resource::<Counter>(TestState::A);
+        app.update();
+        app
+    }
+
+    fn set_state(app: &mut App, state: TestState) {
+        app.world_mut()
+            .resource_mut::<NextState<TestState>>()
+            .set(state);
+        app.update();
+    }
+
+    #[test]
+    fn test_tagged_entities_despawn_on_exit() {
+        let mut app = test_app();
+        let a = app.world_mut().spawn(StateCleanup(TestState::A)).id();
+        let b = app.world_mut().spawn(StateCleanup(TestState::B)).id();
+
+        set_state(&mut app, TestState::B);
+
+        assert!(app.world().get_entity(a).is_err());
+        assert!(app.world().get_entity(b).is_ok());
+    }
+
+    #[test]
+    fn test_scoped_resource_follows_state() {
+        let mut app = test_app();
+        assert!(app.world().contains_resource::<Counter>());
+
+        set_state(&mut app, TestState::B);
+        assert!(!app.world().contains_resource::<Counter>());
+
+        set_state(&mut app, TestState::A);
+        assert!(app.world().contains_resource::<Counter>());
+    }
+
+    #[test]
+    fn test_reentering_state_resets_scope() {
+        let mut app = test_app();
+        app.world_mut().resource_mut::<Counter>().0 = 5;
+        let entity = app.world_mut().spawn(StateCleanup(TestState::A)).id();
+
+        set_state(&mut app, TestState::A);
+
+        assert_eq!(app.world().resource::<Counter>().0, 0);
+        assert!(app.world().get_entity(entity).is_err());
+    }
+}
@@ -0,0 +1,245 @@
+//! Scrolling credits, opened from the main menu.
+//!
+//! The roll is built from `assets/data/credits.json`: who made the game,
+//! what it was made with and which classic inspired each mini-game. It is
+//! compiled in, so the screen works wherever the binary runs.
+
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use super::{StateCleanup, colors};
+use crate::core::input::{GameAction, GameInput};
+use crate::core::states::MenuState;
+
+/// The credits data file, embedded at build time.
+const CREDITS_JSON: &str = include_str!("../../assets/data/credits.json");
+
+/// Logical pixels per second the roll moves up.
+const SCROLL_SPEED: f32 = 40.0;
+
+/// Speed multiplier while Down or Confirm is held.
+const FAST_FORWARD: f32 = 5.0;
+
+/// Plugin for the credits screen.
+pub struct CreditsPlugin;
+
+impl Plugin for CreditsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(MenuState::Credits), setup_credits)
+            .add_systems(
+                Update,
+                (scroll_credits, close_credits).run_if(in_state(MenuState::Credits)),
+            );
+    }
+}
+
+// ─── Data ──────────────────────────────────────────────────────────
+
+/// Contents of the credits file.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Credits {
+    pub title: String,
+    pub contributors: Vec<Contributor>,
+    #[serde(default)]
+    pub tools: Vec<String>,
+    pub games: Vec<GameCredit>,
+    #[serde(default)]
+    pub closing: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Contributor {
+    pub role: String,
+    pub name: String,
+}
+
+/// A mini-game and the classic it pays tribute to.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GameCredit {
+    pub title: String,
+    pub inspired_by: String,
+}
+
+impl Credits {
+    /// The credits shipped with the game.
+    pub fn bundled() -> Result<Credits, serde_json::Error> {
+        serde_json::from_str(CREDITS_JSON)
+    }
+}
+
+// ─── Components ────────────────────────────────────────────────────
+
+/// The clipped area the roll scrolls through.
+#[derive(Component)]
+struct CreditsViewport;
+
+/// The column of credits, moved up every frame.
+#[derive(Component, Default)]
+struct CreditsRoll {
+    /// How far the roll has moved up from just below the viewport.
+    offset: f32,
+}
+
+// ─── Systems ───────────────────────────────────────────────────────
+
+fn setup_credits(mut commands: Commands) {
+    let credits = Credits::bundled().unwrap_or_else(|err| {
+        error!("Could not read the credits: {}", err);
+        Credits {
+            title: "RETROGAMEGAME".into(),
+            contributors: Vec::new(),
+            tools: Vec::new(),
+            games: Vec::new(),
+            closing: String::new(),
+        }
+    });
+
+    commands
+        .spawn((
+            StateCleanup(MenuState::Credits),
+            GlobalZIndex(10),
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.85)),
+        ))
+        .with_children(|root| {
+            root.spawn((
+                CreditsViewport,
+                Node {
+                    width: Val::Px(640.0),
+                    height: Val::Percent(80.0),
+                    overflow: Overflow::clip(),
+                    ..default()
+                },
+            ))
+            .with_children(|viewport| {
+                viewport
+                    .spawn((
+                        CreditsRoll::default(),
+                        Node {
+                            position_type: PositionType::Absolute,
+                            width: Val::Percent(100.0),
+                            top: Val::Percent(100.0),
+                            flex_direction: FlexDirection::Column,
+                            align_items: AlignItems::Center,
+                            row_gap: Val::Px(6.0),
+                            ..default()
+                        },
+                    ))
+                    .with_children(|roll| spawn_roll(roll, &credits));
+            });
+
+            root.spawn((
+                Text::new("Esc — back   ·   hold ↓ to speed up"),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(colors::TEXT_SECONDARY),
+                Node {
+                    margin: UiRect::top(Val::Px(16.0)),
+                    ..default()
+                },
+            ));
+        });
+}
+
+fn spawn_roll(roll: &mut ChildSpawnerCommands, credits: &Credits) {
+    let mut line = |text: &str, size: f32, color: Color, gap: f32| {
+        roll.spawn((
+            Text::new(text),
+            TextFont {
+                font_size: size,
+                ..default()
+            },
+            TextColor(color),
+            TextLayout::new_with_justify(Justify::Center),
+            Node {
+                margin: UiRect::top(Val::Px(gap)),
+                ..default()
+            },
+        ));
+    };
+
+    line(&credits.title, 40.0, colors::TEXT_ACCENT, 0.0);
+
+    for contributor in &credits.contributors {
+        line(&contributor.role, 16.0, colors::TEXT_SECONDARY, 24.0);
+        line(&contributor.name, 22.0, colors::TEXT_PRIMARY, 0.0);
+    }
+
+    if !credits.tools.is_empty() {
+        line("BUILT WITH", 16.0, colors::TEXT_SECONDARY, 48.0);
+        for tool in &credits.tools {
+            line(tool, 20.0, colors::TEXT_PRIMARY, 0.0);
+        }
+    }
+
+    if !credits.games.is_empty() {
+        line("INSPIRED BY", 16.0, colors::TEXT_SECONDARY, 48.0);
+        for game in &credits.games {
+            line(&game.title, 22.0, colors::EGA_BRIGHT_YELLOW, 12.0);
+            line(&game.inspired_by, 16.0, colors::TEXT_PRIMARY, 0.0);
+        }
+    }
+
+    if !credits.closing.is_empty() {
+        line(&credits.closing, 28.0, colors::GOLD, 64.0);
+    }
+}
+
+/// Move the roll up, starting over once it has left the top.
+fn scroll_credits(
+    time: Res<Time>,
+    input: Res<GameInput>,
+    viewports: Query<&ComputedNode, With<CreditsViewport>>,
+    mut rolls: Query<(&mut CreditsRoll, &mut Node, &ComputedNode)>,
+) {
+    let Ok(viewport) = viewports.single() else {
+        return;
+    };
+    let viewport_height = viewport.size().y * viewport.inverse_scale_factor();
+    let speed = if input.pressed(GameAction::Down) || input.pressed(GameAction::Confirm) {
+        SCROLL_SPEED * FAST_FORWARD
+    } else {
+        SCROLL_SPEED
+    };
+
+    for (mut roll, mut node, computed) in &mut rolls {
+        let roll_height = computed.size().y * computed.inverse_scale_factor();
+        roll.offset += speed * time.delta_secs();
+        if roll_height > 0.0 && roll.offset > viewport_height + roll_height {
+            roll.offset = 0.0;
+        }
+        node.top = Val::Px(viewport_height - roll.offset);
+    }
+}
+
+fn close_credits(input: Res<GameInput>, mut next_menu: ResMut<NextState<MenuState>>) {
+    if input.cancel || input.pause || input.mouse_click {
+        next_menu.set(MenuState::Main);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundled_credits_parse() {
+        let credits = Credits::bundled().unwrap();
+        assert!(!credits.contributors.is_empty());
+        assert!(
+            credits
+                .games
+                .iter()
+                .any(|game| game.title == "Tunnel Miner" && game.inspired_by.contains("Digger"))
+        );
+    }
+}
@@ -1,8 +1,9 @@
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
 
-use super::colors;
+use super::{StateCleanup, colors};
 use crate::core::progression::PlayerProgress;
+use crate::core::registry::GameRegistry;
 use crate::core::states::{Era, GameState, MiniGameId, PlayingState};
 
 /// Plugin for the era selection screen (mini-game picker).
@@ -17,8 +18,7 @@ impl Plugin for EraSelectPlugin {
                 Update,
                 (era_select_input, layout_game_carousel, update_game_labels)
                     .run_if(in_state(GameState::EraSelect)),
-            )
-            .add_systems(OnExit(GameState::EraSelect), cleanup_era_select);
+            );
     }
 }
 
@@ -38,9 +38,6 @@ struct SelectedGame {
     index: usize,
 }
 
-#[derive(Component)]
-struct EraSelectRoot;
-
 #[derive(Component)]
 struct GameCarouselCard {
     game: MiniGameId,
@@ -60,10 +57,11 @@ fn setup_era_select(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     current_era: Res<CurrentEra>,
+    registry: Res<GameRegistry>,
     mut selected_game: ResMut<SelectedGame>,
     camera_query: Query<Entity, With<Camera2d>>,
 ) {
-    let games = get_era_games(current_era.era);
+    let games = registry.era_games(current_era.era);
     if games.is_empty() {
         selected_game.index = 0;
     } else {
@@ -76,7 +74,7 @@ fn setup_era_select(
 
     commands
         .spawn((
-            EraSelectRoot,
+            StateCleanup(GameState::EraSelect),
             Node {
                 width: Val::Percent(100.0),
                 height: Val::Percent(100.0),
@@ -180,7 +178,10 @@ fn setup_era_select(
 
             for (index, game) in games.iter().enumerate() {
                 root.spawn((
-                    GameCarouselCard { game: *game, index },
+                    GameCarouselCard {
+                        game: game.id,
+                        index,
+                    },
                     Node {
                         position_type: PositionType::Absolute,
                         width: Val::Px(300.0),
@@ -197,7 +198,7 @@ fn setup_era_select(
                 ))
                 .with_children(|card| {
                     card.spawn((
-                        ImageNode::new(asset_server.load(game_thumbnail_path(*game))),
+                        ImageNode::new(asset_server.load(game.thumbnail)),
                         Node {
                             width: Val::Percent(92.0),
                             height: Val::Percent(65.0),
@@ -206,7 +207,7 @@ fn setup_era_select(
                     ));
 
                     card.spawn((
-                        Text::new(game.display_name().to_string()),
+                        Text::new(game.name.to_string()),
                         TextFont {
                             font_size: 26.0,
                             ..default()
@@ -221,12 +222,13 @@ fn setup_era_select(
 fn era_select_input(
     keyboard: Res<ButtonInput<KeyCode>>,
     current_era: Res<CurrentEra>,
+    registry: Res<GameRegistry>,
     progress: Res<PlayerProgress>,
     mut selected_game: ResMut<SelectedGame>,
     mut game_state: ResMut<NextState<GameState>>,
     mut playing_state: ResMut<NextState<PlayingState>>,
 ) {
-    let games = get_era_games(current_era.era);
+    let games = registry.era_games(current_era.era);
 
     if keyboard.just_pressed(KeyCode::Escape) {
         game_state.set(GameState::Timeline);
@@ -246,8 +248,8 @@ fn era_select_input(
 
     if keyboard.just_pressed(KeyCode::Enter) {
         let game = games[selected_game.index];
-        if progress.is_game_unlocked(game) {
-            playing_state.set(game.playing_state());
+        if progress.is_game_unlocked(game.id) {
+            playing_state.set(game.playing_state);
             game_state.set(GameState::Playing);
         }
     }
@@ -255,6 +257,7 @@ fn era_select_input(
 
 fn layout_game_carousel(
     current_era: Res<CurrentEra>,
+    registry: Res<GameRegistry>,
     selected_game: Res<SelectedGame>,
     progress: Res<PlayerProgress>,
     time: Res<Time>,
@@ -269,8 +272,7 @@ fn layout_game_carousel(
         With<GameCarouselCard>,
     >,
 ) {
-    let games = get_era_games(current_era.era);
-    if games.is_empty() {
+    if registry.games_in_era(current_era.era) == 0 {
         return;
     }
 
@@ -332,8 +334,10 @@ fn layout_game_carousel(
     }
 }
 
+#[allow(clippy::type_complexity)]
 fn update_game_labels(
     current_era: Res<CurrentEra>,
+    registry: Res<GameRegistry>,
     selected_game: Res<SelectedGame>,
     progress: Res<PlayerProgress>,
     mut text_sets: ParamSet<(
@@ -342,7 +346,7 @@ fn update_game_labels(
         Query<&mut Text, With<SelectedGameStatus>>,
     )>,
 ) {
-    let games = get_era_games(current_era.era);
+    let games = registry.era_games(current_era.era);
     if games.is_empty() {
         if let Ok(mut text) = text_sets.p0().single_mut() {
             **text = "No Games Available".to_string();
@@ -356,13 +360,14 @@ fn update_game_labels(
         return;
     }
 
-    let game = games[selected_game.index.min(games.len() - 1)];
+    let info = games[selected_game.index.min(games.len() - 1)];
+    let game = info.id;
 
     if let Ok(mut text) = text_sets.p0().single_mut() {
-        **text = game.display_name().to_string();
+        **text = info.name.to_string();
     }
     if let Ok(mut text) = text_sets.p1().single_mut() {
-        **text = game.description().to_string();
+        **text = info.description.to_string();
     }
     if let Ok(mut text) = text_sets.p2().single_mut() {
         if progress.is_game_completed(game) {
@@ -379,40 +384,3 @@ fn update_game_labels(
         }
     }
 }
-
-fn game_thumbnail_path(game: MiniGameId) -> &'static str {
-    match (game.era, game.index) {
-        (Era::The80s, 0) => "ui/thumbnails/tunnel_miner.png",
-        (Era::The80s, 1) => "ui/thumbnails/cosmic_captain.png",
-        (Era::The80s, 2) => "ui/thumbnails/star_goose.png",
-        (Era::The90s, 0) => "ui/thumbnails/worm_wars.png",
-        (Era::The90s, 1) => "ui/thumbnails/ice_blitz.png",
-        (Era::The90s, 2) => "ui/thumbnails/depths_of_doom.png",
-        (Era::Future, 0) => "ui/thumbnails/nebula_bouncer.png",
-        _ => "ui/thumbnails/tunnel_miner.png",
-    }
-}
-
-fn get_era_games(era: Era) -> Vec<MiniGameId> {
-    match era {
-        Era::The80s => vec![
-            MiniGameId { era, index: 0 },
-            MiniGameId { era, index: 1 },
-            MiniGameId { era, index: 2 },
-        ],
-        Era::The90s => vec![
-            MiniGameId { era, index: 0 },
-            MiniGameId { era, index: 1 },
-            MiniGameId { era, index: 2 },
-        ],
-        Era::Future => vec![MiniGameId { era, index: 0 }],
-        _ => vec![],
-    }
-}
-
-fn cleanup_era_select(mut commands: Commands, query: Query<Entity, With<EraSelectRoot>>) {
-    for entity in &query {
-        commands.entity(entity).despawn_children();
-        commands.entity(entity).despawn();
-    }
-}
@@ -0,0 +1,267 @@
+//! Leaderboard view, opened from the results screen.
+//!
+//! Shows one game's top-10 table at a time and pages through every
+//! registered game, starting from the one just played. The run that just
+//! made the table is highlighted.
+
+use bevy::prelude::*;
+
+use super::results::GameResults;
+use super::{StateCleanup, colors};
+use crate::core::input::GameInput;
+use crate::core::leaderboard::{TABLE_SIZE, format_date};
+use crate::core::progression::PlayerProgress;
+use crate::core::registry::GameRegistry;
+use crate::core::states::ResultsState;
+
+/// Plugin for the leaderboard view.
+pub struct LeaderboardsPlugin;
+
+impl Plugin for LeaderboardsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LeaderboardPage>()
+            .add_systems(OnEnter(ResultsState::Leaderboards), setup_leaderboards)
+            .add_systems(
+                Update,
+                (leaderboard_navigation, rebuild_leaderboard)
+                    .chain()
+                    .run_if(in_state(ResultsState::Leaderboards)),
+            );
+    }
+}
+
+// ─── Resources ─────────────────────────────────────────────────────
+
+#[derive(Resource, Default)]
+struct LeaderboardPage {
+    /// Index into `GameRegistry::iter`.
+    index: usize,
+    dirty: bool,
+}
+
+// ─── Components ────────────────────────────────────────────────────
+
+#[derive(Component)]
+struct LeaderboardTitle;
+
+#[derive(Component)]
+struct LeaderboardTable;
+
+// ─── Setup ─────────────────────────────────────────────────────────
+
+fn setup_leaderboards(
+    mut commands: Commands,
+    results: Res<GameResults>,
+    registry: Res<GameRegistry>,
+    mut page: ResMut<LeaderboardPage>,
+) {
+    *page = LeaderboardPage {
+        index: registry
+            .iter()
+            .position(|info| Some(info.id) == results.game)
+            .unwrap_or(0),
+        dirty: true,
+    };
+
+    commands
+        .spawn((
+            StateCleanup(ResultsState::Leaderboards),
+            GlobalZIndex(10),
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.85)),
+        ))
+        .with_children(|root| {
+            root.spawn((
+                Node {
+                    width: Val::Px(640.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    padding: UiRect::all(Val::Px(24.0)),
+                    border: UiRect::all(Val::Px(2.0)),
+                    row_gap: Val::Px(12.0),
+                    border_radius: BorderRadius::all(Val::Px(12.0)),
+                    ..default()
+                },
+                BackgroundColor(colors::PANEL_BG),
+                BorderColor::all(colors::PANEL_BORDER),
+            ))
+            .with_children(|panel| {
+                panel.spawn((
+                    Text::new("LEADERBOARDS"),
+                    TextFont {
+                        font_size: 36.0,
+                        ..default()
+                    },
+                    TextColor(colors::TEXT_ACCENT),
+                ));
+
+                panel.spawn((
+                    LeaderboardTitle,
+                    Text::new(""),
+                    TextFont {
+                        font_size: 18.0,
+                        ..default()
+                    },
+                    TextColor(colors::EGA_BRIGHT_YELLOW),
+                ));
+
+                panel.spawn((
+                    LeaderboardTable,
+                    Node {
+                        width: Val::Percent(100.0),
+                        flex_direction: FlexDirection::Column,
+                        row_gap: Val::Px(2.0),
+                        ..default()
+                    },
+                ));
+
+                panel.spawn((
+                    Text::new("←→ game — Esc back"),
+                    TextFont {
+                        font_size: 14.0,
+                        ..default()
+                    },
+                    TextColor(colors::TEXT_SECONDARY),
+                ));
+            });
+        });
+}
+
+// ─── Input ─────────────────────────────────────────────────────────
+
+/// Left/right page through the games and cancel goes back to the results.
+fn leaderboard_navigation(
+    input: Res<GameInput>,
+    registry: Res<GameRegistry>,
+    mut page: ResMut<LeaderboardPage>,
+    mut next_results: ResMut<NextState<ResultsState>>,
+) {
+    let count = registry.iter().count().max(1);
+    if input.just_left {
+        page.index = (page.index + count - 1) % count;
+        page.dirty = true;
+    } else if input.just_right {
+        page.index = (page.index + 1) % count;
+        page.dirty = true;
+    } else if input.cancel {
+        next_results.set(ResultsState::Summary);
+    }
+}
+
+// ─── Display ───────────────────────────────────────────────────────
+
+fn rebuild_leaderboard(
+    mut commands: Commands,
+    mut page: ResMut<LeaderboardPage>,
+    registry: Res<GameRegistry>,
+    progress: Res<PlayerProgress>,
+    results: Res<GameResults>,
+    mut title: Query<&mut Text, With<LeaderboardTitle>>,
+    table: Query<Entity, With<LeaderboardTable>>,
+) {
+    if !page.dirty {
+        return;
+    }
+    let Ok(table) = table.single() else {
+        return;
+    };
+    page.dirty = false;
+    let Some(info) = registry.iter().nth(page.index) else {
+        return;
+    };
+    if let Ok(mut text) = title.single_mut() {
+        **text = format!("◀  {}  ▶", info.name);
+    }
+
+    let entries = progress
+        .leaderboard(info.id)
+        .map_or(&[][..], |board| board.entries());
+    let highlighted = results
+        .leaderboard_rank
+        .filter(|_| results.game == Some(info.id));
+
+    commands.entity(table).despawn_children();
+    commands.entity(table).with_children(|table| {
+        spawn_table_row(
+            table,
+            ["#", "NAME", "SCORE", "DATE", "SEED"].map(str::to_string),
+            colors::TEXT_SECONDARY,
+            colors::PANEL_BG,
+        );
+        for rank in 0..TABLE_SIZE {
+            let cells = match entries.get(rank) {
+                Some(entry) => [
+                    format!("{}", rank + 1),
+                    entry.initials.clone(),
+                    entry.score.to_string(),
+                    format_date(entry.date),
+                    if entry.replay.is_some() {
+                        format!("{} 📼", entry.seed)
+                    } else {
+                        entry.seed.to_string()
+                    },
+                ],
+                None => [
+                    format!("{}", rank + 1),
+                    "---".to_string(),
+                    "-".to_string(),
+                    String::new(),
+                    String::new(),
+                ],
+            };
+            let (text, background) = if highlighted == Some(rank) {
+                (colors::GOLD, colors::BUTTON_HOVER)
+            } else if rank < entries.len() {
+                (colors::TEXT_PRIMARY, colors::BUTTON_NORMAL)
+            } else {
+                (colors::TEXT_SECONDARY, colors::BUTTON_LOCKED)
+            };
+            spawn_table_row(table, cells, text, background);
+        }
+    });
+}
+
+/// Column widths, as percentages of the table.
+const COLUMNS: [f32; 5] = [8.0, 16.0, 22.0, 24.0, 30.0];
+
+fn spawn_table_row(
+    table: &mut ChildSpawnerCommands,
+    cells: [String; 5],
+    text: Color,
+    background: Color,
+) {
+    table
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Px(26.0),
+                align_items: AlignItems::Center,
+                padding: UiRect::horizontal(Val::Px(12.0)),
+                border_radius: BorderRadius::all(Val::Px(4.0)),
+                ..default()
+            },
+            BackgroundColor(background),
+        ))
+        .with_children(|row| {
+            for (cell, width) in cells.into_iter().zip(COLUMNS) {
+                row.spawn((
+                    Node {
+                        width: Val::Percent(width),
+                        ..default()
+                    },
+                    Text::new(cell),
+                    TextFont {
+                        font_size: 15.0,
+                        ..default()
+                    },
+                    TextColor(text),
+                ));
+            }
+        });
+}
@@ -1,46 +1,44 @@
 use bevy::prelude::*;
 
-use super::colors;
-use crate::core::states::GameState;
+use super::{StateCleanup, colors};
+use crate::core::profiles::ProfileStore;
+use crate::core::states::{GameState, MenuState};
 /// Plugin for the main menu screen.
 pub struct MenuPlugin;
 
 impl Plugin for MenuPlugin {
     fn build(&self, app: &mut App) {
-        // app.add_event::<ScreenTransitionEvent>()
-        //     .add_systems(Update, (start_transition, update_transition));
         app.add_systems(OnEnter(GameState::Boot), setup_boot)
             .add_systems(Update, boot_timer.run_if(in_state(GameState::Boot)))
             .add_systems(OnEnter(GameState::Menu), setup_menu)
             .add_systems(
                 Update,
-                (button_interaction, menu_action).run_if(in_state(GameState::Menu)),
-            )
-            .add_systems(OnExit(GameState::Menu), cleanup_menu);
+                (
+                    button_interaction,
+                    menu_action.run_if(in_state(MenuState::Main)),
+                    update_profile_label.run_if(resource_exists_and_changed::<ProfileStore>),
+                )
+                    .run_if(in_state(GameState::Menu)),
+            );
     }
 }
 
 // ─── Components ────────────────────────────────────────────────────
 
-#[derive(Component)]
-struct BootScreen;
-
 #[derive(Component)]
 struct BootTimer(Timer);
 
-#[derive(Component)]
-struct MenuRoot;
-
 #[derive(Component)]
 enum MenuButton {
     Play,
+    Profiles,
     Settings,
     Credits,
     Quit,
 }
 
 #[derive(Component)]
-struct MenuWorldElement;
+struct MenuProfileText;
 
 // ─── Boot screen ───────────────────────────────────────────────────
 
@@ -51,7 +49,7 @@ fn setup_boot(mut commands: Commands) {
     // Boot/splash screen
     commands
         .spawn((
-            BootScreen,
+            StateCleanup(GameState::Boot),
             BootTimer(Timer::from_seconds(2.0, TimerMode::Once)),
             Node {
                 width: Val::Percent(100.0),
@@ -107,17 +105,10 @@ fn boot_timer(
     time: Res<Time>,
     mut query: Query<&mut BootTimer>,
     mut next_state: ResMut<NextState<GameState>>,
-    boot_screen: Query<Entity, With<BootScreen>>,
-    mut commands: Commands,
 ) {
     for mut timer in &mut query {
         timer.0.tick(time.delta());
         if timer.0.just_finished() {
-            // Clean up boot screen
-            for entity in &boot_screen {
-                commands.entity(entity).despawn_children();
-                commands.entity(entity).despawn();
-            }
             next_state.set(GameState::Menu);
         }
     }
@@ -125,10 +116,14 @@ fn boot_timer(
 
 // ─── Main menu ─────────────────────────────────────────────────────
 
-fn setup_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
+fn setup_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    profiles: Option<Res<ProfileStore>>,
+) {
     // Spawn Background Image (World Space)
     commands.spawn((
-        MenuWorldElement,
+        StateCleanup(GameState::Menu),
         Sprite {
             image: asset_server.load("ui/NewBackround.png"),
             custom_size: Some(Vec2::new(1920.0, 1080.0)),
@@ -144,7 +139,7 @@ fn setup_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
     // Initialize UI Root
     commands
         .spawn((
-            MenuRoot,
+            StateCleanup(GameState::Menu),
             Node {
                 width: Val::Percent(100.0),
                 height: Val::Percent(100.0),
@@ -179,6 +174,7 @@ fn setup_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
                 .with_children(|buttons| {
                     let menu_items = [
                         ("▶  PLAY", MenuButton::Play),
+                        ("☺  PROFILES", MenuButton::Profiles),
                         ("⚙  SETTINGS", MenuButton::Settings),
                         ("★  CREDITS", MenuButton::Credits),
                         ("✕  QUIT", MenuButton::Quit),
@@ -216,7 +212,8 @@ fn setup_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
 
             // Footer
             parent.spawn((
-                Text::new("v0.1.0 — Press Enter to select"),
+                MenuProfileText,
+                Text::new(footer_text(profiles.as_deref())),
                 TextFont {
                     font_size: 14.0,
                     ..default()
@@ -234,6 +231,7 @@ fn setup_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
 
 // ─── Interaction systems ───────────────────────────────────────────
 
+#[allow(clippy::type_complexity)]
 fn button_interaction(
     mut interaction_query: Query<
         (&Interaction, &mut BackgroundColor, &mut BorderColor),
@@ -258,9 +256,11 @@ fn button_interaction(
     }
 }
 
+#[allow(clippy::type_complexity)]
 fn menu_action(
     interaction_query: Query<(&Interaction, &MenuButton), (Changed<Interaction>, With<Button>)>,
     mut next_state: ResMut<NextState<GameState>>,
+    mut next_menu: ResMut<NextState<MenuState>>,
     // mut exit: ResMut<Events<AppExit>>,
 ) {
     for (interaction, menu_button) in &interaction_query {
@@ -269,11 +269,14 @@ fn menu_action(
                 MenuButton::Play => {
                     next_state.set(GameState::Timeline);
                 }
+                MenuButton::Profiles => {
+                    next_menu.set(MenuState::Profiles);
+                }
                 MenuButton::Settings => {
-                    // TODO: Settings
+                    next_menu.set(MenuState::Settings);
                 }
                 MenuButton::Credits => {
-                    // TODO: Credits
+                    next_menu.set(MenuState::Credits);
                 }
                 MenuButton::Quit => {
                     // exit.send(AppExit::Success);
@@ -283,17 +286,18 @@ fn menu_action(
     }
 }
 
-fn cleanup_menu(
-    mut commands: Commands,
-    query_root: Query<Entity, With<MenuRoot>>,
-    query_world: Query<Entity, With<MenuWorldElement>>,
-) {
-    for entity in &query_root {
-        commands.entity(entity).despawn_children();
-        commands.entity(entity).despawn();
+fn footer_text(profiles: Option<&ProfileStore>) -> String {
+    match profiles.and_then(|p| p.active()) {
+        Some(profile) => format!("v0.1.0 — Profile: {} — Press Enter to select", profile.name),
+        None => "v0.1.0 — Press Enter to select".to_string(),
     }
-    for entity in &query_world {
-        commands.entity(entity).despawn_children();
-        commands.entity(entity).despawn();
+}
+
+fn update_profile_label(
+    profiles: Res<ProfileStore>,
+    mut query: Query<&mut Text, With<MenuProfileText>>,
+) {
+    for mut text in &mut query {
+        **text = footer_text(Some(&profiles));
     }
 }
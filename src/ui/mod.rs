@@ -1,26 +1,65 @@
+pub mod achievements;
+pub mod attract;
+pub mod cabinet;
 pub mod carousel;
+pub mod cleanup;
+pub mod credits;
 pub mod era_select;
+pub mod leaderboards;
 pub mod menu;
 pub mod music;
 pub mod particles;
+pub mod pause;
+pub mod profiles;
 pub mod results;
+pub mod settings;
+pub mod shop;
+pub mod stats;
 pub mod timeline;
 
 use bevy::prelude::*;
 
-/// UI plugin: menus, timeline hub, era selection, results.
+use crate::core::states::{
+    GameState, MenuState, PauseMenuState, PauseState, PlayingState, ResultsState, TimelineState,
+};
+pub use cleanup::{StateCleanup, StateCleanupPlugin, StateScopedResourceExt};
+
+/// UI plugin: menus, settings, credits, attract mode, timeline hub, token
+/// shop, achievement gallery and toasts, stats screen, era selection,
+/// results and leaderboards, pause overlay, cabinet frames.
 pub struct UiPlugin;
 
 impl Plugin for UiPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins((
+            StateCleanupPlugin::<GameState>::default(),
+            StateCleanupPlugin::<MenuState>::default(),
+            StateCleanupPlugin::<TimelineState>::default(),
+            StateCleanupPlugin::<ResultsState>::default(),
+            StateCleanupPlugin::<PlayingState>::default(),
+            StateCleanupPlugin::<PauseState>::default(),
+            StateCleanupPlugin::<PauseMenuState>::default(),
+        ))
+        .add_plugins((
             music::MusicPlugin,
             menu::MenuPlugin,
+            profiles::ProfilesPlugin,
+            settings::SettingsScreenPlugin,
+            credits::CreditsPlugin,
+            attract::AttractPlugin,
             carousel::CarouselPlugin,
             particles::ParticlesPlugin,
             timeline::TimelinePlugin,
+            shop::ShopPlugin,
+            achievements::AchievementsUiPlugin,
+            cabinet::CabinetPlugin,
             era_select::EraSelectPlugin,
+        ))
+        .add_plugins((
+            stats::StatsScreenPlugin,
             results::ResultsPlugin,
+            leaderboards::LeaderboardsPlugin,
+            pause::PausePlugin,
         ));
     }
 }
@@ -49,6 +88,26 @@ pub mod colors {
     pub const EGA_BRIGHT_YELLOW: Color = Color::srgb(1.0, 1.0, 0.33); // 14
     pub const EGA_BRIGHT_WHITE: Color = Color::srgb(1.0, 1.0, 1.0); // 15
 
+    /// The EGA colors in palette index order.
+    pub const EGA_PALETTE: [Color; 16] = [
+        EGA_BLACK,
+        EGA_BLUE,
+        EGA_GREEN,
+        EGA_CYAN,
+        EGA_RED,
+        EGA_MAGENTA,
+        EGA_BROWN,
+        EGA_LIGHT_GRAY,
+        EGA_DARK_GRAY,
+        EGA_BRIGHT_BLUE,
+        EGA_BRIGHT_GREEN,
+        EGA_BRIGHT_CYAN,
+        EGA_BRIGHT_RED,
+        EGA_BRIGHT_MAGENTA,
+        EGA_BRIGHT_YELLOW,
+        EGA_BRIGHT_WHITE,
+    ];
+
     // UI chrome colors
     pub const PANEL_BG: Color = Color::srgba(0.05, 0.05, 0.12, 0.92);
     pub const PANEL_BORDER: Color = Color::srgb(0.25, 0.25, 0.45);
@@ -61,7 +120,3 @@ pub mod colors {
     pub const TEXT_ACCENT: Color = Color::srgb(0.4, 0.8, 1.0);
     pub const GOLD: Color = Color::srgb(1.0, 0.84, 0.0);
 }
-
-/// Marker for UI entities that should be cleaned up on state exit.
-#[derive(Component)]
-pub struct StateCleanup<S: bevy::state::state::FreelyMutableState>(pub S);
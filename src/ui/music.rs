@@ -2,6 +2,9 @@ use bevy::asset::LoadState;
 use bevy::audio::{AudioSink, AudioSinkPlayback, Volume};
 use bevy::prelude::*;
 
+use crate::core::cosmetics::CosmeticSlot;
+use crate::core::progression::PlayerProgress;
+use crate::core::settings::{AudioChannel, UserSettings};
 use crate::core::states::GameState;
 
 /// Global music controller for hub screens.
@@ -21,6 +24,10 @@ struct HubMusicEntity;
 struct HubMusicController {
     entity: Entity,
     handle: Handle<AudioSource>,
+    /// Track played when no hub music is equipped from the shop.
+    default_track: &'static str,
+    /// Track asked for, before any known-good fallback.
+    track: &'static str,
     missing_sink_frames: u32,
     load_wait_frames: u32,
     warned_failed_load: bool,
@@ -32,7 +39,30 @@ struct HubMusicController {
 const HUB_MUSIC_PRIMARY: &str = "music/pixel_pathways.mp3";
 const HUB_MUSIC_FALLBACK: &str = "music/Pixel Pathways.mp3";
 const HUB_MUSIC_KNOWN_GOOD: &str = "music/Pixel Popcorn Rush.mp3";
-const HUB_MUSIC_VOLUME: f32 = 1.25;
+/// Gain on top of the music volume setting; the hub track is mastered quiet.
+const HUB_MUSIC_GAIN: f32 = 1.25;
+
+impl HubMusicController {
+    /// Start over with `handle` on a fresh audio entity.
+    fn replace(&mut self, commands: &mut Commands, handle: Handle<AudioSource>) {
+        commands.entity(self.entity).despawn();
+        self.handle = handle;
+        self.entity = spawn_hub_music_entity(commands, self.handle.clone());
+        self.load_wait_frames = 0;
+        self.missing_sink_frames = 0;
+        self.warned_missing_sink = false;
+        self.warned_failed_load = false;
+        self.logged_sink_ready = false;
+    }
+}
+
+/// Asset path of the hub track sold in the shop as cosmetic `id`.
+pub fn hub_track(id: &str) -> Option<&'static str> {
+    match id {
+        "music_popcorn_rush" => Some(HUB_MUSIC_KNOWN_GOOD),
+        _ => None,
+    }
+}
 
 fn setup_hub_music(mut commands: Commands, asset_server: Res<AssetServer>) {
     let music_path = choose_music_path();
@@ -47,6 +77,8 @@ fn setup_hub_music(mut commands: Commands, asset_server: Res<AssetServer>) {
     commands.insert_resource(HubMusicController {
         entity,
         handle,
+        default_track: music_path,
+        track: music_path,
         missing_sink_frames: 0,
         load_wait_frames: 0,
         warned_failed_load: false,
@@ -56,10 +88,13 @@ fn setup_hub_music(mut commands: Commands, asset_server: Res<AssetServer>) {
     });
 }
 
+#[allow(clippy::too_many_arguments)]
 fn control_hub_music(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     state: Res<State<GameState>>,
+    settings: Res<UserSettings>,
+    progress: Res<PlayerProgress>,
     controller: Option<ResMut<HubMusicController>>,
     music_entity_query: Query<(), With<HubMusicEntity>>,
     mut sink_query: Query<&mut AudioSink, With<HubMusicEntity>>,
@@ -72,6 +107,17 @@ fn control_hub_music(
         controller.entity = spawn_hub_music_entity(&mut commands, controller.handle.clone());
     }
 
+    let track = progress
+        .equipped(CosmeticSlot::HubMusic)
+        .and_then(|cosmetic| hub_track(cosmetic.id))
+        .unwrap_or(controller.default_track);
+    if track != controller.track {
+        info!("Switching hub music to '{}'", track);
+        controller.replace(&mut commands, asset_server.load(track));
+        controller.track = track;
+        controller.tried_known_good_fallback = false;
+    }
+
     let current_state = state.get();
     let should_play = matches!(
         current_state,
@@ -97,14 +143,7 @@ fn control_hub_music(
                 "Hub music did not reach Loaded state in time ({:?}). Switching to fallback track '{}'",
                 load_state, HUB_MUSIC_KNOWN_GOOD
             );
-            controller.handle = asset_server.load(HUB_MUSIC_KNOWN_GOOD);
-            commands.entity(controller.entity).despawn();
-            controller.entity = spawn_hub_music_entity(&mut commands, controller.handle.clone());
-            controller.load_wait_frames = 0;
-            controller.missing_sink_frames = 0;
-            controller.warned_missing_sink = false;
-            controller.warned_failed_load = false;
-            controller.logged_sink_ready = false;
+            controller.replace(&mut commands, asset_server.load(HUB_MUSIC_KNOWN_GOOD));
             controller.tried_known_good_fallback = true;
         }
         return;
@@ -126,7 +165,7 @@ fn control_hub_music(
                 sink.play();
             }
             sink.unmute();
-            sink.set_volume(Volume::Linear(HUB_MUSIC_VOLUME));
+            sink.set_volume(hub_music_volume(&settings));
 
             if sink.empty() {
                 warn!("Hub music sink is empty, respawning music entity");
@@ -155,13 +194,15 @@ fn spawn_hub_music_entity(commands: &mut Commands, handle: Handle<AudioSource>)
         .spawn((
             HubMusicEntity,
             AudioPlayer::new(handle),
-            PlaybackSettings::LOOP
-                .with_volume(Volume::Linear(HUB_MUSIC_VOLUME))
-                .paused(),
+            PlaybackSettings::LOOP.paused(),
         ))
         .id()
 }
 
+fn hub_music_volume(settings: &UserSettings) -> Volume {
+    settings.volume(AudioChannel::Music) * Volume::Linear(HUB_MUSIC_GAIN)
+}
+
 fn choose_music_path() -> &'static str {
     let primary = format!(
         "{}/assets/{}",
@@ -1,7 +1,8 @@
+use crate::core::rng::GameRng;
 use crate::core::states::GameState;
-use crate::ui::colors;
+use crate::ui::{StateCleanup, colors};
 use bevy::prelude::*;
-use rand::prelude::*;
+use rand::RngExt;
 
 pub struct ParticlesPlugin;
 
@@ -11,8 +12,7 @@ impl Plugin for ParticlesPlugin {
             .add_systems(
                 Update,
                 (emit_particles, update_particles).run_if(in_state(GameState::Menu)),
-            )
-            .add_systems(OnExit(GameState::Menu), cleanup_particles);
+            );
     }
 }
 
@@ -27,12 +27,9 @@ struct Particle {
     lifetime: Timer,
 }
 
-#[derive(Component)]
-struct ParticleRoot;
-
 fn setup_particles(mut commands: Commands) {
     commands.spawn((
-        ParticleRoot,
+        StateCleanup(GameState::Menu),
         Transform::default(),
         GlobalTransform::default(),
         Visibility::default(),
@@ -46,9 +43,10 @@ fn setup_particles(mut commands: Commands) {
 fn emit_particles(
     mut commands: Commands,
     time: Res<Time>,
+    mut game_rng: ResMut<GameRng>,
     mut query: Query<(&mut ParticleEmitter, Entity)>,
 ) {
-    let mut rng = rand::rng();
+    let rng = game_rng.cosmetic();
 
     for (mut emitter, parent_entity) in &mut query {
         emitter.timer.tick(time.delta());
@@ -113,10 +111,3 @@ fn update_particles(
         }
     }
 }
-
-fn cleanup_particles(mut commands: Commands, query: Query<Entity, With<ParticleRoot>>) {
-    // This will despawn the root and all its children (particles)
-    for entity in &query {
-        commands.entity(entity).despawn();
-    }
-}
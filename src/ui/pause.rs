@@ -0,0 +1,214 @@
+use bevy::prelude::*;
+
+use super::{StateCleanup, colors};
+use crate::core::input::GameInput;
+use crate::core::replay::ReplayPlayback;
+use crate::core::states::{GameState, PauseMenuState, PauseState};
+use crate::eras::shared::{QuitMiniGame, RestartMiniGame};
+
+/// Plugin for the pause overlay shared by every mini-game. Its settings
+/// button opens the full settings screen over the paused game.
+pub struct PausePlugin;
+
+impl Plugin for PausePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            pause_input.run_if(
+                in_state(GameState::Playing)
+                    .and(not(resource_exists::<ReplayPlayback>))
+                    .and(not(in_state(PauseMenuState::Settings))),
+            ),
+        )
+        .add_systems(OnEnter(PauseState::Paused), freeze_virtual_time)
+        .add_systems(OnExit(PauseState::Paused), resume_virtual_time)
+        .add_systems(OnEnter(PauseMenuState::Main), setup_pause_overlay)
+        .add_systems(
+            Update,
+            (pause_button_interaction, pause_button_action)
+                .chain()
+                .run_if(in_state(PauseMenuState::Main)),
+        );
+    }
+}
+
+// ─── Components ────────────────────────────────────────────────────
+
+#[derive(Component, Clone, Copy)]
+enum PauseButton {
+    Resume,
+    Restart,
+    Settings,
+    QuitToEra,
+}
+
+// ─── Setup ─────────────────────────────────────────────────────────
+
+fn setup_pause_overlay(mut commands: Commands) {
+    commands
+        .spawn((
+            StateCleanup(PauseMenuState::Main),
+            GlobalZIndex(10),
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+        ))
+        .with_children(|root| {
+            spawn_panel(
+                root,
+                "PAUSED",
+                &[
+                    ("RESUME", PauseButton::Resume),
+                    ("RESTART", PauseButton::Restart),
+                    ("SETTINGS", PauseButton::Settings),
+                    ("QUIT TO ERA", PauseButton::QuitToEra),
+                ],
+            );
+        });
+}
+
+fn spawn_panel(root: &mut ChildSpawnerCommands, title: &str, buttons: &[(&str, PauseButton)]) {
+    root.spawn((
+        Node {
+            width: Val::Px(360.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            padding: UiRect::all(Val::Px(30.0)),
+            border: UiRect::all(Val::Px(2.0)),
+            row_gap: Val::Px(12.0),
+            border_radius: BorderRadius::all(Val::Px(12.0)),
+            ..default()
+        },
+        BackgroundColor(colors::PANEL_BG),
+        BorderColor::all(colors::PANEL_BORDER),
+    ))
+    .with_children(|panel| {
+        panel.spawn((
+            Text::new(title),
+            TextFont {
+                font_size: 36.0,
+                ..default()
+            },
+            TextColor(colors::TEXT_ACCENT),
+        ));
+
+        for &(label, action) in buttons {
+            panel
+                .spawn((
+                    action,
+                    Button,
+                    Node {
+                        width: Val::Px(250.0),
+                        height: Val::Px(44.0),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        border: UiRect::all(Val::Px(2.0)),
+                        border_radius: BorderRadius::all(Val::Px(4.0)),
+                        ..default()
+                    },
+                    BackgroundColor(colors::BUTTON_NORMAL),
+                    BorderColor::all(colors::PANEL_BORDER),
+                ))
+                .with_children(|btn| {
+                    btn.spawn((
+                        Text::new(label),
+                        TextFont {
+                            font_size: 18.0,
+                            ..default()
+                        },
+                        TextColor(colors::TEXT_PRIMARY),
+                    ));
+                });
+        }
+
+        panel.spawn((
+            Text::new("Esc / P to resume"),
+            TextFont {
+                font_size: 14.0,
+                ..default()
+            },
+            TextColor(colors::TEXT_SECONDARY),
+        ));
+    });
+}
+
+// ─── Time ──────────────────────────────────────────────────────────
+
+fn freeze_virtual_time(mut time: ResMut<Time<Virtual>>) {
+    time.pause();
+}
+
+fn resume_virtual_time(mut time: ResMut<Time<Virtual>>) {
+    time.unpause();
+}
+
+// ─── Interaction ───────────────────────────────────────────────────
+
+/// The pause action (Esc, P or Start) toggles pause. While the settings
+/// screen is open it handles Esc itself, going back to the pause menu.
+fn pause_input(
+    input: Res<GameInput>,
+    state: Res<State<PauseState>>,
+    mut next_state: ResMut<NextState<PauseState>>,
+) {
+    match state.get() {
+        PauseState::Running if input.pause => next_state.set(PauseState::Paused),
+        PauseState::Paused if input.pause => next_state.set(PauseState::Running),
+        _ => {}
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn pause_button_interaction(
+    mut query: Query<
+        (&Interaction, &mut BackgroundColor, &mut BorderColor),
+        (Changed<Interaction>, With<PauseButton>),
+    >,
+) {
+    for (interaction, mut bg, mut border) in &mut query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg = BackgroundColor(colors::BUTTON_PRESSED);
+                *border = BorderColor::all(colors::TEXT_ACCENT);
+            }
+            Interaction::Hovered => {
+                *bg = BackgroundColor(colors::BUTTON_HOVER);
+                *border = BorderColor::all(colors::EGA_BRIGHT_CYAN);
+            }
+            Interaction::None => {
+                *bg = BackgroundColor(colors::BUTTON_NORMAL);
+                *border = BorderColor::all(colors::PANEL_BORDER);
+            }
+        }
+    }
+}
+
+fn pause_button_action(
+    query: Query<(&Interaction, &PauseButton), Changed<Interaction>>,
+    mut next_state: ResMut<NextState<PauseState>>,
+    mut next_menu: ResMut<NextState<PauseMenuState>>,
+    mut restart: MessageWriter<RestartMiniGame>,
+    mut quit: MessageWriter<QuitMiniGame>,
+) {
+    for (interaction, button) in &query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match button {
+            PauseButton::Resume => next_state.set(PauseState::Running),
+            PauseButton::Restart => {
+                restart.write(RestartMiniGame);
+                next_state.set(PauseState::Running);
+            }
+            PauseButton::Settings => next_menu.set(PauseMenuState::Settings),
+            PauseButton::QuitToEra => {
+                quit.write(QuitMiniGame);
+            }
+        }
+    }
+}
@@ -0,0 +1,448 @@
+use bevy::input::ButtonState;
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::prelude::*;
+
+use super::{StateCleanup, colors};
+use crate::core::profiles::{MAX_PROFILE_NAME_LEN, ProfileError, ProfileId, ProfileStore};
+use crate::core::progression::PlayerProgress;
+use crate::core::states::MenuState;
+
+/// Plugin for the profile picker, opened from the main menu.
+pub struct ProfilesPlugin;
+
+impl Plugin for ProfilesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ProfileScreen>()
+            .add_systems(OnEnter(MenuState::Profiles), setup_profiles)
+            .add_systems(
+                Update,
+                (
+                    profile_row_action,
+                    profile_button_action,
+                    profile_keyboard,
+                    rebuild_profile_list,
+                    update_profile_status,
+                )
+                    .chain()
+                    .run_if(in_state(MenuState::Profiles)),
+            );
+    }
+}
+
+// ─── Resources ─────────────────────────────────────────────────────
+
+#[derive(Resource, Default)]
+struct ProfileScreen {
+    editing: Option<NameEdit>,
+    message: String,
+    dirty: bool,
+}
+
+struct NameEdit {
+    target: EditTarget,
+    buffer: String,
+}
+
+#[derive(Clone, Copy)]
+enum EditTarget {
+    Create,
+    Rename(ProfileId),
+    Copy(ProfileId),
+}
+
+// ─── Components ────────────────────────────────────────────────────
+
+#[derive(Component)]
+struct ProfileList;
+
+#[derive(Component)]
+struct ProfileRow(ProfileId);
+
+#[derive(Component)]
+struct ProfileStatusText;
+
+#[derive(Component, Clone, Copy)]
+enum ProfileButton {
+    New,
+    Rename,
+    Copy,
+    Delete,
+    Back,
+}
+
+// ─── Setup ─────────────────────────────────────────────────────────
+
+fn setup_profiles(mut commands: Commands, mut screen: ResMut<ProfileScreen>) {
+    *screen = ProfileScreen {
+        dirty: true,
+        ..default()
+    };
+
+    commands
+        .spawn((
+            StateCleanup(MenuState::Profiles),
+            GlobalZIndex(10),
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.85)),
+        ))
+        .with_children(|root| {
+            root.spawn((
+                Node {
+                    width: Val::Px(560.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    padding: UiRect::all(Val::Px(30.0)),
+                    border: UiRect::all(Val::Px(2.0)),
+                    row_gap: Val::Px(15.0),
+                    border_radius: BorderRadius::all(Val::Px(12.0)),
+                    ..default()
+                },
+                BackgroundColor(colors::PANEL_BG),
+                BorderColor::all(colors::PANEL_BORDER),
+            ))
+            .with_children(|panel| {
+                panel.spawn((
+                    Text::new("PROFILES"),
+                    TextFont {
+                        font_size: 36.0,
+                        ..default()
+                    },
+                    TextColor(colors::TEXT_ACCENT),
+                ));
+
+                panel.spawn((
+                    ProfileList,
+                    Node {
+                        width: Val::Percent(100.0),
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Val::Px(8.0),
+                        ..default()
+                    },
+                ));
+
+                panel.spawn((
+                    ProfileStatusText,
+                    Text::new(""),
+                    TextFont {
+                        font_size: 18.0,
+                        ..default()
+                    },
+                    TextColor(colors::EGA_BRIGHT_YELLOW),
+                ));
+
+                panel
+                    .spawn(Node {
+                        flex_direction: FlexDirection::Row,
+                        column_gap: Val::Px(8.0),
+                        ..default()
+                    })
+                    .with_children(|row| {
+                        let buttons = [
+                            ("NEW", ProfileButton::New),
+                            ("RENAME", ProfileButton::Rename),
+                            ("COPY", ProfileButton::Copy),
+                            ("DELETE", ProfileButton::Delete),
+                            ("BACK", ProfileButton::Back),
+                        ];
+                        for (label, action) in buttons {
+                            row.spawn((
+                                action,
+                                Button,
+                                Node {
+                                    width: Val::Px(96.0),
+                                    height: Val::Px(40.0),
+                                    justify_content: JustifyContent::Center,
+                                    align_items: AlignItems::Center,
+                                    border: UiRect::all(Val::Px(2.0)),
+                                    border_radius: BorderRadius::all(Val::Px(4.0)),
+                                    ..default()
+                                },
+                                BackgroundColor(colors::BUTTON_NORMAL),
+                                BorderColor::all(colors::PANEL_BORDER),
+                            ))
+                            .with_children(|btn| {
+                                btn.spawn((
+                                    Text::new(label),
+                                    TextFont {
+                                        font_size: 16.0,
+                                        ..default()
+                                    },
+                                    TextColor(colors::TEXT_PRIMARY),
+                                ));
+                            });
+                        }
+                    });
+
+                panel.spawn((
+                    Text::new("Click a profile to play as it — Esc to go back"),
+                    TextFont {
+                        font_size: 14.0,
+                        ..default()
+                    },
+                    TextColor(colors::TEXT_SECONDARY),
+                ));
+            });
+        });
+}
+
+fn rebuild_profile_list(
+    mut commands: Commands,
+    mut screen: ResMut<ProfileScreen>,
+    store: Option<Res<ProfileStore>>,
+    list_query: Query<Entity, With<ProfileList>>,
+) {
+    if !screen.dirty {
+        return;
+    }
+    let Ok(list) = list_query.single() else {
+        return;
+    };
+    screen.dirty = false;
+    commands.entity(list).despawn_children();
+
+    let Some(store) = store else {
+        screen.message = "Profiles are unavailable — see log".to_string();
+        return;
+    };
+    let active = store.active().map(|p| p.id);
+
+    commands.entity(list).with_children(|list| {
+        for profile in store.profiles() {
+            let is_active = Some(profile.id) == active;
+            let label = if is_active {
+                format!("▶ {}", profile.name)
+            } else {
+                profile.name.clone()
+            };
+            list.spawn((
+                ProfileRow(profile.id),
+                Button,
+                Node {
+                    width: Val::Px(360.0),
+                    height: Val::Px(40.0),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    border: UiRect::all(Val::Px(2.0)),
+                    border_radius: BorderRadius::all(Val::Px(4.0)),
+                    ..default()
+                },
+                BackgroundColor(colors::BUTTON_NORMAL),
+                BorderColor::all(colors::PANEL_BORDER),
+            ))
+            .with_children(|btn| {
+                btn.spawn((
+                    Text::new(label),
+                    TextFont {
+                        font_size: 20.0,
+                        ..default()
+                    },
+                    TextColor(if is_active {
+                        colors::GOLD
+                    } else {
+                        colors::TEXT_PRIMARY
+                    }),
+                ));
+            });
+        }
+    });
+}
+
+// ─── Interaction systems ───────────────────────────────────────────
+
+fn profile_row_action(
+    query: Query<(&Interaction, &ProfileRow), Changed<Interaction>>,
+    mut screen: ResMut<ProfileScreen>,
+    store: Option<ResMut<ProfileStore>>,
+    mut progress: ResMut<PlayerProgress>,
+) {
+    let Some(mut store) = store else {
+        return;
+    };
+    if screen.editing.is_some() {
+        return;
+    }
+    for (interaction, row) in &query {
+        if *interaction == Interaction::Pressed {
+            screen.message = match switch_profile(&mut store, &mut progress, row.0) {
+                Ok(name) => format!("Playing as {name}"),
+                Err(err) => err.to_string(),
+            };
+            screen.dirty = true;
+        }
+    }
+}
+
+fn profile_button_action(
+    query: Query<(&Interaction, &ProfileButton), Changed<Interaction>>,
+    mut screen: ResMut<ProfileScreen>,
+    mut store: Option<ResMut<ProfileStore>>,
+    mut progress: ResMut<PlayerProgress>,
+    mut next_menu: ResMut<NextState<MenuState>>,
+) {
+    for (interaction, button) in &query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        if let ProfileButton::Back = button {
+            next_menu.set(MenuState::Main);
+            continue;
+        }
+        let Some(store) = store.as_deref_mut() else {
+            continue;
+        };
+        let active = store.active().cloned();
+
+        match (button, active) {
+            (ProfileButton::New, _) => {
+                screen.editing = Some(NameEdit {
+                    target: EditTarget::Create,
+                    buffer: store.unused_name("Player"),
+                });
+            }
+            (ProfileButton::Rename, Some(active)) => {
+                screen.editing = Some(NameEdit {
+                    target: EditTarget::Rename(active.id),
+                    buffer: active.name,
+                });
+            }
+            (ProfileButton::Copy, Some(active)) => {
+                let base: String = active.name.chars().take(MAX_PROFILE_NAME_LEN - 3).collect();
+                screen.editing = Some(NameEdit {
+                    target: EditTarget::Copy(active.id),
+                    buffer: store.unused_name(&base),
+                });
+            }
+            (ProfileButton::Delete, Some(active)) => {
+                if store.profiles().len() <= 1 {
+                    screen.message = "Can't delete the last profile".to_string();
+                    continue;
+                }
+                screen.message = match delete_profile(store, &mut progress, active.id) {
+                    Ok(()) => format!("Deleted {}", active.name),
+                    Err(err) => err.to_string(),
+                };
+                screen.dirty = true;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Keyboard handling: typing a name while editing, Esc to leave otherwise.
+fn profile_keyboard(
+    mut keys: MessageReader<KeyboardInput>,
+    mut screen: ResMut<ProfileScreen>,
+    mut store: Option<ResMut<ProfileStore>>,
+    mut next_menu: ResMut<NextState<MenuState>>,
+) {
+    for key in keys.read() {
+        if key.state != ButtonState::Pressed {
+            continue;
+        }
+
+        let Some(edit) = screen.editing.as_mut() else {
+            if key.logical_key == Key::Escape {
+                next_menu.set(MenuState::Main);
+            }
+            continue;
+        };
+
+        match &key.logical_key {
+            Key::Escape => screen.editing = None,
+            Key::Backspace => {
+                edit.buffer.pop();
+            }
+            Key::Enter => {
+                let Some(store) = store.as_deref_mut() else {
+                    screen.editing = None;
+                    continue;
+                };
+                let name = edit.buffer.clone();
+                let result = match edit.target {
+                    EditTarget::Create => store.create(&name).map(|_| ()),
+                    EditTarget::Rename(id) => store.rename(id, &name),
+                    EditTarget::Copy(id) => store.copy(id, &name).map(|_| ()),
+                };
+                match result {
+                    Ok(()) => {
+                        screen.message = format!("Saved {}", name.trim());
+                        screen.editing = None;
+                        screen.dirty = true;
+                    }
+                    // Keep the editor open so the name can be fixed.
+                    Err(err) => screen.message = err.to_string(),
+                }
+            }
+            Key::Character(text) => {
+                for c in text.chars() {
+                    if edit.buffer.chars().count() < MAX_PROFILE_NAME_LEN
+                        && (c.is_alphanumeric() || c == ' ' || c == '-' || c == '_')
+                    {
+                        edit.buffer.push(c);
+                    }
+                }
+            }
+            Key::Space if edit.buffer.chars().count() < MAX_PROFILE_NAME_LEN => {
+                edit.buffer.push(' ');
+            }
+            _ => {}
+        }
+    }
+}
+
+fn update_profile_status(
+    screen: Res<ProfileScreen>,
+    mut query: Query<&mut Text, With<ProfileStatusText>>,
+) {
+    if !screen.is_changed() {
+        return;
+    }
+    let status = match &screen.editing {
+        Some(edit) => {
+            let verb = match edit.target {
+                EditTarget::Create => "New profile",
+                EditTarget::Rename(_) => "Rename to",
+                EditTarget::Copy(_) => "Copy as",
+            };
+            format!("{verb}: {}_  (Enter to save, Esc to cancel)", edit.buffer)
+        }
+        None => screen.message.clone(),
+    };
+    for mut text in &mut query {
+        **text = status.clone();
+    }
+}
+
+// ─── Helpers ───────────────────────────────────────────────────────
+
+/// Make `id` active and load its progress into the running game.
+fn switch_profile(
+    store: &mut ProfileStore,
+    progress: &mut PlayerProgress,
+    id: ProfileId,
+) -> Result<String, ProfileError> {
+    progress.replace_with(store.load_progress(id)?);
+    store.set_active(id)?;
+    Ok(store.get(id).map(|p| p.name.clone()).unwrap_or_default())
+}
+
+/// Delete a profile; if it was active, load whichever profile replaced it.
+fn delete_profile(
+    store: &mut ProfileStore,
+    progress: &mut PlayerProgress,
+    id: ProfileId,
+) -> Result<(), ProfileError> {
+    let was_active = store.active().map(|p| p.id) == Some(id);
+    store.delete(id)?;
+    if was_active && let Some(next) = store.active().map(|p| p.id) {
+        progress.replace_with(store.load_progress(next)?);
+    }
+    Ok(())
+}
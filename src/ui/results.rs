@@ -1,7 +1,20 @@
 use bevy::prelude::*;
+use std::fs;
+use std::io;
+use std::path::Path;
 
-use super::colors;
-use crate::core::states::GameState;
+use super::attract::DemoRun;
+use super::{StateCleanup, colors};
+use crate::core::input::GameInput;
+use crate::core::leaderboard::{self, INITIALS_LEN, LeaderboardEntry, cycle_letter};
+use crate::core::progression::{
+    DataDir, MiniGameFinished, MiniGameOutcome, PlayerProgress, SaveProgress,
+};
+use crate::core::registry::GameRegistry;
+use crate::core::replay::{LastReplay, ReplayPlayback, ReplayVerdict, kept_replay_path};
+use crate::core::rng::GameRng;
+use crate::core::states::{GameState, MiniGameId, PlayingState, ResultsState};
+use crate::core::stats::RunClock;
 
 /// Plugin for the results / score screen shown after a mini-game.
 pub struct ResultsPlugin;
@@ -9,17 +22,28 @@ pub struct ResultsPlugin;
 impl Plugin for ResultsPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<GameResults>()
-            .add_systems(OnEnter(GameState::Results), setup_results)
+            .init_resource::<InitialsEntry>()
+            // After every game's `Update` systems, so a run is recorded the
+            // frame it ends, while its replay playback is still around.
+            .add_systems(
+                PostUpdate,
+                record_finished_game.run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(
+                OnEnter(GameState::Results),
+                (keep_leaderboard_replay, setup_results),
+            )
             .add_systems(
                 Update,
                 (
                     results_input,
+                    initials_input,
+                    update_initials,
                     results_button_interaction,
                     results_button_action,
                 )
-                    .run_if(in_state(GameState::Results)),
-            )
-            .add_systems(OnExit(GameState::Results), cleanup_results);
+                    .run_if(in_state(ResultsState::Summary)),
+            );
     }
 }
 
@@ -28,33 +52,221 @@ impl Plugin for ResultsPlugin {
 /// Data to display on the results screen.
 #[derive(Resource, Default)]
 pub struct GameResults {
+    pub game: Option<MiniGameId>,
     pub game_name: String,
+    pub outcome: Option<MiniGameOutcome>,
     pub score: u64,
     pub high_score: u64,
     pub is_new_high: bool,
     pub tokens_earned: u64,
     pub completed: bool,
     pub newly_completed: bool,
+    /// Seed of the run, for reproducing it with `--seed`.
+    pub seed: u64,
+    /// Set when the run was a watched replay rather than a live run.
+    pub replay: Option<ReplayVerdict>,
+    /// Where the run landed on its game's leaderboard, if it made it on.
+    pub leaderboard_rank: Option<usize>,
 }
 
-// ─── Components ────────────────────────────────────────────────────
+/// The three-letter initials entry shown when a run makes the leaderboard.
+/// Up and down change the letter under the cursor, left and right move the
+/// cursor, and confirm on the last letter signs the entry.
+#[derive(Resource, Default)]
+struct InitialsEntry {
+    letters: [char; INITIALS_LEN],
+    cursor: usize,
+    /// Still waiting for the player to sign.
+    active: bool,
+}
 
-#[derive(Component)]
-struct ResultsRoot;
+// ─── Components ────────────────────────────────────────────────────
 
 #[derive(Component)]
 enum ResultsButton {
     Retry,
+    WatchReplay,
+    Leaderboards,
     BackToEra,
     BackToTimeline,
 }
 
+#[derive(Component)]
+struct InitialsLetter(usize);
+
+#[derive(Component)]
+struct InitialsHint;
+
+// ─── Recording ─────────────────────────────────────────────────────
+
+/// Records the first finished run of the frame into `PlayerProgress` and
+/// its stats, saves it, and only then switches to the results screen. Runs
+/// quit from the pause menu only count towards the stats: they earn no
+/// tokens, scores or leaderboard place, and go back to the era instead.
+/// Watched replays only report how they compared with the recording, and
+/// attract-mode demos are left to `attract`.
+#[allow(clippy::too_many_arguments)]
+fn record_finished_game(
+    mut finished: MessageReader<MiniGameFinished>,
+    demo: Option<Res<DemoRun>>,
+    playback: Option<Res<ReplayPlayback>>,
+    rng: Res<GameRng>,
+    clock: Res<RunClock>,
+    dir: Res<DataDir>,
+    mut progress: ResMut<PlayerProgress>,
+    mut save: MessageWriter<SaveProgress>,
+    registry: Res<GameRegistry>,
+    mut results: ResMut<GameResults>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let Some(run) = finished.read().next().copied() else {
+        return;
+    };
+    finished.clear();
+
+    if demo.is_some() {
+        return;
+    }
+    if let Some(playback) = playback {
+        *results = GameResults {
+            game: Some(run.game),
+            game_name: registry.name(run.game).to_string(),
+            outcome: Some(run.outcome),
+            score: run.score,
+            high_score: progress.high_score(run.game),
+            seed: rng.seed(),
+            replay: Some(playback.verdict(run.score)),
+            ..default()
+        };
+        next_state.set(GameState::Results);
+        return;
+    }
+
+    let quit = run.outcome == MiniGameOutcome::Quit;
+    let previous_high = progress.high_score(run.game);
+    let was_completed = progress.is_game_completed(run.game);
+    let tokens_earned = if quit {
+        0
+    } else {
+        progress.record_result(&registry, run.game, run.score)
+    };
+    progress.stats.record_run(run.game, run.outcome, clock.0);
+    let leaderboard_rank = if quit {
+        None
+    } else {
+        let entry = LeaderboardEntry {
+            initials: progress.initials().to_string(),
+            score: run.score,
+            date: leaderboard::now(),
+            seed: rng.seed(),
+            replay: None,
+        };
+        progress
+            .leaderboard_mut(run.game)
+            .insert(entry)
+            .map(|placement| {
+                if let Some(name) = placement.bumped.and_then(|bumped| bumped.replay) {
+                    remove_kept_replay(&dir.0, &name);
+                }
+                placement.rank
+            })
+    };
+    save.write(SaveProgress);
+
+    info!(
+        "{} finished ({:?}) with score {}",
+        registry.name(run.game),
+        run.outcome,
+        run.score
+    );
+
+    *results = GameResults {
+        game: Some(run.game),
+        game_name: registry.name(run.game).to_string(),
+        outcome: Some(run.outcome),
+        score: run.score,
+        high_score: progress.high_score(run.game),
+        is_new_high: !quit && run.score > previous_high,
+        tokens_earned,
+        completed: progress.is_game_completed(run.game),
+        newly_completed: !was_completed && progress.is_game_completed(run.game),
+        seed: rng.seed(),
+        replay: None,
+        leaderboard_rank,
+    };
+
+    next_state.set(if quit {
+        GameState::EraSelect
+    } else {
+        GameState::Results
+    });
+}
+
+/// Keep a copy of the recording behind a new leaderboard entry. The latest
+/// replay of each game is overwritten by the next run, so the entry points
+/// at its own file instead.
+fn keep_leaderboard_replay(
+    results: Res<GameResults>,
+    last_replay: Option<Res<LastReplay>>,
+    dir: Res<DataDir>,
+    mut progress: ResMut<PlayerProgress>,
+    mut save: MessageWriter<SaveProgress>,
+) {
+    let (Some(game), Some(rank)) = (results.game, results.leaderboard_rank) else {
+        return;
+    };
+    let Some(LastReplay(replay)) = last_replay.as_deref() else {
+        return;
+    };
+    if replay.game != game || replay.seed != results.seed || replay.score != results.score {
+        return;
+    }
+    let Some(entry) = progress.leaderboard_mut(game).get_mut(rank) else {
+        return;
+    };
+    let name = format!(
+        "{}-{}-{}-{}.json",
+        game.era as u8, game.index, entry.date, entry.seed
+    );
+    if let Err(err) = replay.write(&kept_replay_path(&dir.0, &name)) {
+        error!("Failed to keep replay: {}", err);
+        return;
+    }
+    entry.replay = Some(name);
+
+    save.write(SaveProgress);
+}
+
+fn remove_kept_replay(dir: &Path, name: &str) {
+    match fs::remove_file(kept_replay_path(dir, name)) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => {
+            warn!("Failed to remove replay {}: {}", name, err)
+        }
+        _ => {}
+    }
+}
+
 // ─── Setup ─────────────────────────────────────────────────────────
 
-fn setup_results(mut commands: Commands, results: Res<GameResults>) {
+fn setup_results(
+    mut commands: Commands,
+    results: Res<GameResults>,
+    progress: Res<PlayerProgress>,
+    last_replay: Option<Res<LastReplay>>,
+    mut initials: ResMut<InitialsEntry>,
+) {
+    let can_watch_replay = last_replay.is_some_and(|last| Some(last.0.game) == results.game);
+
+    *initials = InitialsEntry::default();
+    if results.leaderboard_rank.is_some() {
+        let mut letters = progress.initials().chars();
+        initials.letters = std::array::from_fn(|_| letters.next().unwrap_or('A'));
+        initials.active = true;
+    }
+
     commands
         .spawn((
-            ResultsRoot,
+            StateCleanup(GameState::Results),
             Node {
                 width: Val::Percent(100.0),
                 height: Val::Percent(100.0),
@@ -82,7 +294,9 @@ fn setup_results(mut commands: Commands, results: Res<GameResults>) {
                 .insert(BorderColor::all(colors::PANEL_BORDER))
                 .with_children(|panel| {
                     // Title
-                    let title = if results.newly_completed {
+                    let title = if results.replay.is_some() {
+                        "REPLAY"
+                    } else if results.newly_completed {
                         "🎉 GAME COMPLETED! 🎉"
                     } else {
                         "GAME OVER"
@@ -131,8 +345,30 @@ fn setup_results(mut commands: Commands, results: Res<GameResults>) {
                         TextColor(colors::EGA_BRIGHT_YELLOW),
                     ));
 
-                    // High score
-                    if results.is_new_high {
+                    // Replay check, or high score and tokens for a live run
+                    if let Some(verdict) = results.replay {
+                        let (text, color) = match verdict {
+                            ReplayVerdict::Match => (
+                                "Replay matched the recorded run".to_string(),
+                                colors::TEXT_ACCENT,
+                            ),
+                            ReplayVerdict::Desync { expected, .. } => (
+                                format!("DESYNC — recorded score was {}", expected),
+                                colors::EGA_BRIGHT_RED,
+                            ),
+                            ReplayVerdict::Stopped => {
+                                ("Replay stopped".to_string(), colors::TEXT_SECONDARY)
+                            }
+                        };
+                        panel.spawn((
+                            Text::new(text),
+                            TextFont {
+                                font_size: 18.0,
+                                ..default()
+                            },
+                            TextColor(color),
+                        ));
+                    } else if results.is_new_high {
                         panel.spawn((
                             Text::new("★ NEW HIGH SCORE! ★"),
                             TextFont {
@@ -153,13 +389,79 @@ fn setup_results(mut commands: Commands, results: Res<GameResults>) {
                     }
 
                     // Tokens earned
+                    if results.replay.is_none() {
+                        panel.spawn((
+                            Text::new(format!("🪙 +{} Tokens", results.tokens_earned)),
+                            TextFont {
+                                font_size: 20.0,
+                                ..default()
+                            },
+                            TextColor(colors::GOLD),
+                        ));
+                    }
+
+                    // Initials entry
+                    if let Some(rank) = results.leaderboard_rank {
+                        panel.spawn((
+                            Text::new(format!("#{} ON THE LEADERBOARD", rank + 1)),
+                            TextFont {
+                                font_size: 18.0,
+                                ..default()
+                            },
+                            TextColor(colors::GOLD),
+                        ));
+                        panel
+                            .spawn(Node {
+                                column_gap: Val::Px(8.0),
+                                ..default()
+                            })
+                            .with_children(|row| {
+                                for index in 0..INITIALS_LEN {
+                                    row.spawn((
+                                        InitialsLetter(index),
+                                        Node {
+                                            width: Val::Px(40.0),
+                                            height: Val::Px(48.0),
+                                            justify_content: JustifyContent::Center,
+                                            align_items: AlignItems::Center,
+                                            border: UiRect::all(Val::Px(2.0)),
+                                            border_radius: BorderRadius::all(Val::Px(4.0)),
+                                            ..default()
+                                        },
+                                        BackgroundColor(colors::BUTTON_NORMAL),
+                                        BorderColor::all(colors::PANEL_BORDER),
+                                    ))
+                                    .with_children(|slot| {
+                                        slot.spawn((
+                                            Text::new(""),
+                                            TextFont {
+                                                font_size: 28.0,
+                                                ..default()
+                                            },
+                                            TextColor(colors::EGA_BRIGHT_YELLOW),
+                                        ));
+                                    });
+                                }
+                            });
+                        panel.spawn((
+                            InitialsHint,
+                            Text::new(""),
+                            TextFont {
+                                font_size: 14.0,
+                                ..default()
+                            },
+                            TextColor(colors::TEXT_SECONDARY),
+                        ));
+                    }
+
+                    // Seed
                     panel.spawn((
-                        Text::new(format!("🪙 +{} Tokens", results.tokens_earned)),
+                        Text::new(format!("Seed: {}", results.seed)),
                         TextFont {
-                            font_size: 20.0,
+                            font_size: 14.0,
                             ..default()
                         },
-                        TextColor(colors::GOLD),
+                        TextColor(colors::TEXT_SECONDARY),
                     ));
 
                     // Divider
@@ -210,6 +512,64 @@ fn setup_results(mut commands: Commands, results: Res<GameResults>) {
                                     ));
                                 });
 
+                            // Watch Replay Button
+                            if can_watch_replay {
+                                buttons
+                                    .spawn((
+                                        ResultsButton::WatchReplay,
+                                        Button,
+                                        Node {
+                                            width: Val::Px(250.0),
+                                            height: Val::Px(44.0),
+                                            justify_content: JustifyContent::Center,
+                                            align_items: AlignItems::Center,
+                                            border: UiRect::all(Val::Px(2.0)),
+                                            border_radius: BorderRadius::all(Val::Px(4.0)),
+                                            ..default()
+                                        },
+                                        BackgroundColor(colors::BUTTON_NORMAL),
+                                        BorderColor::all(colors::PANEL_BORDER),
+                                    ))
+                                    .with_children(|btn| {
+                                        btn.spawn((
+                                            Text::new("📼  WATCH REPLAY"),
+                                            TextFont {
+                                                font_size: 18.0,
+                                                ..default()
+                                            },
+                                            TextColor(colors::TEXT_PRIMARY),
+                                        ));
+                                    });
+                            }
+
+                            // Leaderboards Button
+                            buttons
+                                .spawn((
+                                    ResultsButton::Leaderboards,
+                                    Button,
+                                    Node {
+                                        width: Val::Px(250.0),
+                                        height: Val::Px(44.0),
+                                        justify_content: JustifyContent::Center,
+                                        align_items: AlignItems::Center,
+                                        border: UiRect::all(Val::Px(2.0)),
+                                        border_radius: BorderRadius::all(Val::Px(4.0)),
+                                        ..default()
+                                    },
+                                    BackgroundColor(colors::BUTTON_NORMAL),
+                                    BorderColor::all(colors::PANEL_BORDER),
+                                ))
+                                .with_children(|btn| {
+                                    btn.spawn((
+                                        Text::new("🏆  LEADERBOARDS"),
+                                        TextFont {
+                                            font_size: 18.0,
+                                            ..default()
+                                        },
+                                        TextColor(colors::TEXT_PRIMARY),
+                                    ));
+                                });
+
                             // Back to Era Button
                             buttons
                                 .spawn((
@@ -283,6 +643,92 @@ fn results_input(
     }
 }
 
+fn initials_input(
+    input: Res<GameInput>,
+    mut initials: ResMut<InitialsEntry>,
+    results: Res<GameResults>,
+    mut progress: ResMut<PlayerProgress>,
+    mut save: MessageWriter<SaveProgress>,
+) {
+    if !initials.active {
+        return;
+    }
+    let cursor = initials.cursor;
+    if input.just_up {
+        initials.letters[cursor] = cycle_letter(initials.letters[cursor], 1);
+    } else if input.just_down {
+        initials.letters[cursor] = cycle_letter(initials.letters[cursor], -1);
+    } else if input.just_left {
+        initials.cursor = cursor.saturating_sub(1);
+    } else if input.just_right {
+        initials.cursor = (cursor + 1).min(INITIALS_LEN - 1);
+    } else if input.confirm {
+        if cursor + 1 < INITIALS_LEN {
+            initials.cursor += 1;
+            return;
+        }
+        initials.active = false;
+        sign_entry(&initials, &results, &mut progress, &mut save);
+    }
+}
+
+/// Put the entered initials on the run's leaderboard entry and remember
+/// them for next time, then save.
+fn sign_entry(
+    initials: &InitialsEntry,
+    results: &GameResults,
+    progress: &mut PlayerProgress,
+    save: &mut MessageWriter<SaveProgress>,
+) {
+    let (Some(game), Some(rank)) = (results.game, results.leaderboard_rank) else {
+        return;
+    };
+    let signed: String = initials.letters.iter().collect();
+    if let Some(entry) = progress.leaderboard_mut(game).get_mut(rank) {
+        entry.initials = signed.clone();
+    }
+    progress.last_initials = Some(signed);
+
+    save.write(SaveProgress);
+}
+
+fn update_initials(
+    initials: Res<InitialsEntry>,
+    mut slots: Query<(
+        &InitialsLetter,
+        &Children,
+        &mut BackgroundColor,
+        &mut BorderColor,
+    )>,
+    mut texts: Query<&mut Text, Without<InitialsHint>>,
+    mut hint: Query<&mut Text, With<InitialsHint>>,
+    added: Query<(), Added<InitialsHint>>,
+) {
+    if !initials.is_changed() && added.is_empty() {
+        return;
+    }
+    for (slot, children, mut bg, mut border) in &mut slots {
+        if let Some(mut text) = children.first().and_then(|c| texts.get_mut(*c).ok()) {
+            **text = initials.letters[slot.0].to_string();
+        }
+        let (background, edge) = if initials.active && slot.0 == initials.cursor {
+            (colors::BUTTON_HOVER, colors::EGA_BRIGHT_CYAN)
+        } else {
+            (colors::BUTTON_NORMAL, colors::PANEL_BORDER)
+        };
+        *bg = BackgroundColor(background);
+        *border = BorderColor::all(edge);
+    }
+    if let Ok(mut text) = hint.single_mut() {
+        **text = if initials.active {
+            "↑↓ letter — ←→ move — Enter sign".to_string()
+        } else {
+            "Signed!".to_string()
+        };
+    }
+}
+
+#[allow(clippy::type_complexity)]
 fn results_button_interaction(
     mut query: Query<
         (&Interaction, &mut BackgroundColor, &mut BorderColor),
@@ -307,16 +753,39 @@ fn results_button_interaction(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn results_button_action(
+    mut commands: Commands,
     query: Query<(&Interaction, &ResultsButton), Changed<Interaction>>,
+    results: Res<GameResults>,
+    last_replay: Option<Res<LastReplay>>,
+    registry: Res<GameRegistry>,
     mut next_state: ResMut<NextState<GameState>>,
+    mut playing_state: ResMut<NextState<PlayingState>>,
+    mut results_state: ResMut<NextState<ResultsState>>,
 ) {
     for (interaction, button) in &query {
         if *interaction == Interaction::Pressed {
             match button {
                 ResultsButton::Retry => {
+                    if let Some(info) = results.game.and_then(|id| registry.get(id)) {
+                        playing_state.set(info.playing_state);
+                    }
                     next_state.set(GameState::Playing);
                 }
+                ResultsButton::WatchReplay => {
+                    let Some(last) = last_replay.as_deref() else {
+                        continue;
+                    };
+                    if let Some(info) = registry.get(last.0.game) {
+                        commands.insert_resource(ReplayPlayback::new(last.0.clone()));
+                        playing_state.set(info.playing_state);
+                        next_state.set(GameState::Playing);
+                    }
+                }
+                ResultsButton::Leaderboards => {
+                    results_state.set(ResultsState::Leaderboards);
+                }
                 ResultsButton::BackToEra => {
                     next_state.set(GameState::EraSelect);
                 }
@@ -327,10 +796,3 @@ fn results_button_action(
         }
     }
 }
-
-fn cleanup_results(mut commands: Commands, query: Query<Entity, With<ResultsRoot>>) {
-    for entity in &query {
-        commands.entity(entity).despawn_children();
-        commands.entity(entity).despawn();
-    }
-}
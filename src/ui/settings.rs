@@ -0,0 +1,677 @@
+use bevy::input::ButtonState;
+use bevy::input::keyboard::KeyboardInput;
+use bevy::prelude::*;
+use bevy::state::state::FreelyMutableState;
+use bevy::ui::FocusPolicy;
+
+use super::{StateCleanup, colors};
+use crate::core::input::{GameAction, GameInput, InputBinding, InputBindings};
+use crate::core::settings::{RESOLUTIONS, UserSettings};
+use crate::core::states::{MenuState, PauseMenuState};
+
+/// Plugin for the settings screen, opened from the main menu or over a
+/// paused mini-game.
+pub struct SettingsScreenPlugin;
+
+impl Plugin for SettingsScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SettingsScreen>();
+        add_settings_screen(app, MenuState::Settings, MenuState::Main);
+        add_settings_screen(app, PauseMenuState::Settings, PauseMenuState::Main);
+    }
+}
+
+/// Show the settings screen while in `open`, going to `back` when the
+/// player leaves it.
+fn add_settings_screen<S: FreelyMutableState + Copy>(app: &mut App, open: S, back: S) {
+    app.add_systems(
+        OnEnter(open),
+        move |commands: Commands, screen: ResMut<SettingsScreen>| {
+            setup_settings(commands, screen, StateCleanup(open));
+        },
+    )
+    .add_systems(
+        Update,
+        (
+            capture_binding,
+            settings_mouse,
+            settings_navigation,
+            move |mut screen: ResMut<SettingsScreen>, mut next_state: ResMut<NextState<S>>| {
+                if std::mem::take(&mut screen.leaving) {
+                    next_state.set(back);
+                }
+            },
+            rebuild_settings_list,
+            update_settings_text,
+            style_settings_rows,
+        )
+            .chain()
+            .run_if(in_state(open)),
+    );
+}
+
+// ─── Resources ─────────────────────────────────────────────────────
+
+#[derive(Resource, Default)]
+struct SettingsScreen {
+    page: SettingsPage,
+    /// Index into `page.rows()`.
+    selected: usize,
+    /// Action waiting for the player to press its new input.
+    capturing: Option<GameAction>,
+    /// Set when a capture ends, so the input that ended it isn't also
+    /// read as navigation.
+    swallow_input: bool,
+    /// Set when the player backs out, to return to where the screen was
+    /// opened from.
+    leaving: bool,
+    dirty: bool,
+}
+
+impl SettingsScreen {
+    fn selected_row(&self) -> SettingsRow {
+        self.page.rows()[self.selected]
+    }
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+enum SettingsPage {
+    #[default]
+    Audio,
+    Video,
+    Controls,
+}
+
+impl SettingsPage {
+    const ALL: [SettingsPage; 3] = [
+        SettingsPage::Audio,
+        SettingsPage::Video,
+        SettingsPage::Controls,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            SettingsPage::Audio => "AUDIO",
+            SettingsPage::Video => "VIDEO",
+            SettingsPage::Controls => "CONTROLS",
+        }
+    }
+
+    fn rows(self) -> Vec<SettingsRow> {
+        use SettingsRow::*;
+
+        let mut rows = vec![Page];
+        match self {
+            SettingsPage::Audio => rows.extend([MasterVolume, MusicVolume]),
+            SettingsPage::Video => rows.extend([
+                WindowMode,
+                Resolution,
+                Crt,
+                Scanlines,
+                Curvature,
+                Vignette,
+                Brightness,
+                Palette,
+                ScreenShake,
+            ]),
+            SettingsPage::Controls => {
+                rows.extend(GameAction::ALL.map(Binding));
+                rows.push(ResetBindings);
+            }
+        }
+        rows.push(Back);
+        rows
+    }
+
+    /// The page `steps` away, wrapping around.
+    fn cycle(self, steps: i32) -> SettingsPage {
+        let index = Self::ALL.iter().position(|p| *p == self).unwrap_or(0);
+        Self::ALL[cycle_index(index, steps, Self::ALL.len())]
+    }
+}
+
+/// One line on the settings screen.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SettingsRow {
+    Page,
+    MasterVolume,
+    MusicVolume,
+    WindowMode,
+    Resolution,
+    Crt,
+    Scanlines,
+    Curvature,
+    Vignette,
+    Brightness,
+    Palette,
+    ScreenShake,
+    Binding(GameAction),
+    ResetBindings,
+    Back,
+}
+
+impl SettingsRow {
+    fn label(self) -> &'static str {
+        match self {
+            SettingsRow::Page => "PAGE",
+            SettingsRow::MasterVolume => "MASTER VOLUME",
+            SettingsRow::MusicVolume => "MUSIC VOLUME",
+            SettingsRow::WindowMode => "WINDOW MODE",
+            SettingsRow::Resolution => "RESOLUTION",
+            SettingsRow::Crt => "CRT EFFECT",
+            SettingsRow::Scanlines => "SCANLINES",
+            SettingsRow::Curvature => "CURVATURE",
+            SettingsRow::Vignette => "VIGNETTE",
+            SettingsRow::Brightness => "BRIGHTNESS",
+            SettingsRow::Palette => "ERA PALETTE",
+            SettingsRow::ScreenShake => "SCREEN SHAKE",
+            SettingsRow::Binding(action) => action_label(action),
+            SettingsRow::ResetBindings => "RESET CONTROLS",
+            SettingsRow::Back => "BACK",
+        }
+    }
+
+    /// Whether left/right change this row's value.
+    fn adjustable(self) -> bool {
+        !matches!(
+            self,
+            SettingsRow::Binding(_) | SettingsRow::ResetBindings | SettingsRow::Back
+        )
+    }
+
+    /// The value a slider row edits, with its minimum, maximum and step.
+    fn slider(self, settings: &mut UserSettings) -> Option<(&mut f32, f32, f32, f32)> {
+        Some(match self {
+            SettingsRow::MasterVolume => (&mut settings.master_volume, 0.0, 1.0, 0.1),
+            SettingsRow::MusicVolume => (&mut settings.music_volume, 0.0, 1.0, 0.1),
+            SettingsRow::Scanlines => (&mut settings.crt_scanlines, 0.0, 1.0, 0.05),
+            SettingsRow::Curvature => (&mut settings.crt_curvature, 0.0, 0.1, 0.01),
+            SettingsRow::Vignette => (&mut settings.crt_vignette, 0.0, 1.0, 0.05),
+            SettingsRow::Brightness => (&mut settings.crt_brightness, 0.5, 1.5, 0.05),
+            SettingsRow::ScreenShake => (&mut settings.screen_shake, 0.0, 1.0, 0.25),
+            _ => return None,
+        })
+    }
+}
+
+// ─── Components ────────────────────────────────────────────────────
+
+#[derive(Component)]
+struct SettingsList;
+
+#[derive(Component)]
+struct SettingsHint;
+
+#[derive(Component)]
+struct SettingsRowNode(SettingsRow);
+
+#[derive(Component)]
+struct SettingsValue(SettingsRow);
+
+/// The ◀ / ▶ buttons either side of a value.
+#[derive(Component)]
+struct SettingsArrow(SettingsRow, i32);
+
+// ─── Setup ─────────────────────────────────────────────────────────
+
+fn setup_settings<S: FreelyMutableState>(
+    mut commands: Commands,
+    mut screen: ResMut<SettingsScreen>,
+    cleanup: StateCleanup<S>,
+) {
+    *screen = SettingsScreen {
+        dirty: true,
+        ..default()
+    };
+
+    commands
+        .spawn((
+            cleanup,
+            GlobalZIndex(10),
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.85)),
+        ))
+        .with_children(|root| {
+            root.spawn((
+                Node {
+                    width: Val::Px(640.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    padding: UiRect::all(Val::Px(24.0)),
+                    border: UiRect::all(Val::Px(2.0)),
+                    row_gap: Val::Px(12.0),
+                    border_radius: BorderRadius::all(Val::Px(12.0)),
+                    ..default()
+                },
+                BackgroundColor(colors::PANEL_BG),
+                BorderColor::all(colors::PANEL_BORDER),
+            ))
+            .with_children(|panel| {
+                panel.spawn((
+                    Text::new("SETTINGS"),
+                    TextFont {
+                        font_size: 36.0,
+                        ..default()
+                    },
+                    TextColor(colors::TEXT_ACCENT),
+                ));
+
+                panel.spawn((
+                    SettingsList,
+                    Node {
+                        width: Val::Percent(100.0),
+                        flex_direction: FlexDirection::Column,
+                        row_gap: Val::Px(4.0),
+                        ..default()
+                    },
+                ));
+
+                panel.spawn((
+                    SettingsHint,
+                    Text::new(""),
+                    TextFont {
+                        font_size: 14.0,
+                        ..default()
+                    },
+                    TextColor(colors::TEXT_SECONDARY),
+                ));
+            });
+        });
+}
+
+fn rebuild_settings_list(
+    mut commands: Commands,
+    mut screen: ResMut<SettingsScreen>,
+    list_query: Query<Entity, With<SettingsList>>,
+) {
+    if !screen.dirty {
+        return;
+    }
+    let Ok(list) = list_query.single() else {
+        return;
+    };
+    screen.dirty = false;
+    commands.entity(list).despawn_children();
+
+    commands.entity(list).with_children(|list| {
+        for row in screen.page.rows() {
+            list.spawn((
+                SettingsRowNode(row),
+                Interaction::default(),
+                Node {
+                    width: Val::Percent(100.0),
+                    height: Val::Px(30.0),
+                    justify_content: JustifyContent::SpaceBetween,
+                    align_items: AlignItems::Center,
+                    padding: UiRect::horizontal(Val::Px(12.0)),
+                    border: UiRect::all(Val::Px(2.0)),
+                    border_radius: BorderRadius::all(Val::Px(4.0)),
+                    ..default()
+                },
+                BackgroundColor(colors::BUTTON_NORMAL),
+                BorderColor::all(colors::PANEL_BORDER),
+            ))
+            .with_children(|line| {
+                line.spawn((
+                    Text::new(row.label()),
+                    TextFont {
+                        font_size: 16.0,
+                        ..default()
+                    },
+                    TextColor(colors::TEXT_PRIMARY),
+                ));
+
+                line.spawn(Node {
+                    column_gap: Val::Px(10.0),
+                    align_items: AlignItems::Center,
+                    ..default()
+                })
+                .with_children(|value| {
+                    if row.adjustable() {
+                        spawn_arrow(value, row, -1);
+                    }
+                    value.spawn((
+                        SettingsValue(row),
+                        Text::new(""),
+                        TextFont {
+                            font_size: 16.0,
+                            ..default()
+                        },
+                        TextColor(colors::EGA_BRIGHT_YELLOW),
+                    ));
+                    if row.adjustable() {
+                        spawn_arrow(value, row, 1);
+                    }
+                });
+            });
+        }
+    });
+}
+
+fn spawn_arrow(parent: &mut ChildSpawnerCommands, row: SettingsRow, steps: i32) {
+    parent.spawn((
+        SettingsArrow(row, steps),
+        Interaction::default(),
+        FocusPolicy::Block,
+        Text::new(if steps < 0 { "◀" } else { "▶" }),
+        TextFont {
+            font_size: 16.0,
+            ..default()
+        },
+        TextColor(colors::TEXT_ACCENT),
+    ));
+}
+
+// ─── Interaction systems ───────────────────────────────────────────
+
+/// While a binding is being captured, the next key, mouse or pad button
+/// press becomes the action's input on that device. Esc cancels.
+fn capture_binding(
+    mut keys: MessageReader<KeyboardInput>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    gamepads: Query<&Gamepad>,
+    mut screen: ResMut<SettingsScreen>,
+    mut bindings: ResMut<InputBindings>,
+) {
+    // Always drain, so presses from before the capture started don't count.
+    let key = keys
+        .read()
+        .filter(|key| key.state == ButtonState::Pressed && !key.repeat)
+        .map(|key| key.key_code)
+        .last();
+    let Some(action) = screen.capturing else {
+        return;
+    };
+
+    let binding = match key {
+        Some(KeyCode::Escape) => {
+            screen.capturing = None;
+            screen.swallow_input = true;
+            return;
+        }
+        Some(key) => Some(InputBinding::Key(key)),
+        None => mouse
+            .get_just_pressed()
+            .next()
+            .map(|button| InputBinding::Mouse(*button))
+            .or_else(|| {
+                gamepads
+                    .iter()
+                    .find_map(|pad| pad.get_just_pressed().next())
+                    .map(|button| InputBinding::Pad(*button))
+            }),
+    };
+    if let Some(binding) = binding {
+        bindings.rebind(action, binding);
+        screen.capturing = None;
+        screen.swallow_input = true;
+    }
+}
+
+/// Hovering a row selects it, clicking it activates it, and the arrows
+/// step its value.
+fn settings_mouse(
+    rows: Query<(&Interaction, &SettingsRowNode), Changed<Interaction>>,
+    arrows: Query<(&Interaction, &SettingsArrow), Changed<Interaction>>,
+    mut screen: ResMut<SettingsScreen>,
+    mut settings: ResMut<UserSettings>,
+    mut bindings: ResMut<InputBindings>,
+) {
+    if screen.capturing.is_some() {
+        return;
+    }
+    for (interaction, arrow) in &arrows {
+        if *interaction == Interaction::Pressed {
+            adjust(arrow.0, arrow.1, &mut screen, &mut settings);
+        }
+    }
+    for (interaction, row) in &rows {
+        match interaction {
+            Interaction::Hovered => {
+                if let Some(index) = screen.page.rows().iter().position(|r| *r == row.0) {
+                    screen.selected = index;
+                }
+            }
+            Interaction::Pressed => {
+                activate(row.0, &mut screen, &mut settings, &mut bindings);
+            }
+            Interaction::None => {}
+        }
+    }
+}
+
+/// Up/down select a row, left/right change it, confirm activates it and
+/// cancel leaves the screen.
+fn settings_navigation(
+    input: Res<GameInput>,
+    mut screen: ResMut<SettingsScreen>,
+    mut settings: ResMut<UserSettings>,
+    mut bindings: ResMut<InputBindings>,
+) {
+    if std::mem::take(&mut screen.swallow_input) || screen.capturing.is_some() {
+        return;
+    }
+    let count = screen.page.rows().len();
+    let row = screen.selected_row();
+
+    if input.just_up {
+        screen.selected = cycle_index(screen.selected, -1, count);
+    } else if input.just_down {
+        screen.selected = cycle_index(screen.selected, 1, count);
+    } else if input.just_left {
+        adjust(row, -1, &mut screen, &mut settings);
+    } else if input.just_right {
+        adjust(row, 1, &mut screen, &mut settings);
+    } else if input.confirm {
+        activate(row, &mut screen, &mut settings, &mut bindings);
+    } else if input.cancel {
+        screen.leaving = true;
+    }
+}
+
+fn update_settings_text(
+    screen: Res<SettingsScreen>,
+    settings: Res<UserSettings>,
+    bindings: Res<InputBindings>,
+    mut values: Query<(&mut Text, &SettingsValue)>,
+    mut hints: Query<&mut Text, (With<SettingsHint>, Without<SettingsValue>)>,
+    added: Query<(), Added<SettingsValue>>,
+) {
+    if !(screen.is_changed() || settings.is_changed() || bindings.is_changed()) && added.is_empty()
+    {
+        return;
+    }
+    for (mut text, value) in &mut values {
+        **text = value_text(value.0, &screen, &settings, &bindings);
+    }
+
+    let hint = match screen.capturing {
+        Some(action) => format!(
+            "Press a key or button for {} — Esc to cancel",
+            action_label(action)
+        ),
+        None => match screen.selected_row() {
+            SettingsRow::Binding(_) => {
+                "Enter to rebind — replaces the binding on that device — Esc to go back".into()
+            }
+            _ => "↑↓ select — ←→ change — Esc to go back".into(),
+        },
+    };
+    for mut text in &mut hints {
+        **text = hint.clone();
+    }
+}
+
+fn style_settings_rows(
+    screen: Res<SettingsScreen>,
+    mut rows: Query<(
+        Ref<Interaction>,
+        &SettingsRowNode,
+        &mut BackgroundColor,
+        &mut BorderColor,
+    )>,
+) {
+    let selected = screen.selected_row();
+    for (interaction, row, mut bg, mut border) in &mut rows {
+        if !screen.is_changed() && !interaction.is_changed() {
+            continue;
+        }
+        let (background, edge) = if *interaction == Interaction::Pressed {
+            (colors::BUTTON_PRESSED, colors::TEXT_ACCENT)
+        } else if row.0 == selected {
+            (colors::BUTTON_HOVER, colors::EGA_BRIGHT_CYAN)
+        } else {
+            (colors::BUTTON_NORMAL, colors::PANEL_BORDER)
+        };
+        *bg = BackgroundColor(background);
+        *border = BorderColor::all(edge);
+    }
+}
+
+// ─── Helpers ───────────────────────────────────────────────────────
+
+/// Step `row`'s value by `steps`. Settings are only touched when the value
+/// actually changes, so holding against a limit doesn't rewrite the file.
+fn adjust(
+    row: SettingsRow,
+    steps: i32,
+    screen: &mut SettingsScreen,
+    settings: &mut ResMut<UserSettings>,
+) {
+    let mut edited = (**settings).clone();
+    match row {
+        SettingsRow::Page => {
+            screen.page = screen.page.cycle(steps);
+            screen.selected = 0;
+            screen.dirty = true;
+            return;
+        }
+        SettingsRow::WindowMode => edited.fullscreen = !edited.fullscreen,
+        SettingsRow::Crt => edited.crt_enabled = !edited.crt_enabled,
+        SettingsRow::Palette => edited.palette_quantization = !edited.palette_quantization,
+        SettingsRow::Resolution => {
+            let index = RESOLUTIONS
+                .iter()
+                .position(|r| *r == edited.resolution)
+                .unwrap_or(0);
+            edited.resolution = RESOLUTIONS[cycle_index(index, steps, RESOLUTIONS.len())];
+        }
+        _ => {
+            if let Some((level, min, max, step)) = row.slider(&mut edited) {
+                *level = step_level(*level, steps, min, max, step);
+            }
+        }
+    }
+    settings.set_if_neq(edited);
+}
+
+/// Confirm on `row`: toggles flip, bindings start capturing, buttons act.
+fn activate(
+    row: SettingsRow,
+    screen: &mut SettingsScreen,
+    settings: &mut ResMut<UserSettings>,
+    bindings: &mut InputBindings,
+) {
+    match row {
+        SettingsRow::Page
+        | SettingsRow::WindowMode
+        | SettingsRow::Resolution
+        | SettingsRow::Crt
+        | SettingsRow::Palette => adjust(row, 1, screen, settings),
+        SettingsRow::Binding(action) => screen.capturing = Some(action),
+        SettingsRow::ResetBindings => {
+            for action in GameAction::ALL {
+                bindings.reset_action(action);
+            }
+        }
+        SettingsRow::Back => screen.leaving = true,
+        _ => {}
+    }
+}
+
+fn value_text(
+    row: SettingsRow,
+    screen: &SettingsScreen,
+    settings: &UserSettings,
+    bindings: &InputBindings,
+) -> String {
+    match row {
+        SettingsRow::Page => screen.page.label().to_string(),
+        SettingsRow::WindowMode if settings.fullscreen => "FULLSCREEN".into(),
+        SettingsRow::WindowMode => "WINDOWED".into(),
+        SettingsRow::Resolution => {
+            format!("{} × {}", settings.resolution.x, settings.resolution.y)
+        }
+        SettingsRow::Crt if settings.crt_enabled => "ON".into(),
+        SettingsRow::Crt => "OFF".into(),
+        SettingsRow::Palette if settings.palette_quantization => "ON".into(),
+        SettingsRow::Palette => "OFF".into(),
+        SettingsRow::Binding(action) if screen.capturing == Some(action) => "…".into(),
+        SettingsRow::Binding(action) => bindings
+            .bindings(action)
+            .iter()
+            .map(|binding| binding_label(*binding))
+            .collect::<Vec<_>>()
+            .join(", "),
+        SettingsRow::ResetBindings | SettingsRow::Back => String::new(),
+        _ => {
+            let mut settings = settings.clone();
+            match row.slider(&mut settings) {
+                Some((level, ..)) => format!("{:.0}%", *level * 100.0),
+                None => String::new(),
+            }
+        }
+    }
+}
+
+fn action_label(action: GameAction) -> &'static str {
+    match action {
+        GameAction::Up => "UP",
+        GameAction::Down => "DOWN",
+        GameAction::Left => "LEFT",
+        GameAction::Right => "RIGHT",
+        GameAction::Confirm => "CONFIRM",
+        GameAction::Cancel => "CANCEL",
+        GameAction::ActionA => "ACTION A",
+        GameAction::ActionB => "ACTION B",
+        GameAction::Pause => "PAUSE",
+        GameAction::Jump => "JUMP",
+        GameAction::Fire => "FIRE",
+        GameAction::WeaponCycle => "WEAPON CYCLE",
+        GameAction::Skill => "SKILL",
+    }
+}
+
+/// Short name for a binding: `W`, `Up`, `Mouse Left`, `Pad South`.
+fn binding_label(binding: InputBinding) -> String {
+    match binding {
+        InputBinding::Key(key) => {
+            let name = format!("{key:?}");
+            ["Key", "Digit", "Arrow"]
+                .iter()
+                .find_map(|prefix| name.strip_prefix(prefix))
+                .unwrap_or(&name)
+                .to_string()
+        }
+        InputBinding::Mouse(button) => format!("Mouse {button:?}"),
+        InputBinding::Pad(button) => format!("Pad {button:?}"),
+    }
+}
+
+/// `index` moved `steps` places through `len` items, wrapping around.
+fn cycle_index(index: usize, steps: i32, len: usize) -> usize {
+    (index as i64 + steps as i64).rem_euclid(len as i64) as usize
+}
+
+/// Move `value` by `steps` increments of `step`, snapped to the step grid
+/// so repeated presses don't drift, and kept within `min..=max`.
+fn step_level(value: f32, steps: i32, min: f32, max: f32, step: f32) -> f32 {
+    let snapped = ((value / step).round() + steps as f32) * step;
+    snapped.clamp(min, max)
+}
@@ -0,0 +1,433 @@
+//! Token shop, opened from the timeline hub.
+//!
+//! Tokens earned in the mini-games buy cosmetics from the catalog in
+//! `core::cosmetics`, one page per kind. Buying an item equips it; picking an
+//! owned item again takes it off or puts it back on. Every change is saved
+//! to the active profile straight away.
+
+use bevy::prelude::*;
+
+use super::{StateCleanup, colors};
+use crate::core::cosmetics::{CATALOG, Cosmetic, CosmeticSlot};
+use crate::core::input::GameInput;
+use crate::core::progression::{PlayerProgress, SaveProgress};
+use crate::core::states::TimelineState;
+
+/// Plugin for the token shop.
+pub struct ShopPlugin;
+
+impl Plugin for ShopPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ShopScreen>()
+            .add_systems(OnEnter(TimelineState::Shop), setup_shop)
+            .add_systems(
+                Update,
+                (
+                    shop_mouse,
+                    shop_navigation,
+                    rebuild_shop_list,
+                    update_shop_text,
+                    style_shop_rows,
+                )
+                    .chain()
+                    .run_if(in_state(TimelineState::Shop)),
+            );
+    }
+}
+
+// ─── Resources ─────────────────────────────────────────────────────
+
+#[derive(Resource, Default)]
+struct ShopScreen {
+    page: ShopPage,
+    /// Index into `page.items()`.
+    selected: usize,
+    /// Outcome of the last purchase or equip, shown under the list.
+    message: String,
+    dirty: bool,
+}
+
+impl ShopScreen {
+    fn selected_item(&self) -> Option<&'static Cosmetic> {
+        self.page.items().get(self.selected).copied()
+    }
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+enum ShopPage {
+    #[default]
+    Crt,
+    Palettes,
+    Skins,
+    Music,
+    Frames,
+}
+
+impl ShopPage {
+    const ALL: [ShopPage; 5] = [
+        ShopPage::Crt,
+        ShopPage::Palettes,
+        ShopPage::Skins,
+        ShopPage::Music,
+        ShopPage::Frames,
+    ];
+
+    fn slot_matches(self, slot: CosmeticSlot) -> bool {
+        match self {
+            ShopPage::Crt => slot == CosmeticSlot::CrtPreset,
+            ShopPage::Palettes => slot == CosmeticSlot::Palette,
+            ShopPage::Skins => matches!(slot, CosmeticSlot::Skin(_)),
+            ShopPage::Music => slot == CosmeticSlot::HubMusic,
+            ShopPage::Frames => slot == CosmeticSlot::CabinetFrame,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            ShopPage::Crt => CosmeticSlot::CrtPreset.label(),
+            ShopPage::Palettes => CosmeticSlot::Palette.label(),
+            ShopPage::Skins => "SKINS",
+            ShopPage::Music => CosmeticSlot::HubMusic.label(),
+            ShopPage::Frames => CosmeticSlot::CabinetFrame.label(),
+        }
+    }
+
+    fn items(self) -> Vec<&'static Cosmetic> {
+        CATALOG
+            .iter()
+            .filter(|cosmetic| self.slot_matches(cosmetic.slot))
+            .collect()
+    }
+
+    fn cycle(self, steps: i32) -> ShopPage {
+        let index = Self::ALL.iter().position(|page| *page == self).unwrap_or(0);
+        Self::ALL[cycle_index(index, steps, Self::ALL.len())]
+    }
+}
+
+// ─── Components ────────────────────────────────────────────────────
+
+#[derive(Component)]
+struct ShopList;
+
+#[derive(Component)]
+struct ShopPageTitle;
+
+#[derive(Component)]
+struct ShopBalance;
+
+#[derive(Component)]
+struct ShopDetail;
+
+#[derive(Component)]
+struct ShopRow(usize);
+
+// ─── Setup ─────────────────────────────────────────────────────────
+
+fn setup_shop(mut commands: Commands, mut screen: ResMut<ShopScreen>) {
+    *screen = ShopScreen {
+        page: screen.page,
+        dirty: true,
+        ..default()
+    };
+
+    commands
+        .spawn((
+            StateCleanup(TimelineState::Shop),
+            GlobalZIndex(10),
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.85)),
+        ))
+        .with_children(|root| {
+            root.spawn((
+                Node {
+                    width: Val::Px(640.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    padding: UiRect::all(Val::Px(24.0)),
+                    border: UiRect::all(Val::Px(2.0)),
+                    row_gap: Val::Px(12.0),
+                    border_radius: BorderRadius::all(Val::Px(12.0)),
+                    ..default()
+                },
+                BackgroundColor(colors::PANEL_BG),
+                BorderColor::all(colors::PANEL_BORDER),
+            ))
+            .with_children(|panel| {
+                panel.spawn((
+                    Text::new("TOKEN SHOP"),
+                    TextFont {
+                        font_size: 36.0,
+                        ..default()
+                    },
+                    TextColor(colors::TEXT_ACCENT),
+                ));
+
+                panel.spawn((
+                    ShopBalance,
+                    Text::new(""),
+                    TextFont {
+                        font_size: 20.0,
+                        ..default()
+                    },
+                    TextColor(colors::GOLD),
+                ));
+
+                panel.spawn((
+                    ShopPageTitle,
+                    Text::new(""),
+                    TextFont {
+                        font_size: 18.0,
+                        ..default()
+                    },
+                    TextColor(colors::EGA_BRIGHT_YELLOW),
+                ));
+
+                panel.spawn((
+                    ShopList,
+                    Node {
+                        width: Val::Percent(100.0),
+                        flex_direction: FlexDirection::Column,
+                        row_gap: Val::Px(4.0),
+                        ..default()
+                    },
+                ));
+
+                panel.spawn((
+                    ShopDetail,
+                    Text::new(""),
+                    TextFont {
+                        font_size: 14.0,
+                        ..default()
+                    },
+                    TextColor(colors::TEXT_PRIMARY),
+                    TextLayout::new_with_justify(Justify::Center),
+                ));
+
+                panel.spawn((
+                    Text::new("↑↓ select — ←→ page — Enter buy / equip — Esc back"),
+                    TextFont {
+                        font_size: 14.0,
+                        ..default()
+                    },
+                    TextColor(colors::TEXT_SECONDARY),
+                ));
+            });
+        });
+}
+
+fn rebuild_shop_list(
+    mut commands: Commands,
+    mut screen: ResMut<ShopScreen>,
+    progress: Res<PlayerProgress>,
+    list_query: Query<Entity, With<ShopList>>,
+) {
+    if !screen.dirty {
+        return;
+    }
+    let Ok(list) = list_query.single() else {
+        return;
+    };
+    screen.dirty = false;
+    commands.entity(list).despawn_children();
+
+    commands.entity(list).with_children(|list| {
+        for (index, item) in screen.page.items().into_iter().enumerate() {
+            list.spawn((
+                ShopRow(index),
+                Interaction::default(),
+                Node {
+                    width: Val::Percent(100.0),
+                    height: Val::Px(30.0),
+                    justify_content: JustifyContent::SpaceBetween,
+                    align_items: AlignItems::Center,
+                    padding: UiRect::horizontal(Val::Px(12.0)),
+                    border: UiRect::all(Val::Px(2.0)),
+                    border_radius: BorderRadius::all(Val::Px(4.0)),
+                    ..default()
+                },
+                BackgroundColor(colors::BUTTON_NORMAL),
+                BorderColor::all(colors::PANEL_BORDER),
+            ))
+            .with_children(|line| {
+                line.spawn((
+                    Text::new(item.name),
+                    TextFont {
+                        font_size: 16.0,
+                        ..default()
+                    },
+                    TextColor(colors::TEXT_PRIMARY),
+                ));
+                let (status, color) = item_status(item, &progress);
+                line.spawn((
+                    Text::new(status),
+                    TextFont {
+                        font_size: 16.0,
+                        ..default()
+                    },
+                    TextColor(color),
+                ));
+            });
+        }
+    });
+}
+
+// ─── Input ─────────────────────────────────────────────────────────
+
+/// Hovering a row selects it and clicking it buys or equips it.
+fn shop_mouse(
+    rows: Query<(&Interaction, &ShopRow), Changed<Interaction>>,
+    mut screen: ResMut<ShopScreen>,
+    mut progress: ResMut<PlayerProgress>,
+    mut save: MessageWriter<SaveProgress>,
+) {
+    for (interaction, row) in &rows {
+        match interaction {
+            Interaction::Hovered => screen.selected = row.0,
+            Interaction::Pressed => {
+                screen.selected = row.0;
+                activate(&mut screen, &mut progress, &mut save);
+            }
+            Interaction::None => {}
+        }
+    }
+}
+
+/// Up/down select an item, left/right change page, confirm buys or equips
+/// and cancel goes back to the hub.
+fn shop_navigation(
+    input: Res<GameInput>,
+    mut screen: ResMut<ShopScreen>,
+    mut progress: ResMut<PlayerProgress>,
+    mut save: MessageWriter<SaveProgress>,
+    mut next_timeline: ResMut<NextState<TimelineState>>,
+) {
+    let count = screen.page.items().len();
+
+    if input.just_up {
+        screen.selected = cycle_index(screen.selected, -1, count);
+    } else if input.just_down {
+        screen.selected = cycle_index(screen.selected, 1, count);
+    } else if input.just_left || input.just_right {
+        screen.page = screen.page.cycle(if input.just_left { -1 } else { 1 });
+        screen.selected = 0;
+        screen.message.clear();
+        screen.dirty = true;
+    } else if input.confirm {
+        activate(&mut screen, &mut progress, &mut save);
+    } else if input.cancel {
+        next_timeline.set(TimelineState::Hub);
+    }
+}
+
+// ─── Display ───────────────────────────────────────────────────────
+
+#[allow(clippy::type_complexity)]
+fn update_shop_text(
+    screen: Res<ShopScreen>,
+    progress: Res<PlayerProgress>,
+    mut texts: ParamSet<(
+        Query<&mut Text, With<ShopBalance>>,
+        Query<&mut Text, With<ShopPageTitle>>,
+        Query<&mut Text, With<ShopDetail>>,
+    )>,
+    added: Query<(), Added<ShopDetail>>,
+) {
+    if !(screen.is_changed() || progress.is_changed()) && added.is_empty() {
+        return;
+    }
+    if let Ok(mut text) = texts.p0().single_mut() {
+        **text = format!("🪙 {} Tokens", progress.tokens);
+    }
+    if let Ok(mut text) = texts.p1().single_mut() {
+        **text = format!("◀  {}  ▶", screen.page.label());
+    }
+    if let Ok(mut text) = texts.p2().single_mut() {
+        **text = if !screen.message.is_empty() {
+            screen.message.clone()
+        } else {
+            screen
+                .selected_item()
+                .map_or(String::new(), |item| item.description.to_string())
+        };
+    }
+}
+
+fn style_shop_rows(
+    screen: Res<ShopScreen>,
+    mut rows: Query<(
+        Ref<Interaction>,
+        &ShopRow,
+        &mut BackgroundColor,
+        &mut BorderColor,
+    )>,
+) {
+    for (interaction, row, mut bg, mut border) in &mut rows {
+        if !screen.is_changed() && !interaction.is_changed() {
+            continue;
+        }
+        let (background, edge) = if *interaction == Interaction::Pressed {
+            (colors::BUTTON_PRESSED, colors::TEXT_ACCENT)
+        } else if row.0 == screen.selected {
+            (colors::BUTTON_HOVER, colors::EGA_BRIGHT_CYAN)
+        } else {
+            (colors::BUTTON_NORMAL, colors::PANEL_BORDER)
+        };
+        *bg = BackgroundColor(background);
+        *border = BorderColor::all(edge);
+    }
+}
+
+// ─── Helpers ───────────────────────────────────────────────────────
+
+/// Buy the selected item, or toggle it on and off when it's already owned,
+/// then save.
+fn activate(
+    screen: &mut ShopScreen,
+    progress: &mut PlayerProgress,
+    save: &mut MessageWriter<SaveProgress>,
+) {
+    let Some(item) = screen.selected_item() else {
+        return;
+    };
+    screen.message = if progress.owns(item) {
+        if progress.equipped(item.slot) == Some(item) {
+            progress.unequip(item);
+            format!("{} taken off", item.name)
+        } else {
+            progress.equip(item);
+            format!("{} equipped", item.name)
+        }
+    } else {
+        match progress.buy(item) {
+            Ok(()) => format!("Bought {}!", item.name),
+            Err(err) => format!("Can't buy {}: {}", item.name, err),
+        }
+    };
+    screen.dirty = true;
+    save.write(SaveProgress);
+}
+
+/// Right-hand column of an item's row.
+fn item_status(item: &Cosmetic, progress: &PlayerProgress) -> (String, Color) {
+    if progress.equipped(item.slot) == Some(item) {
+        ("EQUIPPED".into(), colors::EGA_BRIGHT_GREEN)
+    } else if progress.owns(item) {
+        ("OWNED".into(), colors::TEXT_ACCENT)
+    } else if progress.tokens >= item.price {
+        (format!("🪙 {}", item.price), colors::GOLD)
+    } else {
+        (format!("🪙 {}", item.price), colors::TEXT_SECONDARY)
+    }
+}
+
+/// `index` moved `steps` places through `len` items, wrapping around.
+fn cycle_index(index: usize, steps: i32, len: usize) -> usize {
+    (index as i64 + steps as i64).rem_euclid(len.max(1) as i64) as usize
+}
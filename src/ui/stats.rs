@@ -0,0 +1,277 @@
+//! Stats screen, opened from the timeline hub.
+//!
+//! The first page sums every era; the others show one era's totals followed
+//! by each of its games with their own counters. Left and right page
+//! through them.
+
+use bevy::prelude::*;
+
+use super::{StateCleanup, colors};
+use crate::core::input::GameInput;
+use crate::core::progression::PlayerProgress;
+use crate::core::registry::GameRegistry;
+use crate::core::states::{Era, TimelineState};
+use crate::core::stats::{GameStats, counters_for};
+
+/// Plugin for the stats screen.
+pub struct StatsScreenPlugin;
+
+impl Plugin for StatsScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<StatsPage>()
+            .add_systems(OnEnter(TimelineState::Stats), setup_stats)
+            .add_systems(
+                Update,
+                (stats_navigation, rebuild_stats)
+                    .chain()
+                    .run_if(in_state(TimelineState::Stats)),
+            );
+    }
+}
+
+// ─── Resources ─────────────────────────────────────────────────────
+
+#[derive(Resource, Default)]
+struct StatsPage {
+    /// Index into `pages`.
+    index: usize,
+    dirty: bool,
+}
+
+/// The overview, then every era that has games.
+fn pages(registry: &GameRegistry) -> Vec<Option<Era>> {
+    std::iter::once(None)
+        .chain(
+            Era::ALL
+                .into_iter()
+                .filter(|era| registry.games_in_era(*era) > 0)
+                .map(Some),
+        )
+        .collect()
+}
+
+// ─── Components ────────────────────────────────────────────────────
+
+#[derive(Component)]
+struct StatsTitle;
+
+#[derive(Component)]
+struct StatsBody;
+
+// ─── Setup ─────────────────────────────────────────────────────────
+
+fn setup_stats(mut commands: Commands, mut page: ResMut<StatsPage>) {
+    *page = StatsPage {
+        index: 0,
+        dirty: true,
+    };
+
+    commands
+        .spawn((
+            StateCleanup(TimelineState::Stats),
+            GlobalZIndex(10),
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.85)),
+        ))
+        .with_children(|root| {
+            root.spawn((
+                Node {
+                    width: Val::Px(640.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    padding: UiRect::all(Val::Px(24.0)),
+                    border: UiRect::all(Val::Px(2.0)),
+                    row_gap: Val::Px(12.0),
+                    border_radius: BorderRadius::all(Val::Px(12.0)),
+                    ..default()
+                },
+                BackgroundColor(colors::PANEL_BG),
+                BorderColor::all(colors::PANEL_BORDER),
+            ))
+            .with_children(|panel| {
+                panel.spawn((
+                    Text::new("STATS"),
+                    TextFont {
+                        font_size: 36.0,
+                        ..default()
+                    },
+                    TextColor(colors::TEXT_ACCENT),
+                ));
+
+                panel.spawn((
+                    StatsTitle,
+                    Text::new(""),
+                    TextFont {
+                        font_size: 18.0,
+                        ..default()
+                    },
+                    TextColor(colors::EGA_BRIGHT_YELLOW),
+                ));
+
+                panel.spawn((
+                    StatsBody,
+                    Node {
+                        width: Val::Percent(100.0),
+                        flex_direction: FlexDirection::Column,
+                        row_gap: Val::Px(4.0),
+                        ..default()
+                    },
+                ));
+
+                panel.spawn((
+                    Text::new("←→ page — Esc back"),
+                    TextFont {
+                        font_size: 14.0,
+                        ..default()
+                    },
+                    TextColor(colors::TEXT_SECONDARY),
+                ));
+            });
+        });
+}
+
+// ─── Input ─────────────────────────────────────────────────────────
+
+/// Left/right change page and cancel goes back to the hub.
+fn stats_navigation(
+    input: Res<GameInput>,
+    registry: Res<GameRegistry>,
+    mut page: ResMut<StatsPage>,
+    mut next_timeline: ResMut<NextState<TimelineState>>,
+) {
+    let count = pages(&registry).len();
+    if input.just_left {
+        page.index = (page.index + count - 1) % count;
+        page.dirty = true;
+    } else if input.just_right {
+        page.index = (page.index + 1) % count;
+        page.dirty = true;
+    } else if input.cancel {
+        next_timeline.set(TimelineState::Hub);
+    }
+}
+
+// ─── Display ───────────────────────────────────────────────────────
+
+fn rebuild_stats(
+    mut commands: Commands,
+    mut page: ResMut<StatsPage>,
+    registry: Res<GameRegistry>,
+    progress: Res<PlayerProgress>,
+    mut title: Query<&mut Text, With<StatsTitle>>,
+    body: Query<Entity, With<StatsBody>>,
+) {
+    if !page.dirty {
+        return;
+    }
+    let Ok(body) = body.single() else {
+        return;
+    };
+    page.dirty = false;
+    let era = pages(&registry).get(page.index).copied().flatten();
+    let stats = &progress.stats;
+
+    if let Ok(mut text) = title.single_mut() {
+        **text = format!("◀  {}  ▶", era.map_or("ALL ERAS", |era| era.title()));
+    }
+
+    commands.entity(body).despawn_children();
+    commands.entity(body).with_children(|body| match era {
+        None => {
+            spawn_heading(body, "Total");
+            spawn_totals(body, &stats.total());
+            for era in pages(&registry).into_iter().flatten() {
+                spawn_heading(body, era.title());
+                spawn_totals(body, &stats.era(era));
+            }
+        }
+        Some(era) => {
+            spawn_heading(body, "All games");
+            spawn_totals(body, &stats.era(era));
+            for info in registry.era_games(era) {
+                spawn_heading(body, info.name);
+                spawn_totals(body, &stats.game(info.id));
+                for counter in counters_for(info.id) {
+                    spawn_line(body, counter.label, stats.counter(counter.stat).to_string());
+                }
+            }
+        }
+    });
+}
+
+fn spawn_heading(body: &mut ChildSpawnerCommands, heading: &str) {
+    body.spawn((
+        Node {
+            margin: UiRect::top(Val::Px(6.0)),
+            ..default()
+        },
+        Text::new(heading),
+        TextFont {
+            font_size: 16.0,
+            ..default()
+        },
+        TextColor(colors::TEXT_ACCENT),
+    ));
+}
+
+/// The run totals every game, era and the overview share.
+fn spawn_totals(body: &mut ChildSpawnerCommands, stats: &GameStats) {
+    spawn_line(
+        body,
+        "Plays · time",
+        format!(
+            "{} · {}",
+            stats.plays,
+            format_play_time(stats.play_time_secs)
+        ),
+    );
+    spawn_line(body, "Deaths", stats.deaths.to_string());
+    spawn_line(
+        body,
+        "Victories · best streak",
+        format!("{} · {}", stats.victories, stats.best_win_streak),
+    );
+}
+
+fn spawn_line(body: &mut ChildSpawnerCommands, label: &str, value: String) {
+    body.spawn(Node {
+        width: Val::Percent(100.0),
+        justify_content: JustifyContent::SpaceBetween,
+        padding: UiRect::horizontal(Val::Px(12.0)),
+        ..default()
+    })
+    .with_children(|line| {
+        line.spawn((
+            Text::new(label),
+            TextFont {
+                font_size: 14.0,
+                ..default()
+            },
+            TextColor(colors::TEXT_SECONDARY),
+        ));
+        line.spawn((
+            Text::new(value),
+            TextFont {
+                font_size: 14.0,
+                ..default()
+            },
+            TextColor(colors::TEXT_PRIMARY),
+        ));
+    });
+}
+
+/// `secs` as hours and minutes, or minutes and seconds under an hour.
+fn format_play_time(secs: f64) -> String {
+    let secs = secs as u64;
+    if secs >= 3600 {
+        format!("{}h {:02}m", secs / 3600, secs % 3600 / 60)
+    } else {
+        format!("{}m {:02}s", secs / 60, secs % 60)
+    }
+}
@@ -1,9 +1,11 @@
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
 
-use super::colors;
+use super::{StateCleanup, colors};
+use crate::core::profiles::ProfileStore;
 use crate::core::progression::PlayerProgress;
-use crate::core::states::{Era, GameState};
+use crate::core::registry::GameRegistry;
+use crate::core::states::{Era, GameState, TimelineState};
 use crate::ui::era_select::CurrentEra;
 
 /// Plugin for the timeline hub screen.
@@ -16,13 +18,12 @@ impl Plugin for TimelinePlugin {
             .add_systems(
                 Update,
                 (
-                    timeline_input,
+                    timeline_input.run_if(in_state(TimelineState::Hub)),
                     layout_timeline_carousel,
                     update_timeline_labels,
                 )
                     .run_if(in_state(GameState::Timeline)),
-            )
-            .add_systems(OnExit(GameState::Timeline), cleanup_timeline);
+            );
     }
 }
 
@@ -31,9 +32,6 @@ struct SelectedEra {
     index: usize,
 }
 
-#[derive(Component)]
-struct TimelineRoot;
-
 #[derive(Component)]
 struct EraCarouselCard {
     era: Era,
@@ -49,12 +47,20 @@ struct SelectedEraSubtitle;
 #[derive(Component)]
 struct SelectedEraStatus;
 
-const ERA_COUNT: usize = 5;
+#[derive(Component)]
+struct TokenBalance;
+
+const ERA_COUNT: usize = Era::ALL.len();
+
+/// Card image for eras that have no games yet.
+const COMING_SOON_THUMBNAIL: &str = "ui/thumbnails/tunnel_miner.png";
 
 fn setup_timeline(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     progress: Res<PlayerProgress>,
+    registry: Res<GameRegistry>,
+    profiles: Option<Res<ProfileStore>>,
     mut selected: ResMut<SelectedEra>,
     camera_query: Query<Entity, With<Camera2d>>,
 ) {
@@ -66,7 +72,7 @@ fn setup_timeline(
 
     commands
         .spawn((
-            TimelineRoot,
+            StateCleanup(GameState::Timeline),
             Node {
                 width: Val::Percent(100.0),
                 height: Val::Percent(100.0),
@@ -109,6 +115,7 @@ fn setup_timeline(
                     justify_content: JustifyContent::Center,
                     ..default()
                 },
+                TokenBalance,
                 Text::new(format!("🪙 {} Tokens", progress.tokens)),
                 TextFont {
                     font_size: 20.0,
@@ -176,7 +183,9 @@ fn setup_timeline(
                     justify_content: JustifyContent::Center,
                     ..default()
                 },
-                Text::new("← → Navigate Era  |  Enter Select  |  Esc Back"),
+                Text::new(
+                    "← → Navigate Era  |  Enter Select  |  S Shop  |  A Achievements  |  T Stats  |  Esc Back",
+                ),
                 TextFont {
                     font_size: 16.0,
                     ..default()
@@ -184,9 +193,28 @@ fn setup_timeline(
                 TextColor(colors::TEXT_SECONDARY),
             ));
 
-            for (index, (era, title, subtitle, image_path)) in era_entries().iter().enumerate() {
+            if profiles.as_deref().is_some_and(|p| !p.is_persistent()) {
                 root.spawn((
-                    EraCarouselCard { era: *era, index },
+                    Node {
+                        width: Val::Percent(100.0),
+                        position_type: PositionType::Absolute,
+                        bottom: Val::Px(48.0),
+                        justify_content: JustifyContent::Center,
+                        ..default()
+                    },
+                    Text::new("⚠ Progress can't be saved — the save data is unavailable"),
+                    TextFont {
+                        font_size: 14.0,
+                        ..default()
+                    },
+                    TextColor(colors::EGA_BRIGHT_RED),
+                ));
+            }
+
+            for (index, era) in Era::ALL.into_iter().enumerate() {
+                let image_path = registry.era_thumbnail(era).unwrap_or(COMING_SOON_THUMBNAIL);
+                root.spawn((
+                    EraCarouselCard { era, index },
                     Node {
                         position_type: PositionType::Absolute,
                         width: Val::Px(280.0),
@@ -203,7 +231,7 @@ fn setup_timeline(
                 ))
                 .with_children(|card| {
                     card.spawn((
-                        ImageNode::new(asset_server.load(*image_path)),
+                        ImageNode::new(asset_server.load(image_path)),
                         Node {
                             width: Val::Percent(90.0),
                             height: Val::Percent(62.0),
@@ -212,7 +240,7 @@ fn setup_timeline(
                     ));
 
                     card.spawn((
-                        Text::new(title.to_string()),
+                        Text::new(era.title()),
                         TextFont {
                             font_size: 24.0,
                             ..default()
@@ -221,7 +249,7 @@ fn setup_timeline(
                     ));
 
                     card.spawn((
-                        Text::new(subtitle.to_string()),
+                        Text::new(era.tagline()),
                         TextFont {
                             font_size: 14.0,
                             ..default()
@@ -238,8 +266,22 @@ fn timeline_input(
     mut selected: ResMut<SelectedEra>,
     mut current_era: ResMut<CurrentEra>,
     mut next_state: ResMut<NextState<GameState>>,
+    mut next_timeline: ResMut<NextState<TimelineState>>,
 ) {
     if keyboard.just_pressed(KeyCode::Escape) {
+        next_state.set(GameState::Menu);
+        return;
+    }
+    if keyboard.just_pressed(KeyCode::KeyS) {
+        next_timeline.set(TimelineState::Shop);
+        return;
+    }
+    if keyboard.just_pressed(KeyCode::KeyA) {
+        next_timeline.set(TimelineState::Achievements);
+        return;
+    }
+    if keyboard.just_pressed(KeyCode::KeyT) {
+        next_timeline.set(TimelineState::Stats);
         return;
     }
 
@@ -251,8 +293,7 @@ fn timeline_input(
     }
 
     if keyboard.just_pressed(KeyCode::Enter) {
-        let (era, _, _, _) = era_entries()[selected.index];
-        current_era.era = era;
+        current_era.era = Era::ALL[selected.index];
         next_state.set(GameState::EraSelect);
     }
 }
@@ -326,6 +367,7 @@ fn layout_timeline_carousel(
     }
 }
 
+#[allow(clippy::type_complexity)]
 fn update_timeline_labels(
     selected: Res<SelectedEra>,
     progress: Res<PlayerProgress>,
@@ -333,15 +375,16 @@ fn update_timeline_labels(
         Query<&mut Text, With<SelectedEraTitle>>,
         Query<&mut Text, With<SelectedEraSubtitle>>,
         Query<&mut Text, With<SelectedEraStatus>>,
+        Query<&mut Text, With<TokenBalance>>,
     )>,
 ) {
-    let (era, title, subtitle, _) = era_entries()[selected.index];
+    let era = Era::ALL[selected.index];
 
     if let Ok(mut text) = text_sets.p0().single_mut() {
-        **text = title.to_string();
+        **text = era.title().to_string();
     }
     if let Ok(mut text) = text_sets.p1().single_mut() {
-        **text = subtitle.to_string();
+        **text = era.tagline().to_string();
     }
     if let Ok(mut text) = text_sets.p2().single_mut() {
         if progress.is_era_unlocked(era) {
@@ -350,46 +393,9 @@ fn update_timeline_labels(
             **text = "LOCKED".to_string();
         }
     }
-}
-
-fn cleanup_timeline(mut commands: Commands, query: Query<Entity, With<TimelineRoot>>) {
-    for entity in &query {
-        commands.entity(entity).despawn_children();
-        commands.entity(entity).despawn();
+    if progress.is_changed()
+        && let Ok(mut text) = text_sets.p3().single_mut()
+    {
+        **text = format!("🪙 {} Tokens", progress.tokens);
     }
 }
-
-fn era_entries() -> [(Era, &'static str, &'static str, &'static str); ERA_COUNT] {
-    [
-        (
-            Era::The80s,
-            "The 1980s",
-            "The DOS Age",
-            "ui/thumbnails/tunnel_miner.png",
-        ),
-        (
-            Era::The90s,
-            "The 1990s",
-            "The Golden Age",
-            "ui/thumbnails/worm_wars.png",
-        ),
-        (
-            Era::The2000s,
-            "The 2000s",
-            "Coming Soon",
-            "ui/thumbnails/ice_blitz.png",
-        ),
-        (
-            Era::The2010s,
-            "The 2010s",
-            "Coming Soon",
-            "ui/thumbnails/depths_of_doom.png",
-        ),
-        (
-            Era::Future,
-            "Future",
-            "Nebula Bouncer",
-            "ui/thumbnails/nebula_bouncer.png",
-        ),
-    ]
-}
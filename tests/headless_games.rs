@@ -0,0 +1,427 @@
+//! Integration tests that run mini-games headless.
+
+use bevy::camera::ScalingMode;
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use retro_game_game::core::input::GameAction;
+use retro_game_game::core::progression::{MiniGameOutcome, PlayerProgress};
+use retro_game_game::core::replay::ReplayVerdict;
+use retro_game_game::core::states::{PauseMenuState, PlayingState};
+use retro_game_game::effects::virtual_screen::ScreenCamera;
+use retro_game_game::eras::era_80s::tunnel_miner::Nobbin;
+use retro_game_game::eras::era_90s::depths_of_doom::{Stairs, Wall};
+use retro_game_game::eras::era_90s::ice_blitz::{Goal, Puck, Scores};
+use retro_game_game::eras::era_future::nebula_bouncer::components::{Enemy, PlayerShip};
+use retro_game_game::eras::era_future::nebula_bouncer::resources::HitStop;
+use retro_game_game::headless::HeadlessGame;
+use retro_game_game::shared::components::{GridPosition, Health, Player, Score, Velocity};
+use std::time::Duration;
+
+/// Every mini-game sets up and survives a few seconds of scripted input.
+#[test]
+fn every_game_runs_headless() {
+    for game in [
+        PlayingState::TunnelMiner,
+        PlayingState::CosmicCaptain,
+        PlayingState::StarGoose,
+        PlayingState::WormWars,
+        PlayingState::IceBlitz,
+        PlayingState::DepthsOfDoom,
+        PlayingState::NebulaBouncer,
+    ] {
+        let mut headless = HeadlessGame::new(game);
+        headless.hold(&[GameAction::Right, GameAction::Fire]);
+        headless.step(120);
+        let state = headless.world().resource::<State<PlayingState>>();
+        assert_eq!(*state.get(), game);
+    }
+}
+
+#[test]
+fn tunnel_miner_player_dies_when_a_nobbin_reaches_its_tile() {
+    let mut game = HeadlessGame::new(PlayingState::TunnelMiner);
+    let lives = game.lives().unwrap();
+
+    // Wait for the first Nobbin to spawn, then put it on the player's tile.
+    let mut nobbins = Vec::new();
+    for _ in 0..600 {
+        game.step(1);
+        nobbins = game.entities::<With<Nobbin>>();
+        if !nobbins.is_empty() {
+            break;
+        }
+    }
+    let nobbin = *nobbins.first().expect("no Nobbin spawned");
+    let player = game.entities::<With<Player>>()[0];
+    let player_tile = *game.world().get::<GridPosition>(player).unwrap();
+    *game.world_mut().get_mut::<GridPosition>(nobbin).unwrap() = player_tile;
+
+    game.step(1);
+
+    assert_eq!(game.lives(), Some(lives - 1));
+}
+
+#[test]
+fn cosmic_captain_jumps_off_the_ground() {
+    let mut game = HeadlessGame::new(PlayingState::CosmicCaptain);
+    game.step(120);
+    let captain = game.entities::<With<Player>>()[0];
+    let height = |game: &HeadlessGame| {
+        game.world()
+            .get::<Transform>(captain)
+            .unwrap()
+            .translation
+            .y
+    };
+    let resting = height(&game);
+
+    game.tap(&[GameAction::Jump]);
+    game.step(10);
+
+    assert!(height(&game) > resting + 20.0);
+}
+
+#[test]
+fn cosmic_captain_walks_into_the_boots() {
+    let mut game = HeadlessGame::new(PlayingState::CosmicCaptain);
+    game.hold(&[GameAction::Right]);
+    game.step(60);
+
+    let found = game
+        .world()
+        .resource::<PlayerProgress>()
+        .stat("cosmic_captain.items_found");
+    assert_eq!(found, 1);
+}
+
+#[test]
+fn cosmic_captain_level_ends_at_the_exit() {
+    let mut game = HeadlessGame::new(PlayingState::CosmicCaptain);
+    let captain = game.entities::<With<Player>>()[0];
+    game.world_mut()
+        .get_mut::<Transform>(captain)
+        .unwrap()
+        .translation = Vec3::new(560.0, -118.0, 1.0);
+    game.step(10);
+
+    let runs: Vec<_> = game
+        .finished()
+        .iter()
+        .map(|run| (run.outcome, run.score))
+        .collect();
+    assert_eq!(runs, [(MiniGameOutcome::Victory, 300)]);
+}
+
+#[test]
+fn ice_blitz_goal_increments_scores() {
+    let mut game = HeadlessGame::new(PlayingState::IceBlitz);
+    assert_eq!(game.score(), 0);
+
+    // Drop the puck into the red team's goal mouth.
+    let world = game.world_mut();
+    let goal = world
+        .query::<(&Goal, &Transform)>()
+        .iter(world)
+        .find(|(goal, _)| goal.team_id == 1)
+        .map(|(_, transform)| transform.translation)
+        .unwrap();
+    let mut pucks = world.query_filtered::<&mut Transform, With<Puck>>();
+    pucks.single_mut(world).unwrap().translation = goal;
+
+    game.step(1);
+
+    let scores = game.world().resource::<Scores>();
+    assert_eq!((scores.team0, scores.team1), (1, 0));
+    assert_eq!(game.score(), 100);
+}
+
+#[test]
+fn ice_blitz_match_ends_at_three_goals() {
+    let mut game = HeadlessGame::new(PlayingState::IceBlitz);
+    game.world_mut().resource_mut::<Scores>().team0 = 2;
+
+    let world = game.world_mut();
+    let goal = world
+        .query::<(&Goal, &Transform)>()
+        .iter(world)
+        .find(|(goal, _)| goal.team_id == 1)
+        .map(|(_, transform)| transform.translation)
+        .unwrap();
+    let mut pucks = world.query_filtered::<&mut Transform, With<Puck>>();
+    pucks.single_mut(world).unwrap().translation = goal;
+    game.step(1);
+
+    let runs: Vec<_> = game
+        .finished()
+        .iter()
+        .map(|run| (run.outcome, run.score))
+        .collect();
+    assert_eq!(runs, [(MiniGameOutcome::Victory, 300)]);
+}
+
+#[test]
+fn ice_blitz_camera_shows_its_own_view() {
+    let mut game = HeadlessGame::new(PlayingState::IceBlitz);
+    let world = game.world_mut();
+    let modes: Vec<_> = world
+        .query_filtered::<&Projection, (With<Camera2d>, Without<ScreenCamera>)>()
+        .iter(world)
+        .map(|projection| match projection {
+            Projection::Orthographic(ortho) => Some(ortho.scaling_mode),
+            _ => None,
+        })
+        .collect();
+
+    assert!(!modes.is_empty());
+    for mode in modes {
+        assert!(matches!(
+            mode,
+            Some(ScalingMode::Fixed {
+                width: 960.0,
+                height: 720.0
+            })
+        ));
+    }
+}
+
+#[test]
+fn worm_wars_bazooka_ends_the_match_once() {
+    let mut game = HeadlessGame::new(PlayingState::WormWars);
+
+    // Bunch the red worms up where the opening shot lands, one hit from
+    // going down.
+    for worm in game.entities::<(With<Health>, Without<Player>)>() {
+        let world = game.world_mut();
+        world.get_mut::<Health>(worm).unwrap().current = 50;
+        world.get_mut::<Transform>(worm).unwrap().translation.x = 260.0;
+    }
+    game.tap(&[GameAction::Fire]);
+    game.step(180);
+
+    let runs: Vec<_> = game
+        .finished()
+        .iter()
+        .map(|run| (run.outcome, run.score))
+        .collect();
+    assert_eq!(runs, [(MiniGameOutcome::Victory, 500)]);
+    assert_eq!(
+        game.world()
+            .resource::<PlayerProgress>()
+            .stat("worm_wars.flawless_wins"),
+        1
+    );
+}
+
+#[test]
+fn nebula_bouncer_run_ends_when_the_ship_is_destroyed() {
+    let mut game = HeadlessGame::new(PlayingState::NebulaBouncer);
+
+    // Wait for a chunk with an enemy, leave the ship one hit from
+    // destruction and fly it into the enemy.
+    let mut enemies = game.entities::<With<Enemy>>();
+    for _ in 0..600 {
+        if !enemies.is_empty() {
+            break;
+        }
+        game.step(1);
+        enemies = game.entities::<With<Enemy>>();
+    }
+    let enemy = *enemies.first().expect("no enemy spawned");
+    let ship = game.entities::<With<PlayerShip>>()[0];
+    game.world_mut().get_mut::<Health>(ship).unwrap().current = 1;
+    game.world_mut().resource_mut::<Score>().value = 300;
+    let position = game.world().get::<Transform>(enemy).unwrap().translation;
+    game.world_mut()
+        .get_mut::<Transform>(ship)
+        .unwrap()
+        .translation = position;
+
+    for _ in 0..10 {
+        game.step(1);
+        if !game.finished().is_empty() {
+            break;
+        }
+    }
+
+    let runs: Vec<_> = game
+        .finished()
+        .iter()
+        .map(|run| (run.outcome, run.score))
+        .collect();
+    assert_eq!(runs, [(MiniGameOutcome::Defeat, 300)]);
+}
+
+#[test]
+fn quitting_during_hit_stop_restores_time() {
+    let mut game = HeadlessGame::new(PlayingState::NebulaBouncer);
+    game.world_mut().resource_mut::<HitStop>().timer = 10.0;
+    game.step(1);
+    assert_eq!(
+        game.world().resource::<Time<Virtual>>().relative_speed(),
+        0.0
+    );
+
+    game.quit();
+    for _ in 0..600 {
+        if !game.is_playing() {
+            break;
+        }
+        game.step(1);
+    }
+
+    assert!(!game.is_playing());
+    assert_eq!(
+        game.world().resource::<Time<Virtual>>().relative_speed(),
+        1.0
+    );
+}
+
+#[test]
+fn same_seed_builds_the_same_dungeon() {
+    let walls = |seed| {
+        let mut game = HeadlessGame::with_seed(PlayingState::DepthsOfDoom, seed);
+        let world = game.world_mut();
+        let mut positions: Vec<(i32, i32)> = world
+            .query_filtered::<&GridPosition, With<Wall>>()
+            .iter(world)
+            .map(|p| (p.x, p.y))
+            .collect();
+        positions.sort();
+        positions
+    };
+    assert_eq!(walls(7), walls(7));
+    assert_ne!(walls(7), walls(8));
+}
+
+#[test]
+fn taking_the_stairs_records_the_new_depth() {
+    let mut game = HeadlessGame::with_seed(PlayingState::DepthsOfDoom, 7);
+    let depth = |game: &HeadlessGame| {
+        game.world()
+            .resource::<PlayerProgress>()
+            .stat("depths_of_doom.depth")
+    };
+    assert_eq!(depth(&game), 1);
+
+    let world = game.world_mut();
+    let stairs = *world
+        .query_filtered::<&GridPosition, With<Stairs>>()
+        .single(world)
+        .unwrap();
+    let mut player = world
+        .query_filtered::<&mut GridPosition, With<Player>>()
+        .single_mut(world)
+        .unwrap();
+    *player = stairs;
+    game.step(2);
+
+    assert_eq!(depth(&game), 2);
+    let world = game.world_mut();
+    let player = world
+        .query_filtered::<&GridPosition, With<Player>>()
+        .single(world)
+        .unwrap();
+    assert_eq!((player.x, player.y), (0, 0));
+    assert_eq!(
+        world
+            .query_filtered::<(), With<Stairs>>()
+            .iter(world)
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn recorded_run_replays_to_the_same_score() {
+    // Dig a loop through the emeralds, then quit.
+    let mut game = HeadlessGame::with_seed(PlayingState::TunnelMiner, 42);
+    for actions in [
+        [GameAction::Left],
+        [GameAction::Down],
+        [GameAction::Right],
+        [GameAction::Up],
+    ] {
+        game.hold(&actions);
+        game.step(120);
+    }
+    game.quit();
+    game.step(1);
+    let replay = game.last_replay().expect("run wasn't recorded").clone();
+    assert_eq!(replay.seed, 42);
+    assert!(replay.score > 0, "the scripted run should score");
+
+    let mut watched = HeadlessGame::watch(&replay);
+    for _ in 0..replay.len() + 60 {
+        if watched.results().replay.is_some() {
+            break;
+        }
+        watched.step(1);
+    }
+
+    assert_eq!(watched.results().replay, Some(ReplayVerdict::Match));
+    assert_eq!(watched.results().score, replay.score);
+}
+
+#[test]
+fn esc_in_pause_settings_goes_back_to_the_pause_menu() {
+    let mut game = HeadlessGame::new(PlayingState::TunnelMiner);
+    let menu = |game: &HeadlessGame| {
+        game.world()
+            .get_resource::<State<PauseMenuState>>()
+            .map(|state| *state.get())
+    };
+
+    game.tap(&[GameAction::Pause]);
+    game.step(1);
+    assert_eq!(menu(&game), Some(PauseMenuState::Main));
+
+    game.world_mut()
+        .resource_mut::<NextState<PauseMenuState>>()
+        .set(PauseMenuState::Settings);
+    game.step(1);
+    assert_eq!(menu(&game), Some(PauseMenuState::Settings));
+
+    // Esc is both pause and cancel; it leaves the settings, not the pause.
+    game.tap(&[GameAction::Pause, GameAction::Cancel]);
+    game.step(1);
+    assert_eq!(menu(&game), Some(PauseMenuState::Main));
+}
+
+/// Player velocity after `SNAPSHOT_TICK` gameplay ticks.
+#[derive(Resource, Default)]
+struct Snapshot(Option<Vec2>);
+
+const SNAPSHOT_TICK: u32 = 120;
+
+fn snapshot_velocity(
+    mut ticks: Local<u32>,
+    mut snapshot: ResMut<Snapshot>,
+    player: Query<&Velocity, With<Player>>,
+) {
+    *ticks += 1;
+    if *ticks == SNAPSHOT_TICK {
+        snapshot.0 = player.single().ok().map(Velocity::as_vec2);
+    }
+}
+
+#[test]
+fn ice_blitz_skating_does_not_depend_on_frame_rate() {
+    let skate = |frame: Duration| {
+        let mut game = HeadlessGame::new(PlayingState::IceBlitz);
+        game.app_mut()
+            .insert_resource(TimeUpdateStrategy::ManualDuration(frame))
+            .init_resource::<Snapshot>()
+            .add_systems(FixedLast, snapshot_velocity);
+        game.hold(&[GameAction::Right, GameAction::Up]);
+        while game.world().resource::<Snapshot>().0.is_none() {
+            game.step(1);
+        }
+        game.world().resource::<Snapshot>().0
+    };
+
+    // 240 Hz and 30 Hz displays run the same ticks.
+    assert_eq!(
+        skate(Duration::from_micros(4_167)),
+        skate(Duration::from_micros(33_333))
+    );
+}